@@ -0,0 +1,62 @@
+//! 根域名指向其他主机名的 ALIAS 记录。对外统一为 `ALIAS` 类型，
+//! Cloudflare 用根域 CNAME 拉平实现，华为云、阿里云和 DNS.COM 有原生的 ALIAS 类型。
+
+use crate::error::AppError;
+use crate::types::{DnsRecord, Provider};
+
+pub const ALIAS_TYPE: &str = "ALIAS";
+
+pub fn native_type(provider: Provider) -> Option<&'static str> {
+    match provider {
+        Provider::Cloudflare => Some("CNAME"),
+        Provider::Huawei | Provider::Aliyun | Provider::Dnscom => Some(ALIAS_TYPE),
+        _ => None,
+    }
+}
+
+/// 靠根域 CNAME 拉平实现 ALIAS 的服务商，子域名上写进去就是普通 CNAME，读回来也是 CNAME。
+pub fn apex_only(provider: Provider) -> bool {
+    provider == Provider::Cloudflare
+}
+
+/// `ALIAS` 换成服务商的原生类型，其他类型不变。
+pub fn to_native(provider: Provider, record_type: &mut String) -> Result<(), AppError> {
+    if record_type != ALIAS_TYPE {
+        return Ok(());
+    }
+    let native = native_type(provider).ok_or_else(|| AppError::new("unsupported", "该服务商不支持 ALIAS 记录"))?;
+    *record_type = native.to_string();
+    Ok(())
+}
+
+/// Cloudflare 根域上的 CNAME 实际就是 ALIAS，读回来时按 ALIAS 展示。
+pub fn from_native(mut record: DnsRecord) -> DnsRecord {
+    if record.provider == Provider::Cloudflare && record.record_type == "CNAME" && record.name == "@" {
+        record.record_type = ALIAS_TYPE.to_string();
+    }
+    record
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::providers::mock::create_request;
+
+    #[test]
+    fn maps_to_native_types() {
+        let mut record_type = ALIAS_TYPE.to_string();
+        to_native(Provider::Cloudflare, &mut record_type).unwrap();
+        assert_eq!(record_type, "CNAME");
+
+        let mut record_type = ALIAS_TYPE.to_string();
+        to_native(Provider::Dnscom, &mut record_type).unwrap();
+        assert_eq!(record_type, "ALIAS");
+
+        let mut record_type = ALIAS_TYPE.to_string();
+        assert_eq!(to_native(Provider::Rainyun, &mut record_type).unwrap_err().code, "unsupported");
+
+        let mut record_type = create_request("CNAME", "www", "a.example.net").record_type;
+        to_native(Provider::Huawei, &mut record_type).unwrap();
+        assert_eq!(record_type, "CNAME");
+    }
+}
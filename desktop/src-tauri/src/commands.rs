@@ -1,18 +1,34 @@
+use crate::alias;
 use crate::error::AppError;
+use crate::forwarding;
+use crate::idn;
+use crate::lint;
+use crate::presets;
 use crate::providers::{
-    aliyun::AliyunClient, baidu::BaiduClient, cloudflare::CloudflareClient, dnscom::DnscomClient,
-    dnspod::DnspodClient, huawei::HuaweiClient, rainyun::RainyunClient, tencentcloud::TencentCloudClient,
+    aliyun::{self, AliyunClient}, baidu::{self, BaiduClient}, cloudflare::{self, CloudflareClient},
+    dnscom::{self, DnscomClient}, dnspod::{self, DnspodClient}, http, huawei::{self, HuaweiAuth, HuaweiClient},
+    rainyun::{self, RainyunClient}, sts, tencentcloud::{self, TencentCloudClient},
 };
+use crate::resolver::Resolver;
+use crate::search;
+use crate::txt;
 use crate::types::{
-    ConflictStrategy, DnsRecord, DomainItem, DomainStatus, IntegrationsInfo, IntegrationInfoItem,
-    IntegrationTestResult, Provider, RecordCreateRequest, RecordUpdateRequest, VaultStatus,
+    BulkChange, BulkChangeStatus, BulkReplaceRequest, BulkReplaceResult, ConflictStrategy, DnsRecord, DomainItem,
+    DomainStatus, IntegrationsInfo, IntegrationInfoItem, IntegrationTestResult, LintFinding, MailPresetInfo,
+    NetworkProbeResult, NetworkSettings, PresetAction, PresetApplyResult, PresetPreviewItem, Provider,
+    RecordCreateRequest, RecordSearchFailure, RecordSearchHit, RecordSearchQuery, RecordSearchResult, RecordStatus,
+    RecordUpdateRequest, RecordWeight, VaultStatus, ZoneLintOptions,
 };
+use crate::validation::{self, RecordDraft};
 use crate::vault::{
-    self, AliyunCreds, BaiduCreds, CloudflareCreds, DnscomCreds, DnspodCreds, HuaweiCreds, RainyunCreds,
-    TencentCloudCreds,
+    self, AliyunCreds, BaiduCreds, CloudflareCreds, DnscomCreds, DnspodCreds, HuaweiCreds, PlainVault,
+    RainyunCreds, TencentCloudCreds,
 };
 use chrono::Utc;
-use std::net::{Ipv4Addr, Ipv6Addr};
+use futures_util::stream::{self, StreamExt};
+use std::collections::HashMap;
+use std::future::Future;
+use std::time::{Duration, Instant};
 use tauri::AppHandle;
 
 #[tauri::command]
@@ -55,7 +71,11 @@ pub fn vault_initialize(app: AppHandle, master_password: String) -> Result<(), A
 
 #[tauri::command]
 pub fn vault_unlock(app: AppHandle, master_password: String) -> Result<(), AppError> {
-    let _ = vault::decrypt_vault(&app, &master_password)?;
+    let (_, plain) = vault::decrypt_vault(&app, &master_password)?;
+    // 启动时只有不带凭证的代理地址，解锁后补上账号密码
+    if plain.proxy.is_some() {
+        http::configure(vault::load_network_settings(&app, &plain)?);
+    }
     Ok(())
 }
 
@@ -106,10 +126,79 @@ pub fn integrations_get(app: AppHandle, master_password: String) -> Result<Integ
     })
 }
 
+#[tauri::command]
+pub fn network_settings_get(app: AppHandle, master_password: String) -> Result<NetworkSettings, AppError> {
+    let (_, plain) = vault::decrypt_vault(&app, &master_password)?;
+    vault::load_network_settings(&app, &plain)
+}
+
+#[tauri::command]
+pub fn network_settings_save(
+    app: AppHandle,
+    master_password: String,
+    settings: NetworkSettings,
+) -> Result<(), AppError> {
+    http::build_client(&settings)?;
+    vault::save_network_settings(&app, &master_password, &settings)?;
+    http::configure(settings);
+    Ok(())
+}
+
+// 探测只看能否拿到 HTTP 响应，不校验凭证，未鉴权的 4xx 也算连通
+const PROBE_TIMEOUT_SECS: u64 = 10;
+
+#[tauri::command]
+pub async fn network_test(settings: NetworkSettings) -> Result<Vec<NetworkProbeResult>, AppError> {
+    let client = http::build_client(&settings)?;
+    let targets = [
+        (Provider::Cloudflare, cloudflare::API_BASE.to_string()),
+        (Provider::Dnspod, dnspod::API_BASE.to_string()),
+        (Provider::Aliyun, aliyun::API_BASE.to_string()),
+        (Provider::Huawei, format!("https://dns.{}.myhuaweicloud.com", huawei::DEFAULT_REGION)),
+        (Provider::Baidu, baidu::API_BASE.to_string()),
+        (Provider::Dnscom, dnscom::API_BASE.to_string()),
+        (Provider::Rainyun, rainyun::API_BASE.to_string()),
+        (Provider::Tencentcloud, tencentcloud::API_BASE.to_string()),
+    ];
+    let mut results = Vec::new();
+    for (provider, url) in targets {
+        let started = Instant::now();
+        let outcome = client
+            .get(&url)
+            .timeout(Duration::from_secs(PROBE_TIMEOUT_SECS))
+            .send()
+            .await;
+        let elapsed_ms = started.elapsed().as_millis() as u64;
+        results.push(match outcome {
+            Ok(res) => NetworkProbeResult {
+                provider,
+                url,
+                ok: true,
+                http_status: Some(res.status().as_u16()),
+                elapsed_ms,
+                message: format!("HTTP {}", res.status().as_u16()),
+            },
+            Err(e) => NetworkProbeResult {
+                provider,
+                url,
+                ok: false,
+                http_status: None,
+                elapsed_ms,
+                message: AppError::from(e).message,
+            },
+        });
+    }
+    Ok(results)
+}
 
 #[tauri::command]
-pub async fn cloudflare_test(api_token: String) -> Result<IntegrationTestResult, AppError> {
-    let client = CloudflareClient::new(api_token)?;
+pub async fn cloudflare_test(api_token: String, endpoint: Option<String>) -> Result<IntegrationTestResult, AppError> {
+    let creds = CloudflareCreds {
+        api_token,
+        endpoint,
+        last_verified_at: None,
+    };
+    let client = cloudflare_client(&creds)?;
     match client.test().await {
         Ok(_) => Ok(IntegrationTestResult {
             ok: true,
@@ -127,20 +216,27 @@ pub async fn cloudflare_save(
     app: AppHandle,
     master_password: String,
     api_token: String,
+    endpoint: Option<String>,
 ) -> Result<(), AppError> {
-    let client = CloudflareClient::new(api_token.clone())?;
+    let mut creds = CloudflareCreds {
+        api_token,
+        endpoint,
+        last_verified_at: None,
+    };
+    let client = cloudflare_client(&creds)?;
     client.test().await?;
 
     let (file, mut plain) = vault::decrypt_vault(&app, &master_password)?;
-    let now = Utc::now().to_rfc3339();
-    plain.cloudflare = Some(CloudflareCreds {
-        api_token,
-        last_verified_at: Some(now),
-    });
+    creds.last_verified_at = Some(Utc::now().to_rfc3339());
+    plain.cloudflare = Some(creds);
 
     vault::encrypt_and_save_vault(&app, file, &plain, &master_password)
 }
 
+fn cloudflare_client(creds: &CloudflareCreds) -> Result<CloudflareClient, AppError> {
+    CloudflareClient::new(creds.api_token.clone())?.with_endpoint(creds.endpoint.as_deref())
+}
+
 #[tauri::command]
 pub fn cloudflare_clear(app: AppHandle, master_password: String) -> Result<(), AppError> {
     let (file, mut plain) = vault::decrypt_vault(&app, &master_password)?;
@@ -149,8 +245,22 @@ pub fn cloudflare_clear(app: AppHandle, master_password: String) -> Result<(), A
 }
 
 #[tauri::command]
-pub async fn dnspod_test(token_id: String, token: String) -> Result<IntegrationTestResult, AppError> {
-    let client = DnspodClient::new(token_id, token)?;
+pub async fn dnspod_test(
+    token_id: String,
+    token: String,
+    secret_id: Option<String>,
+    secret_key: Option<String>,
+    endpoint: Option<String>,
+) -> Result<IntegrationTestResult, AppError> {
+    let creds = DnspodCreds {
+        token_id,
+        token,
+        secret_id: secret_id.unwrap_or_default(),
+        secret_key: secret_key.unwrap_or_default(),
+        endpoint,
+        last_verified_at: None,
+    };
+    let client = dnspod_client(&creds)?;
     match client.test().await {
         Ok(_) => Ok(IntegrationTestResult {
             ok: true,
@@ -169,21 +279,46 @@ pub async fn dnspod_save(
     master_password: String,
     token_id: String,
     token: String,
+    secret_id: Option<String>,
+    secret_key: Option<String>,
+    endpoint: Option<String>,
 ) -> Result<(), AppError> {
-    let client = DnspodClient::new(token_id.clone(), token.clone())?;
+    let mut creds = DnspodCreds {
+        token_id,
+        token,
+        secret_id: secret_id.unwrap_or_default(),
+        secret_key: secret_key.unwrap_or_default(),
+        endpoint,
+        last_verified_at: None,
+    };
+    let client = dnspod_client(&creds)?;
     client.test().await?;
 
+    // 保存 API 3.0 密钥后不再保留旧版 Token，避免旧接口下线后继续被使用
+    if !creds.secret_id.is_empty() {
+        creds.token_id.clear();
+        creds.token.clear();
+    }
     let (file, mut plain) = vault::decrypt_vault(&app, &master_password)?;
-    let now = Utc::now().to_rfc3339();
-    plain.dnspod = Some(DnspodCreds {
-        token_id,
-        token,
-        last_verified_at: Some(now),
-    });
+    creds.last_verified_at = Some(Utc::now().to_rfc3339());
+    plain.dnspod = Some(creds);
 
     vault::encrypt_and_save_vault(&app, file, &plain, &master_password)
 }
 
+// 旧版 vault 只有 login_token，继续走 dnsapi.cn；有 SecretId/SecretKey 时改走腾讯云 API 3.0
+fn dnspod_client(creds: &DnspodCreds) -> Result<DnspodClient, AppError> {
+    if creds.secret_id.trim().is_empty() != creds.secret_key.trim().is_empty() {
+        return Err(AppError::new("missing_field", "SecretId 和 SecretKey 需要同时填写"));
+    }
+    let client = if !creds.secret_id.trim().is_empty() {
+        DnspodClient::with_api3(creds.secret_id.trim().to_string(), creds.secret_key.trim().to_string())?
+    } else {
+        DnspodClient::new(creds.token_id.clone(), creds.token.clone())?
+    };
+    client.with_endpoint(creds.endpoint.as_deref())
+}
+
 #[tauri::command]
 pub fn dnspod_clear(app: AppHandle, master_password: String) -> Result<(), AppError> {
     let (file, mut plain) = vault::decrypt_vault(&app, &master_password)?;
@@ -192,8 +327,30 @@ pub fn dnspod_clear(app: AppHandle, master_password: String) -> Result<(), AppEr
 }
 
 #[tauri::command]
-pub async fn aliyun_test(access_key_id: String, access_key_secret: String) -> Result<IntegrationTestResult, AppError> {
-    let client = AliyunClient::new(access_key_id, access_key_secret)?;
+pub async fn aliyun_test(
+    access_key_id: String,
+    access_key_secret: String,
+    security_token: Option<String>,
+    role_arn: Option<String>,
+    endpoint: Option<String>,
+) -> Result<IntegrationTestResult, AppError> {
+    let creds = AliyunCreds {
+        access_key_id,
+        access_key_secret,
+        security_token,
+        role_arn,
+        endpoint,
+        last_verified_at: None,
+    };
+    let client = match aliyun_client(&creds).await {
+        Ok(client) => client,
+        Err(e) => {
+            return Ok(IntegrationTestResult {
+                ok: false,
+                message: e.message,
+            })
+        }
+    };
     match client.test().await {
         Ok(_) => Ok(IntegrationTestResult {
             ok: true,
@@ -212,21 +369,43 @@ pub async fn aliyun_save(
     master_password: String,
     access_key_id: String,
     access_key_secret: String,
+    security_token: Option<String>,
+    role_arn: Option<String>,
+    endpoint: Option<String>,
 ) -> Result<(), AppError> {
-    let client = AliyunClient::new(access_key_id.clone(), access_key_secret.clone())?;
+    let mut creds = AliyunCreds {
+        access_key_id,
+        access_key_secret,
+        security_token,
+        role_arn,
+        endpoint,
+        last_verified_at: None,
+    };
+    let client = aliyun_client(&creds).await?;
     client.test().await?;
 
     let (file, mut plain) = vault::decrypt_vault(&app, &master_password)?;
-    let now = Utc::now().to_rfc3339();
-    plain.aliyun = Some(AliyunCreds {
-        access_key_id,
-        access_key_secret,
-        last_verified_at: Some(now),
-    });
+    creds.last_verified_at = Some(Utc::now().to_rfc3339());
+    plain.aliyun = Some(creds);
 
     vault::encrypt_and_save_vault(&app, file, &plain, &master_password)
 }
 
+// 配置了角色 ARN 时先用长期密钥换取临时凭证，临时凭证缓存到临近过期再刷新
+async fn aliyun_client(creds: &AliyunCreds) -> Result<AliyunClient, AppError> {
+    let client = AliyunClient::new(creds.access_key_id.clone(), creds.access_key_secret.clone())?
+        .with_security_token(creds.security_token.clone())
+        .with_endpoint(creds.endpoint.as_deref())?;
+    let role_arn = match creds.role_arn.as_deref().map(str::trim).filter(|r| !r.is_empty()) {
+        Some(role_arn) => role_arn,
+        None => return Ok(client),
+    };
+    let temp = sts::get_or_assume("aliyun", &creds.access_key_id, role_arn, || client.assume_role(role_arn)).await?;
+    AliyunClient::new(temp.access_key_id.clone(), temp.access_key_secret.clone())?
+        .with_security_token(Some(temp.security_token.clone()))
+        .with_endpoint(creds.endpoint.as_deref())
+}
+
 #[tauri::command]
 pub fn aliyun_clear(app: AppHandle, master_password: String) -> Result<(), AppError> {
     let (file, mut plain) = vault::decrypt_vault(&app, &master_password)?;
@@ -235,8 +414,24 @@ pub fn aliyun_clear(app: AppHandle, master_password: String) -> Result<(), AppEr
 }
 
 #[tauri::command]
-pub async fn huawei_test(token: String) -> Result<IntegrationTestResult, AppError> {
-    let client = HuaweiClient::new(token)?;
+pub async fn huawei_test(
+    token: String,
+    access_key_id: Option<String>,
+    secret_access_key: Option<String>,
+    region: Option<String>,
+    project_id: Option<String>,
+    endpoint: Option<String>,
+) -> Result<IntegrationTestResult, AppError> {
+    let creds = HuaweiCreds {
+        token,
+        access_key_id: access_key_id.unwrap_or_default(),
+        secret_access_key: secret_access_key.unwrap_or_default(),
+        region,
+        project_id,
+        endpoint,
+        last_verified_at: None,
+    };
+    let client = huawei_client(&creds)?;
     match client.test().await {
         Ok(_) => Ok(IntegrationTestResult {
             ok: true,
@@ -250,20 +445,55 @@ pub async fn huawei_test(token: String) -> Result<IntegrationTestResult, AppErro
 }
 
 #[tauri::command]
-pub async fn huawei_save(app: AppHandle, master_password: String, token: String) -> Result<(), AppError> {
-    let client = HuaweiClient::new(token.clone())?;
+#[allow(clippy::too_many_arguments)]
+pub async fn huawei_save(
+    app: AppHandle,
+    master_password: String,
+    token: String,
+    access_key_id: Option<String>,
+    secret_access_key: Option<String>,
+    region: Option<String>,
+    project_id: Option<String>,
+    endpoint: Option<String>,
+) -> Result<(), AppError> {
+    let mut creds = HuaweiCreds {
+        token,
+        access_key_id: access_key_id.unwrap_or_default(),
+        secret_access_key: secret_access_key.unwrap_or_default(),
+        region,
+        project_id,
+        endpoint,
+        last_verified_at: None,
+    };
+    let client = huawei_client(&creds)?;
     client.test().await?;
 
     let (file, mut plain) = vault::decrypt_vault(&app, &master_password)?;
-    let now = Utc::now().to_rfc3339();
-    plain.huawei = Some(HuaweiCreds {
-        token,
-        last_verified_at: Some(now),
-    });
+    creds.last_verified_at = Some(Utc::now().to_rfc3339());
+    plain.huawei = Some(creds);
 
     vault::encrypt_and_save_vault(&app, file, &plain, &master_password)
 }
 
+// 填了 AK/SK 时优先使用签名认证，否则退回 X-Auth-Token
+fn huawei_client(creds: &HuaweiCreds) -> Result<HuaweiClient, AppError> {
+    let auth = if !creds.access_key_id.trim().is_empty() {
+        if creds.secret_access_key.trim().is_empty() {
+            return Err(AppError::new("invalid_input", "Huawei Cloud secret access key is required"));
+        }
+        HuaweiAuth::AkSk {
+            access_key_id: creds.access_key_id.trim().to_string(),
+            secret_access_key: creds.secret_access_key.trim().to_string(),
+            project_id: creds.project_id.clone(),
+        }
+    } else if !creds.token.trim().is_empty() {
+        HuaweiAuth::Token(creds.token.trim().to_string())
+    } else {
+        return Err(AppError::new("invalid_input", "Huawei Cloud token or access key is required"));
+    };
+    HuaweiClient::new(auth, creds.region.clone())?.with_endpoint(creds.endpoint.as_deref())
+}
+
 #[tauri::command]
 pub fn huawei_clear(app: AppHandle, master_password: String) -> Result<(), AppError> {
     let (file, mut plain) = vault::decrypt_vault(&app, &master_password)?;
@@ -272,8 +502,20 @@ pub fn huawei_clear(app: AppHandle, master_password: String) -> Result<(), AppEr
 }
 
 #[tauri::command]
-pub async fn baidu_test(access_key_id: String, secret_access_key: String) -> Result<IntegrationTestResult, AppError> {
-    let client = BaiduClient::new(access_key_id, secret_access_key)?;
+pub async fn baidu_test(
+    access_key_id: String,
+    secret_access_key: String,
+    security_token: Option<String>,
+    endpoint: Option<String>,
+) -> Result<IntegrationTestResult, AppError> {
+    let creds = BaiduCreds {
+        access_key_id,
+        secret_access_key,
+        security_token,
+        endpoint,
+        last_verified_at: None,
+    };
+    let client = baidu_client(&creds)?;
     match client.test().await {
         Ok(_) => Ok(IntegrationTestResult {
             ok: true,
@@ -292,21 +534,32 @@ pub async fn baidu_save(
     master_password: String,
     access_key_id: String,
     secret_access_key: String,
+    security_token: Option<String>,
+    endpoint: Option<String>,
 ) -> Result<(), AppError> {
-    let client = BaiduClient::new(access_key_id.clone(), secret_access_key.clone())?;
+    let mut creds = BaiduCreds {
+        access_key_id,
+        secret_access_key,
+        security_token,
+        endpoint,
+        last_verified_at: None,
+    };
+    let client = baidu_client(&creds)?;
     client.test().await?;
 
     let (file, mut plain) = vault::decrypt_vault(&app, &master_password)?;
-    let now = Utc::now().to_rfc3339();
-    plain.baidu = Some(BaiduCreds {
-        access_key_id,
-        secret_access_key,
-        last_verified_at: Some(now),
-    });
+    creds.last_verified_at = Some(Utc::now().to_rfc3339());
+    plain.baidu = Some(creds);
 
     vault::encrypt_and_save_vault(&app, file, &plain, &master_password)
 }
 
+fn baidu_client(creds: &BaiduCreds) -> Result<BaiduClient, AppError> {
+    BaiduClient::new(creds.access_key_id.clone(), creds.secret_access_key.clone())?
+        .with_security_token(creds.security_token.clone())
+        .with_endpoint(creds.endpoint.as_deref())
+}
+
 #[tauri::command]
 pub fn baidu_clear(app: AppHandle, master_password: String) -> Result<(), AppError> {
     let (file, mut plain) = vault::decrypt_vault(&app, &master_password)?;
@@ -315,8 +568,18 @@ pub fn baidu_clear(app: AppHandle, master_password: String) -> Result<(), AppErr
 }
 
 #[tauri::command]
-pub async fn dnscom_test(api_key: String, api_secret: String) -> Result<IntegrationTestResult, AppError> {
-    let client = DnscomClient::new(api_key, api_secret)?;
+pub async fn dnscom_test(
+    api_key: String,
+    api_secret: String,
+    endpoint: Option<String>,
+) -> Result<IntegrationTestResult, AppError> {
+    let creds = DnscomCreds {
+        api_key,
+        api_secret,
+        endpoint,
+        last_verified_at: None,
+    };
+    let client = dnscom_client(&creds)?;
     match client.test().await {
         Ok(_) => Ok(IntegrationTestResult {
             ok: true,
@@ -335,21 +598,28 @@ pub async fn dnscom_save(
     master_password: String,
     api_key: String,
     api_secret: String,
+    endpoint: Option<String>,
 ) -> Result<(), AppError> {
-    let client = DnscomClient::new(api_key.clone(), api_secret.clone())?;
+    let mut creds = DnscomCreds {
+        api_key,
+        api_secret,
+        endpoint,
+        last_verified_at: None,
+    };
+    let client = dnscom_client(&creds)?;
     client.test().await?;
 
     let (file, mut plain) = vault::decrypt_vault(&app, &master_password)?;
-    let now = Utc::now().to_rfc3339();
-    plain.dnscom = Some(DnscomCreds {
-        api_key,
-        api_secret,
-        last_verified_at: Some(now),
-    });
+    creds.last_verified_at = Some(Utc::now().to_rfc3339());
+    plain.dnscom = Some(creds);
 
     vault::encrypt_and_save_vault(&app, file, &plain, &master_password)
 }
 
+fn dnscom_client(creds: &DnscomCreds) -> Result<DnscomClient, AppError> {
+    DnscomClient::new(creds.api_key.clone(), creds.api_secret.clone())?.with_endpoint(creds.endpoint.as_deref())
+}
+
 #[tauri::command]
 pub fn dnscom_clear(app: AppHandle, master_password: String) -> Result<(), AppError> {
     let (file, mut plain) = vault::decrypt_vault(&app, &master_password)?;
@@ -358,8 +628,13 @@ pub fn dnscom_clear(app: AppHandle, master_password: String) -> Result<(), AppEr
 }
 
 #[tauri::command]
-pub async fn rainyun_test(api_key: String) -> Result<IntegrationTestResult, AppError> {
-    let client = RainyunClient::new(api_key)?;
+pub async fn rainyun_test(api_key: String, endpoint: Option<String>) -> Result<IntegrationTestResult, AppError> {
+    let creds = RainyunCreds {
+        api_key,
+        endpoint,
+        last_verified_at: None,
+    };
+    let client = rainyun_client(&creds)?;
     match client.test().await {
         Ok(_) => Ok(IntegrationTestResult {
             ok: true,
@@ -373,20 +648,31 @@ pub async fn rainyun_test(api_key: String) -> Result<IntegrationTestResult, AppE
 }
 
 #[tauri::command]
-pub async fn rainyun_save(app: AppHandle, master_password: String, api_key: String) -> Result<(), AppError> {
-    let client = RainyunClient::new(api_key.clone())?;
+pub async fn rainyun_save(
+    app: AppHandle,
+    master_password: String,
+    api_key: String,
+    endpoint: Option<String>,
+) -> Result<(), AppError> {
+    let mut creds = RainyunCreds {
+        api_key,
+        endpoint,
+        last_verified_at: None,
+    };
+    let client = rainyun_client(&creds)?;
     client.test().await?;
 
     let (file, mut plain) = vault::decrypt_vault(&app, &master_password)?;
-    let now = Utc::now().to_rfc3339();
-    plain.rainyun = Some(RainyunCreds {
-        api_key,
-        last_verified_at: Some(now),
-    });
+    creds.last_verified_at = Some(Utc::now().to_rfc3339());
+    plain.rainyun = Some(creds);
 
     vault::encrypt_and_save_vault(&app, file, &plain, &master_password)
 }
 
+fn rainyun_client(creds: &RainyunCreds) -> Result<RainyunClient, AppError> {
+    RainyunClient::new(creds.api_key.clone())?.with_endpoint(creds.endpoint.as_deref())
+}
+
 #[tauri::command]
 pub fn rainyun_clear(app: AppHandle, master_password: String) -> Result<(), AppError> {
     let (file, mut plain) = vault::decrypt_vault(&app, &master_password)?;
@@ -395,8 +681,30 @@ pub fn rainyun_clear(app: AppHandle, master_password: String) -> Result<(), AppE
 }
 
 #[tauri::command]
-pub async fn tencentcloud_test(secret_id: String, secret_key: String) -> Result<IntegrationTestResult, AppError> {
-    let client = TencentCloudClient::new(secret_id, secret_key)?;
+pub async fn tencentcloud_test(
+    secret_id: String,
+    secret_key: String,
+    token: Option<String>,
+    role_arn: Option<String>,
+    endpoint: Option<String>,
+) -> Result<IntegrationTestResult, AppError> {
+    let creds = TencentCloudCreds {
+        secret_key,
+        secret_id,
+        token,
+        role_arn,
+        endpoint,
+        last_verified_at: None,
+    };
+    let client = match tencentcloud_client(&creds).await {
+        Ok(client) => client,
+        Err(e) => {
+            return Ok(IntegrationTestResult {
+                ok: false,
+                message: e.message,
+            })
+        }
+    };
     match client.test().await {
         Ok(_) => Ok(IntegrationTestResult {
             ok: true,
@@ -415,21 +723,42 @@ pub async fn tencentcloud_save(
     master_password: String,
     secret_id: String,
     secret_key: String,
+    token: Option<String>,
+    role_arn: Option<String>,
+    endpoint: Option<String>,
 ) -> Result<(), AppError> {
-    let client = TencentCloudClient::new(secret_id.clone(), secret_key.clone())?;
+    let mut creds = TencentCloudCreds {
+        secret_key,
+        secret_id,
+        token,
+        role_arn,
+        endpoint,
+        last_verified_at: None,
+    };
+    let client = tencentcloud_client(&creds).await?;
     client.test().await?;
 
     let (file, mut plain) = vault::decrypt_vault(&app, &master_password)?;
-    let now = Utc::now().to_rfc3339();
-    plain.tencentcloud = Some(TencentCloudCreds {
-        secret_id,
-        secret_key,
-        last_verified_at: Some(now),
-    });
+    creds.last_verified_at = Some(Utc::now().to_rfc3339());
+    plain.tencentcloud = Some(creds);
 
     vault::encrypt_and_save_vault(&app, file, &plain, &master_password)
 }
 
+async fn tencentcloud_client(creds: &TencentCloudCreds) -> Result<TencentCloudClient, AppError> {
+    let client = TencentCloudClient::new(creds.secret_id.clone(), creds.secret_key.clone())?
+        .with_security_token(creds.token.clone())
+        .with_endpoint(creds.endpoint.as_deref())?;
+    let role_arn = match creds.role_arn.as_deref().map(str::trim).filter(|r| !r.is_empty()) {
+        Some(role_arn) => role_arn,
+        None => return Ok(client),
+    };
+    let temp = sts::get_or_assume("tencentcloud", &creds.secret_id, role_arn, || client.assume_role(role_arn)).await?;
+    TencentCloudClient::new(temp.access_key_id.clone(), temp.access_key_secret.clone())?
+        .with_security_token(Some(temp.security_token.clone()))
+        .with_endpoint(creds.endpoint.as_deref())
+}
+
 #[tauri::command]
 pub fn tencentcloud_clear(app: AppHandle, master_password: String) -> Result<(), AppError> {
     let (file, mut plain) = vault::decrypt_vault(&app, &master_password)?;
@@ -445,6 +774,14 @@ pub async fn domains_list(
     search: Option<String>,
 ) -> Result<Vec<DomainItem>, AppError> {
     let (_, plain) = vault::decrypt_vault(&app, &master_password)?;
+    run_domains_list(plain, provider_filter, search).await
+}
+
+async fn run_domains_list(
+    plain: PlainVault,
+    provider_filter: Option<Provider>,
+    search: Option<String>,
+) -> Result<Vec<DomainItem>, AppError> {
     let mut items: Vec<DomainItem> = Vec::new();
 
     let search_norm = search.unwrap_or_default().to_lowercase();
@@ -457,22 +794,6 @@ pub async fn domains_list(
     let wants_rainyun = provider_filter.is_none() || provider_filter == Some(Provider::Rainyun);
     let wants_tencentcloud = provider_filter.is_none() || provider_filter == Some(Provider::Tencentcloud);
 
-    let make_error_item = |provider: Provider, display_name: &str, e: AppError| {
-        let status = match e.code.as_str() {
-            "auth_failed" => DomainStatus::AuthFailed,
-            "unreachable" | "timeout" => DomainStatus::Unreachable,
-            _ => DomainStatus::FetchFailed,
-        };
-        DomainItem {
-            provider,
-            name: format!("{display_name} (错误: {})", e.message),
-            provider_id: "".to_string(),
-            status,
-            records_count: None,
-            last_changed_at: None,
-        }
-    };
-
     let cf_creds = plain.cloudflare.clone();
     let dp_creds = plain.dnspod.clone();
     let aliyun_creds = plain.aliyun.clone();
@@ -484,10 +805,10 @@ pub async fn domains_list(
 
     if wants_cloudflare {
         if let Some(cf) = cf_creds {
-            let cf_client = CloudflareClient::new(cf.api_token.clone())?;
+            let cf_client = cloudflare_client(&cf)?;
             match cf_client.list_domains().await {
                 Ok(mut v) => items.append(&mut v),
-                Err(e) => items.push(make_error_item(Provider::Cloudflare, "Cloudflare", e)),
+                Err(e) => items.push(domain_error_item(Provider::Cloudflare, "Cloudflare", e)),
             }
         } else {
             items.push(DomainItem {
@@ -497,16 +818,17 @@ pub async fn domains_list(
                 status: DomainStatus::NotConfigured,
                 records_count: None,
                 last_changed_at: None,
+                display_name: None,
             });
         }
     }
 
     if wants_dnspod {
         if let Some(dp) = dp_creds {
-            let dp_client = DnspodClient::new(dp.token_id.clone(), dp.token.clone())?;
+            let dp_client = dnspod_client(&dp)?;
             match dp_client.list_domains().await {
                 Ok(mut v) => items.append(&mut v),
-                Err(e) => items.push(make_error_item(Provider::Dnspod, "DNSPod", e)),
+                Err(e) => items.push(domain_error_item(Provider::Dnspod, "DNSPod", e)),
             }
         } else {
             items.push(DomainItem {
@@ -516,16 +838,20 @@ pub async fn domains_list(
                 status: DomainStatus::NotConfigured,
                 records_count: None,
                 last_changed_at: None,
+                display_name: None,
             });
         }
     }
 
     if wants_aliyun {
         if let Some(aliyun) = aliyun_creds {
-            let client = AliyunClient::new(aliyun.access_key_id.clone(), aliyun.access_key_secret.clone())?;
-            match client.list_domains().await {
+            let listed = match aliyun_client(&aliyun).await {
+                Ok(client) => client.list_domains().await,
+                Err(e) => Err(e),
+            };
+            match listed {
                 Ok(mut v) => items.append(&mut v),
-                Err(e) => items.push(make_error_item(Provider::Aliyun, "Aliyun", e)),
+                Err(e) => items.push(domain_error_item(Provider::Aliyun, "Aliyun", e)),
             }
         } else {
             items.push(DomainItem {
@@ -535,16 +861,17 @@ pub async fn domains_list(
                 status: DomainStatus::NotConfigured,
                 records_count: None,
                 last_changed_at: None,
+                display_name: None,
             });
         }
     }
 
     if wants_huawei {
         if let Some(huawei) = huawei_creds {
-            let client = HuaweiClient::new(huawei.token.clone())?;
+            let client = huawei_client(&huawei)?;
             match client.list_domains().await {
                 Ok(mut v) => items.append(&mut v),
-                Err(e) => items.push(make_error_item(Provider::Huawei, "华为云DNS", e)),
+                Err(e) => items.push(domain_error_item(Provider::Huawei, "华为云DNS", e)),
             }
         } else {
             items.push(DomainItem {
@@ -554,16 +881,17 @@ pub async fn domains_list(
                 status: DomainStatus::NotConfigured,
                 records_count: None,
                 last_changed_at: None,
+                display_name: None,
             });
         }
     }
 
     if wants_baidu {
         if let Some(baidu) = baidu_creds {
-            let client = BaiduClient::new(baidu.access_key_id.clone(), baidu.secret_access_key.clone())?;
+            let client = baidu_client(&baidu)?;
             match client.list_domains().await {
                 Ok(mut v) => items.append(&mut v),
-                Err(e) => items.push(make_error_item(Provider::Baidu, "百度智能云DNS", e)),
+                Err(e) => items.push(domain_error_item(Provider::Baidu, "百度智能云DNS", e)),
             }
         } else {
             items.push(DomainItem {
@@ -573,16 +901,17 @@ pub async fn domains_list(
                 status: DomainStatus::NotConfigured,
                 records_count: None,
                 last_changed_at: None,
+                display_name: None,
             });
         }
     }
 
     if wants_dnscom {
         if let Some(dnscom) = dnscom_creds {
-            let client = DnscomClient::new(dnscom.api_key.clone(), dnscom.api_secret.clone())?;
+            let client = dnscom_client(&dnscom)?;
             match client.list_domains().await {
                 Ok(mut v) => items.append(&mut v),
-                Err(e) => items.push(make_error_item(Provider::Dnscom, "DNS.COM", e)),
+                Err(e) => items.push(domain_error_item(Provider::Dnscom, "DNS.COM", e)),
             }
         } else {
             items.push(DomainItem {
@@ -592,16 +921,17 @@ pub async fn domains_list(
                 status: DomainStatus::NotConfigured,
                 records_count: None,
                 last_changed_at: None,
+                display_name: None,
             });
         }
     }
 
     if wants_rainyun {
         if let Some(rainyun) = rainyun_creds {
-            let client = RainyunClient::new(rainyun.api_key.clone())?;
+            let client = rainyun_client(&rainyun)?;
             match client.list_domains().await {
                 Ok(mut v) => items.append(&mut v),
-                Err(e) => items.push(make_error_item(Provider::Rainyun, "Rainyun", e)),
+                Err(e) => items.push(domain_error_item(Provider::Rainyun, "Rainyun", e)),
             }
         } else {
             items.push(DomainItem {
@@ -611,16 +941,20 @@ pub async fn domains_list(
                 status: DomainStatus::NotConfigured,
                 records_count: None,
                 last_changed_at: None,
+                display_name: None,
             });
         }
     }
 
     if wants_tencentcloud {
         if let Some(tc) = tencentcloud_creds {
-            let client = TencentCloudClient::new(tc.secret_id.clone(), tc.secret_key.clone())?;
-            match client.list_domains().await {
+            let listed = match tencentcloud_client(&tc).await {
+                Ok(client) => client.list_domains().await,
+                Err(e) => Err(e),
+            };
+            match listed {
                 Ok(mut v) => items.append(&mut v),
-                Err(e) => items.push(make_error_item(Provider::Tencentcloud, "腾讯云DNS", e)),
+                Err(e) => items.push(domain_error_item(Provider::Tencentcloud, "腾讯云DNS", e)),
             }
         } else {
             items.push(DomainItem {
@@ -630,20 +964,54 @@ pub async fn domains_list(
                 status: DomainStatus::NotConfigured,
                 records_count: None,
                 last_changed_at: None,
+                display_name: None,
             });
         }
     }
 
+    let items = idn::display_domains(items);
     if search_norm.is_empty() {
         return Ok(items);
     }
 
     Ok(items
         .into_iter()
-        .filter(|d| d.name.to_lowercase().contains(&search_norm))
+        .filter(|d| {
+            d.name.to_lowercase().contains(&search_norm)
+                || d.display_name.as_deref().is_some_and(|n| n.to_lowercase().contains(&search_norm))
+        })
         .collect())
 }
 
+fn domain_error_item(provider: Provider, display_name: &str, e: AppError) -> DomainItem {
+    let e = e.in_context(provider, "domains_list");
+    let status = if e.is_auth_failure() {
+        DomainStatus::AuthFailed
+    } else if e.is_unreachable() {
+        DomainStatus::Unreachable
+    } else {
+        DomainStatus::FetchFailed
+    };
+    DomainItem {
+        provider,
+        name: format!("{display_name} (错误: {})", e.message),
+        provider_id: "".to_string(),
+        status,
+        records_count: None,
+        last_changed_at: None,
+        display_name: None,
+    }
+}
+
+/// 把服务商原生的记录换成界面上展示的形式：URL 转发和 ALIAS 用统一的类型名，域名附带 Unicode 形式。
+fn present_record(record: DnsRecord) -> DnsRecord {
+    idn::display_record(alias::from_native(forwarding::from_native(record)))
+}
+
+fn present_records(records: Vec<DnsRecord>) -> Vec<DnsRecord> {
+    records.into_iter().map(present_record).collect()
+}
+
 #[tauri::command]
 pub async fn records_list(
     app: AppHandle,
@@ -653,66 +1021,82 @@ pub async fn records_list(
     domain_name: String,
 ) -> Result<Vec<DnsRecord>, AppError> {
     let (_, plain) = vault::decrypt_vault(&app, &master_password)?;
+    let domain_name = idn::to_ascii(&domain_name)?;
+    run_records_list(plain, provider, domain_id, domain_name)
+        .await
+        .map(present_records)
+        .map_err(|e| e.in_context(provider, "records_list"))
+}
+
+async fn run_records_list(
+    plain: PlainVault,
+    provider: Provider,
+    domain_id: String,
+    domain_name: String,
+) -> Result<Vec<DnsRecord>, AppError> {
     match provider {
         Provider::Cloudflare => {
             let cf = plain
                 .cloudflare
                 .ok_or_else(|| AppError::new("not_configured", "Cloudflare is not configured"))?;
-            let cf_client = CloudflareClient::new(cf.api_token.clone())?;
+            let cf_client = cloudflare_client(&cf)?;
             cf_client.list_records(&domain_id, &domain_name).await
         }
         Provider::Dnspod => {
             let dp = plain
                 .dnspod
                 .ok_or_else(|| AppError::new("not_configured", "DNSPod is not configured"))?;
-            let dp_client = DnspodClient::new(dp.token_id.clone(), dp.token.clone())?;
+            let dp_client = dnspod_client(&dp)?;
             dp_client.list_records(&domain_id, &domain_name).await
         }
         Provider::Aliyun => {
             let aliyun = plain
                 .aliyun
                 .ok_or_else(|| AppError::new("not_configured", "Aliyun is not configured"))?;
-            let client = AliyunClient::new(aliyun.access_key_id.clone(), aliyun.access_key_secret.clone())?;
+            let client = aliyun_client(&aliyun).await?;
             client.list_records(&domain_id, &domain_name).await
         }
         Provider::Huawei => {
             let huawei = plain
                 .huawei
                 .ok_or_else(|| AppError::new("not_configured", "Huawei Cloud is not configured"))?;
-            let client = HuaweiClient::new(huawei.token.clone())?;
+            let client = huawei_client(&huawei)?;
             client.list_records(&domain_id, &domain_name).await
         }
         Provider::Baidu => {
             let baidu = plain
                 .baidu
                 .ok_or_else(|| AppError::new("not_configured", "Baidu Cloud is not configured"))?;
-            let client = BaiduClient::new(baidu.access_key_id.clone(), baidu.secret_access_key.clone())?;
+            let client = baidu_client(&baidu)?;
             client.list_records(&domain_id, &domain_name).await
         }
         Provider::Dnscom => {
             let dnscom = plain
                 .dnscom
                 .ok_or_else(|| AppError::new("not_configured", "DNS.COM is not configured"))?;
-            let client = DnscomClient::new(dnscom.api_key.clone(), dnscom.api_secret.clone())?;
+            let client = dnscom_client(&dnscom)?;
             client.list_records(&domain_id, &domain_name).await
         }
         Provider::Rainyun => {
             let rainyun = plain
                 .rainyun
                 .ok_or_else(|| AppError::new("not_configured", "Rainyun is not configured"))?;
-            let client = RainyunClient::new(rainyun.api_key.clone())?;
+            let client = rainyun_client(&rainyun)?;
             client.list_records(&domain_id, &domain_name).await
         }
         Provider::Tencentcloud => {
             let tencentcloud = plain
                 .tencentcloud
                 .ok_or_else(|| AppError::new("not_configured", "Tencent Cloud is not configured"))?;
-            let client = TencentCloudClient::new(tencentcloud.secret_id.clone(), tencentcloud.secret_key.clone())?;
+            let client = tencentcloud_client(&tencentcloud).await?;
             client.list_records(&domain_id, &domain_name).await
         }
     }
 }
 
+/// 新建记录，按冲突策略处理同名同类型的已有记录。
+/// replace_all 在 Cloudflare 和华为云上是一次性替换；其他服务商先覆盖一条再删除其余记录，
+/// 不是原子操作，删除失败时返回 partial_failure。
 #[tauri::command]
 pub async fn record_create(
     app: AppHandle,
@@ -723,377 +1107,372 @@ pub async fn record_create(
     req: RecordCreateRequest,
 ) -> Result<DnsRecord, AppError> {
     let (_, plain) = vault::decrypt_vault(&app, &master_password)?;
-    validate_record_request(&req)?;
+    let domain_name = idn::to_ascii(&domain_name)?;
+    run_record_create(plain, provider, domain_id, domain_name, req)
+        .await
+        .map(present_record)
+        .map_err(|e| e.in_context(provider, "record_create"))
+}
+
+async fn run_record_create(
+    plain: PlainVault,
+    provider: Provider,
+    domain_id: String,
+    domain_name: String,
+    mut req: RecordCreateRequest,
+) -> Result<DnsRecord, AppError> {
+    let _invalidation = search::invalidate_on_exit(&plain, provider, &domain_id);
+    ascii_record_names(&req.record_type, &mut req.name, &mut req.content)?;
+    if req.record_type == "TXT" {
+        req.content = txt::decode(&req.content);
+    }
+    validation::validate_record(provider, &domain_name, &RecordDraft::from(&req))?;
+    forwarding::to_native(provider, &mut req.record_type, &req.extra)?;
+    alias::to_native(provider, &mut req.record_type)?;
 
     match provider {
         Provider::Cloudflare => {
             let cf = plain
                 .cloudflare
                 .ok_or_else(|| AppError::new("not_configured", "Cloudflare is not configured"))?;
-            let cf_client = CloudflareClient::new(cf.api_token.clone())?;
-            let conflict_ids = cf_client
-                .find_conflict_ids(&domain_id, &domain_name, &req.record_type, &req.name)
+            let cf_client = cloudflare_client(&cf)?;
+            // Cloudflare 服务端会拒绝和 CNAME 冲突的记录，不必为此拉取整个 zone
+            let existing = cf_client
+                .find_conflicts(&domain_id, &domain_name, &req.record_type, &req.name)
                 .await?;
-            if !conflict_ids.is_empty() {
-                match req.conflict_strategy {
-                    ConflictStrategy::DoNotCreate => {
-                        return Err(AppError::new("conflict", "Record already exists"));
-                    }
-                    ConflictStrategy::Overwrite => {
-                        if conflict_ids.len() != 1 {
-                            return Err(AppError::new("conflict", "Multiple conflicting records found"));
-                        }
-                        let update = RecordUpdateRequest {
-                            id: conflict_ids[0].clone(),
-                            record_type: req.record_type,
-                            name: req.name,
-                            content: req.content,
-                            ttl: req.ttl,
-                            mx_priority: req.mx_priority,
-                            srv_priority: req.srv_priority,
-                            srv_weight: req.srv_weight,
-                            srv_port: req.srv_port,
-                            caa_flags: req.caa_flags,
-                            caa_tag: req.caa_tag,
-                        };
-                        return cf_client.update_record(&domain_id, &domain_name, &update).await;
-                    }
+            let conflicts: Vec<&DnsRecord> = existing.iter().collect();
+            match plan_record_create(&conflicts, &req)? {
+                CreatePlan::Create => cf_client.create_record(&domain_id, &domain_name, &req).await,
+                CreatePlan::Update(update) => cf_client.update_record(&domain_id, &domain_name, &update).await,
+                CreatePlan::ReplaceAll { update, delete_ids } => {
+                    // Cloudflare 的批量接口在一个事务内完成删除和覆盖
+                    cf_client
+                        .replace_records(&domain_id, &domain_name, &update, &delete_ids)
+                        .await
                 }
             }
-            cf_client.create_record(&domain_id, &domain_name, &req).await
         }
         Provider::Dnspod => {
             let dp = plain
                 .dnspod
                 .ok_or_else(|| AppError::new("not_configured", "DNSPod is not configured"))?;
-            let dp_client = DnspodClient::new(dp.token_id.clone(), dp.token.clone())?;
+            let dp_client = dnspod_client(&dp)?;
             let existing = dp_client.list_records(&domain_id, &domain_name).await?;
+            validation::ensure_exclusive(&existing, &req.record_type, &req.name, None)?;
             let conflicts: Vec<&DnsRecord> = existing
                 .iter()
-                .filter(|r| r.record_type == req.record_type && r.name == req.name)
+                .filter(|r| r.record_type == req.record_type && idn::same_name(&r.name, &req.name))
                 .collect();
-            if !conflicts.is_empty() {
-                match req.conflict_strategy {
-                    ConflictStrategy::DoNotCreate => {
-                        return Err(AppError::new("conflict", "Record already exists"));
-                    }
-                    ConflictStrategy::Overwrite => {
-                        if conflicts.len() != 1 {
-                            return Err(AppError::new("conflict", "Multiple conflicting records found"));
-                        }
-                        let update = RecordUpdateRequest {
-                            id: conflicts[0].id.clone(),
-                            record_type: req.record_type,
-                            name: req.name,
-                            content: req.content,
-                            ttl: req.ttl,
-                            mx_priority: req.mx_priority,
-                            srv_priority: req.srv_priority,
-                            srv_weight: req.srv_weight,
-                            srv_port: req.srv_port,
-                            caa_flags: req.caa_flags,
-                            caa_tag: req.caa_tag,
-                        };
-                        return dp_client.update_record(&domain_id, &domain_name, &update).await;
-                    }
+            match plan_record_create(&conflicts, &req)? {
+                CreatePlan::Create => dp_client.create_record(&domain_id, &domain_name, &req).await,
+                CreatePlan::Update(update) => dp_client.update_record(&domain_id, &domain_name, &update).await,
+                CreatePlan::ReplaceAll { update, delete_ids } => {
+                    replace_all(
+                        || dp_client.update_record(&domain_id, &domain_name, &update),
+                        &delete_ids,
+                        |id| dp_client.delete_record(&domain_id, &domain_name, id),
+                    )
+                    .await
                 }
             }
-            dp_client.create_record(&domain_id, &domain_name, &req).await
         }
         Provider::Aliyun => {
             let aliyun = plain
                 .aliyun
                 .ok_or_else(|| AppError::new("not_configured", "Aliyun is not configured"))?;
-            let client = AliyunClient::new(aliyun.access_key_id.clone(), aliyun.access_key_secret.clone())?;
+            let client = aliyun_client(&aliyun).await?;
             let existing = client.list_records(&domain_id, &domain_name).await?;
+            validation::ensure_exclusive(&existing, &req.record_type, &req.name, None)?;
             let conflicts: Vec<&DnsRecord> = existing
                 .iter()
-                .filter(|r| r.record_type == req.record_type && r.name == req.name)
+                .filter(|r| r.record_type == req.record_type && idn::same_name(&r.name, &req.name))
                 .collect();
-            if !conflicts.is_empty() {
-                match req.conflict_strategy {
-                    ConflictStrategy::DoNotCreate => {
-                        return Err(AppError::new("conflict", "Record already exists"));
-                    }
-                    ConflictStrategy::Overwrite => {
-                        if conflicts.len() != 1 {
-                            return Err(AppError::new("conflict", "Multiple conflicting records found"));
-                        }
-                        let update = RecordUpdateRequest {
-                            id: conflicts[0].id.clone(),
-                            record_type: req.record_type,
-                            name: req.name,
-                            content: req.content,
-                            ttl: req.ttl,
-                            mx_priority: req.mx_priority,
-                            srv_priority: req.srv_priority,
-                            srv_weight: req.srv_weight,
-                            srv_port: req.srv_port,
-                            caa_flags: req.caa_flags,
-                            caa_tag: req.caa_tag,
-                        };
-                        return client.update_record(&domain_id, &domain_name, &update).await;
-                    }
+            match plan_record_create(&conflicts, &req)? {
+                CreatePlan::Create => client.create_record(&domain_id, &domain_name, &req).await,
+                CreatePlan::Update(update) => client.update_record(&domain_id, &domain_name, &update).await,
+                CreatePlan::ReplaceAll { update, delete_ids } => {
+                    replace_all(
+                        || client.update_record(&domain_id, &domain_name, &update),
+                        &delete_ids,
+                        |id| client.delete_record(&domain_id, id),
+                    )
+                    .await
                 }
             }
-            client.create_record(&domain_id, &domain_name, &req).await
         }
         Provider::Huawei => {
             let huawei = plain
                 .huawei
                 .ok_or_else(|| AppError::new("not_configured", "Huawei Cloud is not configured"))?;
-            let client = HuaweiClient::new(huawei.token.clone())?;
+            let client = huawei_client(&huawei)?;
             let existing = client.list_records(&domain_id, &domain_name).await?;
+            validation::ensure_exclusive(&existing, &req.record_type, &req.name, None)?;
             let conflicts: Vec<&DnsRecord> = existing
                 .iter()
-                .filter(|r| r.record_type == req.record_type && r.name == req.name)
+                .filter(|r| r.record_type == req.record_type && idn::same_name(&r.name, &req.name))
                 .collect();
-            if !conflicts.is_empty() {
-                match req.conflict_strategy {
-                    ConflictStrategy::DoNotCreate => {
-                        return Err(AppError::new("conflict", "Record already exists"));
-                    }
-                    ConflictStrategy::Overwrite => {
-                        if conflicts.len() != 1 {
-                            return Err(AppError::new("conflict", "Multiple conflicting records found"));
-                        }
-                        let update = RecordUpdateRequest {
-                            id: conflicts[0].id.clone(),
-                            record_type: req.record_type,
-                            name: req.name,
-                            content: req.content,
-                            ttl: req.ttl,
-                            mx_priority: req.mx_priority,
-                            srv_priority: req.srv_priority,
-                            srv_weight: req.srv_weight,
-                            srv_port: req.srv_port,
-                            caa_flags: req.caa_flags,
-                            caa_tag: req.caa_tag,
-                        };
-                        return client.update_record(&domain_id, &domain_name, &update).await;
-                    }
+            match plan_record_create(&conflicts, &req)? {
+                // 华为云同名同类型的值都在一个记录集里，新建时会追加到已有记录集
+                CreatePlan::Create => client.create_record(&domain_id, &domain_name, &req).await,
+                CreatePlan::Update(update) => client.update_record(&domain_id, &domain_name, &update).await,
+                CreatePlan::ReplaceAll { update, .. } => {
+                    client.replace_recordset(&domain_id, &domain_name, &update).await
                 }
             }
-            client.create_record(&domain_id, &domain_name, &req).await
         }
         Provider::Baidu => {
             let baidu = plain
                 .baidu
                 .ok_or_else(|| AppError::new("not_configured", "Baidu Cloud is not configured"))?;
-            let client = BaiduClient::new(baidu.access_key_id.clone(), baidu.secret_access_key.clone())?;
+            let client = baidu_client(&baidu)?;
             let existing = client.list_records(&domain_id, &domain_name).await?;
+            validation::ensure_exclusive(&existing, &req.record_type, &req.name, None)?;
             let conflicts: Vec<&DnsRecord> = existing
                 .iter()
-                .filter(|r| r.record_type == req.record_type && r.name == req.name)
+                .filter(|r| r.record_type == req.record_type && idn::same_name(&r.name, &req.name))
                 .collect();
-            if !conflicts.is_empty() {
-                match req.conflict_strategy {
-                    ConflictStrategy::DoNotCreate => {
-                        return Err(AppError::new("conflict", "Record already exists"));
-                    }
-                    ConflictStrategy::Overwrite => {
-                        if conflicts.len() != 1 {
-                            return Err(AppError::new("conflict", "Multiple conflicting records found"));
-                        }
-                        let update = RecordUpdateRequest {
-                            id: conflicts[0].id.clone(),
-                            record_type: req.record_type,
-                            name: req.name,
-                            content: req.content,
-                            ttl: req.ttl,
-                            mx_priority: req.mx_priority,
-                            srv_priority: req.srv_priority,
-                            srv_weight: req.srv_weight,
-                            srv_port: req.srv_port,
-                            caa_flags: req.caa_flags,
-                            caa_tag: req.caa_tag,
-                        };
-                        return client.update_record(&domain_id, &domain_name, &update).await;
-                    }
+            match plan_record_create(&conflicts, &req)? {
+                CreatePlan::Create => client.create_record(&domain_id, &domain_name, &req).await,
+                CreatePlan::Update(update) => client.update_record(&domain_id, &domain_name, &update).await,
+                CreatePlan::ReplaceAll { update, delete_ids } => {
+                    replace_all(
+                        || client.update_record(&domain_id, &domain_name, &update),
+                        &delete_ids,
+                        |id| client.delete_record(&domain_id, id),
+                    )
+                    .await
                 }
             }
-            client.create_record(&domain_id, &domain_name, &req).await
         }
         Provider::Dnscom => {
             let dnscom = plain
                 .dnscom
                 .ok_or_else(|| AppError::new("not_configured", "DNS.COM is not configured"))?;
-            let client = DnscomClient::new(dnscom.api_key.clone(), dnscom.api_secret.clone())?;
+            let client = dnscom_client(&dnscom)?;
             let existing = client.list_records(&domain_id, &domain_name).await?;
+            validation::ensure_exclusive(&existing, &req.record_type, &req.name, None)?;
             let conflicts: Vec<&DnsRecord> = existing
                 .iter()
-                .filter(|r| r.record_type == req.record_type && r.name == req.name)
+                .filter(|r| r.record_type == req.record_type && idn::same_name(&r.name, &req.name))
                 .collect();
-            if !conflicts.is_empty() {
-                match req.conflict_strategy {
-                    ConflictStrategy::DoNotCreate => {
-                        return Err(AppError::new("conflict", "Record already exists"));
-                    }
-                    ConflictStrategy::Overwrite => {
-                        if conflicts.len() != 1 {
-                            return Err(AppError::new("conflict", "Multiple conflicting records found"));
-                        }
-                        let update = RecordUpdateRequest {
-                            id: conflicts[0].id.clone(),
-                            record_type: req.record_type,
-                            name: req.name,
-                            content: req.content,
-                            ttl: req.ttl,
-                            mx_priority: req.mx_priority,
-                            srv_priority: req.srv_priority,
-                            srv_weight: req.srv_weight,
-                            srv_port: req.srv_port,
-                            caa_flags: req.caa_flags,
-                            caa_tag: req.caa_tag,
-                        };
-                        return client.update_record(&domain_id, &domain_name, &update).await;
-                    }
+            match plan_record_create(&conflicts, &req)? {
+                CreatePlan::Create => client.create_record(&domain_id, &domain_name, &req).await,
+                CreatePlan::Update(update) => client.update_record(&domain_id, &domain_name, &update).await,
+                CreatePlan::ReplaceAll { update, delete_ids } => {
+                    replace_all(
+                        || client.update_record(&domain_id, &domain_name, &update),
+                        &delete_ids,
+                        |id| client.delete_record(&domain_id, &domain_name, id),
+                    )
+                    .await
                 }
             }
-            client.create_record(&domain_id, &domain_name, &req).await
         }
         Provider::Rainyun => {
             let rainyun = plain
                 .rainyun
                 .ok_or_else(|| AppError::new("not_configured", "Rainyun is not configured"))?;
-            let client = RainyunClient::new(rainyun.api_key.clone())?;
+            let client = rainyun_client(&rainyun)?;
             let existing = client.list_records(&domain_id, &domain_name).await?;
+            validation::ensure_exclusive(&existing, &req.record_type, &req.name, None)?;
             let conflicts: Vec<&DnsRecord> = existing
                 .iter()
-                .filter(|r| r.record_type == req.record_type && r.name == req.name)
+                .filter(|r| r.record_type == req.record_type && idn::same_name(&r.name, &req.name))
                 .collect();
-            if !conflicts.is_empty() {
-                match req.conflict_strategy {
-                    ConflictStrategy::DoNotCreate => {
-                        return Err(AppError::new("conflict", "Record already exists"));
-                    }
-                    ConflictStrategy::Overwrite => {
-                        if conflicts.len() != 1 {
-                            return Err(AppError::new("conflict", "Multiple conflicting records found"));
-                        }
-                        let update = RecordUpdateRequest {
-                            id: conflicts[0].id.clone(),
-                            record_type: req.record_type,
-                            name: req.name,
-                            content: req.content,
-                            ttl: req.ttl,
-                            mx_priority: req.mx_priority,
-                            srv_priority: req.srv_priority,
-                            srv_weight: req.srv_weight,
-                            srv_port: req.srv_port,
-                            caa_flags: req.caa_flags,
-                            caa_tag: req.caa_tag,
-                        };
-                        return client.update_record(&domain_id, &domain_name, &update).await;
-                    }
+            match plan_record_create(&conflicts, &req)? {
+                CreatePlan::Create => client.create_record(&domain_id, &domain_name, &req).await,
+                CreatePlan::Update(update) => client.update_record(&domain_id, &domain_name, &update).await,
+                CreatePlan::ReplaceAll { update, delete_ids } => {
+                    replace_all(
+                        || client.update_record(&domain_id, &domain_name, &update),
+                        &delete_ids,
+                        |id| client.delete_record(&domain_id, id),
+                    )
+                    .await
                 }
             }
-            client.create_record(&domain_id, &domain_name, &req).await
         }
         Provider::Tencentcloud => {
             let tencentcloud = plain
                 .tencentcloud
                 .ok_or_else(|| AppError::new("not_configured", "Tencent Cloud is not configured"))?;
-            let client = TencentCloudClient::new(tencentcloud.secret_id.clone(), tencentcloud.secret_key.clone())?;
+            let client = tencentcloud_client(&tencentcloud).await?;
             let existing = client.list_records(&domain_id, &domain_name).await?;
+            validation::ensure_exclusive(&existing, &req.record_type, &req.name, None)?;
             let conflicts: Vec<&DnsRecord> = existing
                 .iter()
-                .filter(|r| r.record_type == req.record_type && r.name == req.name)
+                .filter(|r| r.record_type == req.record_type && idn::same_name(&r.name, &req.name))
                 .collect();
-            if !conflicts.is_empty() {
-                match req.conflict_strategy {
-                    ConflictStrategy::DoNotCreate => {
-                        return Err(AppError::new("conflict", "Record already exists"));
-                    }
-                    ConflictStrategy::Overwrite => {
-                        if conflicts.len() != 1 {
-                            return Err(AppError::new("conflict", "Multiple conflicting records found"));
-                        }
-                        let update = RecordUpdateRequest {
-                            id: conflicts[0].id.clone(),
-                            record_type: req.record_type,
-                            name: req.name,
-                            content: req.content,
-                            ttl: req.ttl,
-                            mx_priority: req.mx_priority,
-                            srv_priority: req.srv_priority,
-                            srv_weight: req.srv_weight,
-                            srv_port: req.srv_port,
-                            caa_flags: req.caa_flags,
-                            caa_tag: req.caa_tag,
-                        };
-                        return client.update_record(&domain_id, &domain_name, &update).await;
-                    }
+            match plan_record_create(&conflicts, &req)? {
+                CreatePlan::Create => client.create_record(&domain_id, &domain_name, &req).await,
+                CreatePlan::Update(update) => client.update_record(&domain_id, &domain_name, &update).await,
+                CreatePlan::ReplaceAll { update, delete_ids } => {
+                    replace_all(
+                        || client.update_record(&domain_id, &domain_name, &update),
+                        &delete_ids,
+                        |id| client.delete_record(&domain_id, &domain_name, id),
+                    )
+                    .await
                 }
             }
-            client.create_record(&domain_id, &domain_name, &req).await
         }
     }
 }
 
-fn validate_record_request(req: &RecordCreateRequest) -> Result<(), AppError> {
-    let valid_types = ["A", "AAAA", "CNAME", "TXT", "MX", "NS", "SRV", "CAA"];
-    if !valid_types.contains(&req.record_type.as_str()) {
-        return Err(AppError::new("invalid_type", format!("不支持的记录类型: {}", req.record_type)));
-    }
-
-    let name = req.name.trim();
-    if name.is_empty() {
-        return Err(AppError::new("invalid_name", "主机记录不能为空"));
+// 中文主机记录和指向的中文域名转成 xn-- 形式后再校验、发给服务商
+fn ascii_record_names(record_type: &str, name: &mut String, content: &mut String) -> Result<(), AppError> {
+    *name = idn::to_ascii(name)?;
+    if matches!(record_type, "CNAME" | "NS" | "MX" | "SRV" | "PTR" | "NAPTR" | "HTTPS" | "SVCB" | "ALIAS") {
+        *content = idn::to_ascii(content)?;
     }
+    Ok(())
+}
 
-    let content = req.content.trim();
-    if content.is_empty() {
-        return Err(AppError::new("invalid_content", "记录值不能为空"));
-    }
+enum CreatePlan {
+    Create,
+    Update(RecordUpdateRequest),
+    ReplaceAll {
+        update: RecordUpdateRequest,
+        delete_ids: Vec<String>,
+    },
+}
 
-    if req.ttl < 60 || req.ttl > 86400 {
-        return Err(AppError::new("invalid_ttl", "TTL 必须在 60-86400 秒之间"));
+fn plan_record_create(conflicts: &[&DnsRecord], req: &RecordCreateRequest) -> Result<CreatePlan, AppError> {
+    if conflicts.is_empty() {
+        return Ok(CreatePlan::Create);
     }
-
-    match req.record_type.as_str() {
-        "A" => {
-            if content.parse::<Ipv4Addr>().is_err() {
-                return Err(AppError::new("invalid_content", "A 记录必须是有效的 IPv4 地址"));
-            }
-        }
-        "AAAA" => {
-            if content.parse::<Ipv6Addr>().is_err() {
-                return Err(AppError::new("invalid_content", "AAAA 记录必须是有效的 IPv6 地址"));
-            }
-        }
-        "CNAME" | "NS" => {
-            if !content.contains('.') {
-                return Err(AppError::new("invalid_content", "记录值必须是有效域名"));
-            }
+    let content = req.content.trim();
+    match req.conflict_strategy {
+        ConflictStrategy::DoNotCreate => Err(AppError::new("conflict", "Record already exists")),
+        ConflictStrategy::Overwrite => {
+            let target = if conflicts.len() == 1 {
+                conflicts[0]
+            } else {
+                // 多值记录集里只覆盖值相同的那一条，否则无法判断要替换哪一个
+                conflicts.iter().copied().find(|r| r.content == content).ok_or_else(|| {
+                    AppError::new(
+                        "conflict",
+                        "Multiple conflicting records found, use append or replace_all instead",
+                    )
+                })?
+            };
+            Ok(CreatePlan::Update(overwrite_request(target.id.clone(), req)))
         }
-        "MX" => {
-            if req.mx_priority.is_none() {
-                return Err(AppError::new("missing_field", "MX 记录必须设置优先级"));
-            }
+        ConflictStrategy::Append => {
+            ensure_appendable(conflicts, req)?;
+            Ok(CreatePlan::Create)
         }
-        "SRV" => {
-            if req.srv_priority.is_none() || req.srv_weight.is_none() || req.srv_port.is_none() {
-                return Err(AppError::new("missing_field", "SRV 记录必须设置优先级、权重和端口"));
-            }
-            let parts: Vec<&str> = name.split('.').collect();
-            if parts.len() < 2 || !parts[0].starts_with('_') || !parts[1].starts_with('_') {
-                return Err(AppError::new("invalid_name", "SRV 主机记录需为 _service._proto 形式"));
-            }
+        ConflictStrategy::ReplaceAll => {
+            let keep = conflicts
+                .iter()
+                .copied()
+                .find(|r| r.content == content)
+                .unwrap_or(conflicts[0]);
+            let delete_ids = conflicts
+                .iter()
+                .filter(|r| r.id != keep.id)
+                .map(|r| r.id.clone())
+                .collect();
+            Ok(CreatePlan::ReplaceAll {
+                update: overwrite_request(keep.id.clone(), req),
+                delete_ids,
+            })
         }
-        "CAA" => {
-            if let Some(tag) = &req.caa_tag {
-                if tag.trim().is_empty() {
-                    return Err(AppError::new("invalid_caa_tag", "CAA Tag 不能为空"));
-                }
-            }
+    }
+}
+
+// 先覆盖保留的那条，再逐条删除其余记录，两步之间不是原子的。
+// 个别删除失败时继续删完，再把没删掉的记录一并报出来
+async fn replace_all<'a, U, D, DF>(
+    update: impl FnOnce() -> U,
+    delete_ids: &'a [String],
+    delete: D,
+) -> Result<DnsRecord, AppError>
+where
+    U: Future<Output = Result<DnsRecord, AppError>>,
+    D: Fn(&'a str) -> DF,
+    DF: Future<Output = Result<(), AppError>>,
+{
+    let record = update().await?;
+    let mut failed = Vec::new();
+    for id in delete_ids {
+        if let Err(e) = delete(id).await {
+            failed.push(format!("{id} ({})", e.message));
         }
-        _ => {}
     }
+    if failed.is_empty() {
+        return Ok(record);
+    }
+    Err(AppError::new(
+        "partial_failure",
+        format!(
+            "Record {} was overwritten, but {} other record(s) could not be deleted \
+             (replacement updates first and then deletes, so it is not atomic): {}",
+            record.id,
+            failed.len(),
+            failed.join(", ")
+        ),
+    ))
+}
 
+fn ensure_appendable(conflicts: &[&DnsRecord], req: &RecordCreateRequest) -> Result<(), AppError> {
+    if conflicts.iter().any(|r| r.content == req.content.trim()) {
+        return Err(AppError::new("conflict", "Record with the same value already exists"));
+    }
     Ok(())
 }
 
+fn weighted_update_request(record: &DnsRecord, weight: u16) -> RecordUpdateRequest {
+    RecordUpdateRequest {
+        weight: Some(weight),
+        ..update_request_from(record)
+    }
+}
+
+fn update_request_from(record: &DnsRecord) -> RecordUpdateRequest {
+    RecordUpdateRequest {
+        id: record.id.clone(),
+        record_type: record.record_type.clone(),
+        name: record.name.clone(),
+        content: record.content.clone(),
+        ttl: record.ttl,
+        mx_priority: record.mx_priority,
+        srv_priority: record.srv_priority,
+        srv_weight: record.srv_weight,
+        srv_port: record.srv_port,
+        caa_flags: record.caa_flags,
+        caa_tag: record.caa_tag.clone(),
+        comment: record.comment.clone(),
+        tags: Some(record.tags.clone()),
+        weight: record.weight,
+        original_type: Some(record.record_type.clone()),
+        original_name: Some(record.name.clone()),
+        extra: record.extra.clone(),
+    }
+}
+
+fn overwrite_request(id: String, req: &RecordCreateRequest) -> RecordUpdateRequest {
+    RecordUpdateRequest {
+        id,
+        record_type: req.record_type.clone(),
+        name: req.name.clone(),
+        content: req.content.clone(),
+        ttl: req.ttl,
+        mx_priority: req.mx_priority,
+        srv_priority: req.srv_priority,
+        srv_weight: req.srv_weight,
+        srv_port: req.srv_port,
+        caa_flags: req.caa_flags,
+        caa_tag: req.caa_tag.clone(),
+        comment: req.comment.clone(),
+        tags: req.tags.clone(),
+        weight: req.weight,
+        original_type: Some(req.record_type.clone()),
+        original_name: Some(req.name.clone()),
+        extra: req.extra.clone(),
+    }
+}
+
 #[tauri::command]
 pub async fn record_update(
     app: AppHandle,
@@ -1104,61 +1483,108 @@ pub async fn record_update(
     req: RecordUpdateRequest,
 ) -> Result<DnsRecord, AppError> {
     let (_, plain) = vault::decrypt_vault(&app, &master_password)?;
+    let domain_name = idn::to_ascii(&domain_name)?;
+    run_record_update(plain, provider, domain_id, domain_name, req)
+        .await
+        .map(present_record)
+        .map_err(|e| e.in_context(provider, "record_update"))
+}
+
+async fn run_record_update(
+    plain: PlainVault,
+    provider: Provider,
+    domain_id: String,
+    domain_name: String,
+    req: RecordUpdateRequest,
+) -> Result<DnsRecord, AppError> {
+    write_record_update(plain, provider, domain_id, domain_name, req, true).await
+}
+
+// 回滚写回的是服务商原本接受的值，不再套用本地校验规则
+async fn write_record_update(
+    plain: PlainVault,
+    provider: Provider,
+    domain_id: String,
+    domain_name: String,
+    mut req: RecordUpdateRequest,
+    validate: bool,
+) -> Result<DnsRecord, AppError> {
+    let _invalidation = search::invalidate_on_exit(&plain, provider, &domain_id);
+    ascii_record_names(&req.record_type, &mut req.name, &mut req.content)?;
+    if req.record_type == "TXT" {
+        req.content = txt::decode(&req.content);
+    }
+    if validate {
+        validation::validate_record(provider, &domain_name, &RecordDraft::from(&req))?;
+    }
+    // 类型和名称都没变时不会引入新的 CNAME 冲突
+    let renamed = req.original_type.as_deref() != Some(req.record_type.as_str())
+        || !req
+            .original_name
+            .as_deref()
+            .is_some_and(|name| name.eq_ignore_ascii_case(&req.name));
+    forwarding::to_native(provider, &mut req.record_type, &req.extra)?;
+    alias::to_native(provider, &mut req.record_type)?;
+    if provider != Provider::Cloudflare && renamed {
+        let existing = run_records_list(plain.clone(), provider, domain_id.clone(), domain_name.clone()).await?;
+        validation::ensure_exclusive(&existing, &req.record_type, &req.name, Some(&req.id))?;
+    }
+
     match provider {
         Provider::Cloudflare => {
             let cf = plain
                 .cloudflare
                 .ok_or_else(|| AppError::new("not_configured", "Cloudflare is not configured"))?;
-            let cf_client = CloudflareClient::new(cf.api_token.clone())?;
+            let cf_client = cloudflare_client(&cf)?;
             cf_client.update_record(&domain_id, &domain_name, &req).await
         }
         Provider::Dnspod => {
             let dp = plain
                 .dnspod
                 .ok_or_else(|| AppError::new("not_configured", "DNSPod is not configured"))?;
-            let dp_client = DnspodClient::new(dp.token_id.clone(), dp.token.clone())?;
+            let dp_client = dnspod_client(&dp)?;
             dp_client.update_record(&domain_id, &domain_name, &req).await
         }
         Provider::Aliyun => {
             let aliyun = plain
                 .aliyun
                 .ok_or_else(|| AppError::new("not_configured", "Aliyun is not configured"))?;
-            let client = AliyunClient::new(aliyun.access_key_id.clone(), aliyun.access_key_secret.clone())?;
+            let client = aliyun_client(&aliyun).await?;
             client.update_record(&domain_id, &domain_name, &req).await
         }
         Provider::Huawei => {
             let huawei = plain
                 .huawei
                 .ok_or_else(|| AppError::new("not_configured", "Huawei Cloud is not configured"))?;
-            let client = HuaweiClient::new(huawei.token.clone())?;
+            let client = huawei_client(&huawei)?;
             client.update_record(&domain_id, &domain_name, &req).await
         }
         Provider::Baidu => {
             let baidu = plain
                 .baidu
                 .ok_or_else(|| AppError::new("not_configured", "Baidu Cloud is not configured"))?;
-            let client = BaiduClient::new(baidu.access_key_id.clone(), baidu.secret_access_key.clone())?;
+            let client = baidu_client(&baidu)?;
             client.update_record(&domain_id, &domain_name, &req).await
         }
         Provider::Dnscom => {
             let dnscom = plain
                 .dnscom
                 .ok_or_else(|| AppError::new("not_configured", "DNS.COM is not configured"))?;
-            let client = DnscomClient::new(dnscom.api_key.clone(), dnscom.api_secret.clone())?;
+            let client = dnscom_client(&dnscom)?;
             client.update_record(&domain_id, &domain_name, &req).await
         }
         Provider::Rainyun => {
             let rainyun = plain
                 .rainyun
                 .ok_or_else(|| AppError::new("not_configured", "Rainyun is not configured"))?;
-            let client = RainyunClient::new(rainyun.api_key.clone())?;
+            let client = rainyun_client(&rainyun)?;
             client.update_record(&domain_id, &domain_name, &req).await
         }
         Provider::Tencentcloud => {
             let tencentcloud = plain
                 .tencentcloud
                 .ok_or_else(|| AppError::new("not_configured", "Tencent Cloud is not configured"))?;
-            let client = TencentCloudClient::new(tencentcloud.secret_id.clone(), tencentcloud.secret_key.clone())?;
+            let client = tencentcloud_client(&tencentcloud).await?;
             client.update_record(&domain_id, &domain_name, &req).await
         }
     }
@@ -1174,64 +1600,1346 @@ pub async fn record_delete(
     record_id: String,
 ) -> Result<(), AppError> {
     let (_, plain) = vault::decrypt_vault(&app, &master_password)?;
+    let domain_name = idn::to_ascii(&domain_name)?;
+    run_record_delete(plain, provider, domain_id, domain_name, record_id)
+        .await
+        .map_err(|e| e.in_context(provider, "record_delete"))
+}
+
+async fn run_record_delete(
+    plain: PlainVault,
+    provider: Provider,
+    domain_id: String,
+    domain_name: String,
+    record_id: String,
+) -> Result<(), AppError> {
+    let _invalidation = search::invalidate_on_exit(&plain, provider, &domain_id);
     match provider {
         Provider::Cloudflare => {
             let cf = plain
                 .cloudflare
                 .ok_or_else(|| AppError::new("not_configured", "Cloudflare is not configured"))?;
-            let cf_client = CloudflareClient::new(cf.api_token.clone())?;
+            let cf_client = cloudflare_client(&cf)?;
             cf_client.delete_record(&domain_id, &record_id).await
         }
         Provider::Dnspod => {
             let dp = plain
                 .dnspod
                 .ok_or_else(|| AppError::new("not_configured", "DNSPod is not configured"))?;
-            let dp_client = DnspodClient::new(dp.token_id.clone(), dp.token.clone())?;
-            dp_client.delete_record(&domain_id, &record_id).await
+            let dp_client = dnspod_client(&dp)?;
+            dp_client.delete_record(&domain_id, &domain_name, &record_id).await
         }
         Provider::Aliyun => {
             let aliyun = plain
                 .aliyun
                 .ok_or_else(|| AppError::new("not_configured", "Aliyun is not configured"))?;
-            let client = AliyunClient::new(aliyun.access_key_id.clone(), aliyun.access_key_secret.clone())?;
+            let client = aliyun_client(&aliyun).await?;
             client.delete_record(&domain_id, &record_id).await
         }
         Provider::Huawei => {
             let huawei = plain
                 .huawei
                 .ok_or_else(|| AppError::new("not_configured", "Huawei Cloud is not configured"))?;
-            let client = HuaweiClient::new(huawei.token.clone())?;
+            let client = huawei_client(&huawei)?;
             client.delete_record(&domain_id, &record_id).await
         }
         Provider::Baidu => {
             let baidu = plain
                 .baidu
                 .ok_or_else(|| AppError::new("not_configured", "Baidu Cloud is not configured"))?;
-            let client = BaiduClient::new(baidu.access_key_id.clone(), baidu.secret_access_key.clone())?;
+            let client = baidu_client(&baidu)?;
             client.delete_record(&domain_id, &record_id).await
         }
         Provider::Dnscom => {
             let dnscom = plain
                 .dnscom
                 .ok_or_else(|| AppError::new("not_configured", "DNS.COM is not configured"))?;
-            let client = DnscomClient::new(dnscom.api_key.clone(), dnscom.api_secret.clone())?;
+            let client = dnscom_client(&dnscom)?;
             client.delete_record(&domain_id, &domain_name, &record_id).await
         }
         Provider::Rainyun => {
             let rainyun = plain
                 .rainyun
                 .ok_or_else(|| AppError::new("not_configured", "Rainyun is not configured"))?;
-            let client = RainyunClient::new(rainyun.api_key.clone())?;
+            let client = rainyun_client(&rainyun)?;
             client.delete_record(&domain_id, &record_id).await
         }
         Provider::Tencentcloud => {
             let tencentcloud = plain
                 .tencentcloud
                 .ok_or_else(|| AppError::new("not_configured", "Tencent Cloud is not configured"))?;
-            let client = TencentCloudClient::new(tencentcloud.secret_id.clone(), tencentcloud.secret_key.clone())?;
+            let client = tencentcloud_client(&tencentcloud).await?;
             client.delete_record(&domain_id, &domain_name, &record_id).await
         }
     }
 }
 
- 
+#[tauri::command]
+pub async fn record_set_status(
+    app: AppHandle,
+    master_password: String,
+    provider: Provider,
+    domain_id: String,
+    domain_name: String,
+    record_id: String,
+    status: RecordStatus,
+) -> Result<(), AppError> {
+    let (_, plain) = vault::decrypt_vault(&app, &master_password)?;
+    let domain_name = idn::to_ascii(&domain_name)?;
+    run_record_set_status(plain, provider, domain_id, domain_name, record_id, status)
+        .await
+        .map_err(|e| e.in_context(provider, "record_set_status"))
+}
+
+async fn run_record_set_status(
+    plain: PlainVault,
+    provider: Provider,
+    domain_id: String,
+    domain_name: String,
+    record_id: String,
+    status: RecordStatus,
+) -> Result<(), AppError> {
+    let _invalidation = search::invalidate_on_exit(&plain, provider, &domain_id);
+    match provider {
+        Provider::Cloudflare => Err(AppError::new(
+            "unsupported",
+            "Cloudflare does not support pausing records",
+        )),
+        Provider::Dnspod => {
+            let dp = plain
+                .dnspod
+                .ok_or_else(|| AppError::new("not_configured", "DNSPod is not configured"))?;
+            let dp_client = dnspod_client(&dp)?;
+            dp_client.set_record_status(&domain_id, &domain_name, &record_id, status).await
+        }
+        Provider::Aliyun => {
+            let aliyun = plain
+                .aliyun
+                .ok_or_else(|| AppError::new("not_configured", "Aliyun is not configured"))?;
+            let client = aliyun_client(&aliyun).await?;
+            client.set_record_status(&domain_id, &record_id, status).await
+        }
+        Provider::Huawei => {
+            let huawei = plain
+                .huawei
+                .ok_or_else(|| AppError::new("not_configured", "Huawei Cloud is not configured"))?;
+            let client = huawei_client(&huawei)?;
+            client.set_record_status(&domain_id, &record_id, status).await
+        }
+        Provider::Baidu => Err(AppError::new(
+            "unsupported",
+            "Baidu Cloud does not support pausing records",
+        )),
+        Provider::Dnscom => {
+            let dnscom = plain
+                .dnscom
+                .ok_or_else(|| AppError::new("not_configured", "DNS.COM is not configured"))?;
+            let client = dnscom_client(&dnscom)?;
+            client.set_record_status(&domain_id, &domain_name, &record_id, status).await
+        }
+        Provider::Rainyun => Err(AppError::new(
+            "unsupported",
+            "Rainyun does not support pausing records",
+        )),
+        Provider::Tencentcloud => {
+            let tencentcloud = plain
+                .tencentcloud
+                .ok_or_else(|| AppError::new("not_configured", "Tencent Cloud is not configured"))?;
+            let client = tencentcloud_client(&tencentcloud).await?;
+            client.set_record_status(&domain_id, &domain_name, &record_id, status).await
+        }
+    }
+}
+
+#[tauri::command]
+pub async fn record_set_weights(
+    app: AppHandle,
+    master_password: String,
+    provider: Provider,
+    domain_id: String,
+    domain_name: String,
+    weights: Vec<RecordWeight>,
+) -> Result<(), AppError> {
+    let (_, plain) = vault::decrypt_vault(&app, &master_password)?;
+    let domain_name = idn::to_ascii(&domain_name)?;
+    run_record_set_weights(plain, provider, domain_id, domain_name, weights)
+        .await
+        .map_err(|e| e.in_context(provider, "record_set_weights"))
+}
+
+async fn run_record_set_weights(
+    plain: PlainVault,
+    provider: Provider,
+    domain_id: String,
+    domain_name: String,
+    weights: Vec<RecordWeight>,
+) -> Result<(), AppError> {
+    // 先检查全部权重，避免写了一半才发现越界
+    for item in &weights {
+        validation::check_weight(provider, item.weight)?;
+    }
+    let _invalidation = search::invalidate_on_exit(&plain, provider, &domain_id);
+    match provider {
+        Provider::Dnspod => {
+            let dp = plain
+                .dnspod
+                .ok_or_else(|| AppError::new("not_configured", "DNSPod is not configured"))?;
+            let dp_client = dnspod_client(&dp)?;
+            let existing = dp_client.list_records(&domain_id, &domain_name).await?;
+            for item in &weights {
+                let record = find_record(&existing, &item.id)?;
+                let update = weighted_update_request(record, item.weight);
+                dp_client.update_record(&domain_id, &domain_name, &update).await?;
+            }
+            Ok(())
+        }
+        Provider::Aliyun => {
+            let aliyun = plain
+                .aliyun
+                .ok_or_else(|| AppError::new("not_configured", "Aliyun is not configured"))?;
+            let client = aliyun_client(&aliyun).await?;
+            client.set_record_weights(&domain_name, &weights).await
+        }
+        Provider::Huawei => {
+            let huawei = plain
+                .huawei
+                .ok_or_else(|| AppError::new("not_configured", "Huawei Cloud is not configured"))?;
+            let client = huawei_client(&huawei)?;
+            client.set_record_weights(&domain_id, &weights).await
+        }
+        Provider::Tencentcloud => {
+            let tencentcloud = plain
+                .tencentcloud
+                .ok_or_else(|| AppError::new("not_configured", "Tencent Cloud is not configured"))?;
+            let client = tencentcloud_client(&tencentcloud).await?;
+            let existing = client.list_records(&domain_id, &domain_name).await?;
+            for item in &weights {
+                let record = find_record(&existing, &item.id)?;
+                let update = weighted_update_request(record, item.weight);
+                client.update_record(&domain_id, &domain_name, &update).await?;
+            }
+            Ok(())
+        }
+        Provider::Cloudflare | Provider::Baidu | Provider::Dnscom | Provider::Rainyun => Err(AppError::new(
+            "unsupported",
+            "This provider does not support weighted records",
+        )),
+    }
+}
+
+#[tauri::command]
+pub fn mail_presets_list() -> Vec<MailPresetInfo> {
+    presets::list()
+}
+
+#[tauri::command]
+pub async fn mail_preset_preview(
+    app: AppHandle,
+    master_password: String,
+    provider: Provider,
+    domain_id: String,
+    domain_name: String,
+    preset: String,
+    params: HashMap<String, String>,
+) -> Result<Vec<PresetPreviewItem>, AppError> {
+    let (_, plain) = vault::decrypt_vault(&app, &master_password)?;
+    let domain_name = idn::to_ascii(&domain_name)?;
+    run_mail_preset_preview(plain, provider, domain_id, domain_name, &preset, &params)
+        .await
+        .map_err(|e| e.in_context(provider, "mail_preset_preview"))
+}
+
+async fn run_mail_preset_preview(
+    plain: PlainVault,
+    provider: Provider,
+    domain_id: String,
+    domain_name: String,
+    preset: &str,
+    params: &HashMap<String, String>,
+) -> Result<Vec<PresetPreviewItem>, AppError> {
+    let requests = presets::render(preset, &domain_name, params)?;
+    for req in &requests {
+        validation::validate_record(provider, &domain_name, &RecordDraft::from(req))?;
+    }
+    let existing = run_records_list(plain, provider, domain_id, domain_name).await?;
+    Ok(requests
+        .iter()
+        .map(|req| preset_preview_item(&existing, &requests, req.clone()))
+        .collect())
+}
+
+// 同名同类型的已有记录里，和模板里任一条内容相同的不算冲突；TXT 只和同一种 v= 标记的比
+fn preset_preview_item(
+    existing: &[DnsRecord],
+    rendered: &[RecordCreateRequest],
+    request: RecordCreateRequest,
+) -> PresetPreviewItem {
+    let same_slot = |record_type: &str, name: &str, content: &str| {
+        record_type == request.record_type
+            && idn::same_name(name, &request.name)
+            && (record_type != "TXT" || txt_tag(content) == txt_tag(&request.content))
+    };
+    let slot: Vec<&DnsRecord> = existing
+        .iter()
+        .filter(|r| same_slot(&r.record_type, &r.name, &r.content))
+        .collect();
+    let action = if slot.iter().any(|r| same_record_content(r, &request)) {
+        PresetAction::Skip
+    } else if slot.iter().any(|r| !rendered.iter().any(|other| same_record_content(r, other))) {
+        PresetAction::Conflict
+    } else {
+        PresetAction::Create
+    };
+    let conflicts = slot
+        .into_iter()
+        .filter(|r| !rendered.iter().any(|other| same_record_content(r, other)))
+        .cloned()
+        .collect();
+    PresetPreviewItem { request, action, conflicts }
+}
+
+fn same_record_content(record: &DnsRecord, req: &RecordCreateRequest) -> bool {
+    if record.record_type != req.record_type || !idn::same_name(&record.name, &req.name) {
+        return false;
+    }
+    if record.record_type == "TXT" {
+        return txt::decode(&record.content).trim() == req.content.trim();
+    }
+    let same_target = record
+        .content
+        .trim()
+        .trim_end_matches('.')
+        .eq_ignore_ascii_case(req.content.trim().trim_end_matches('.'));
+    same_target && (record.record_type != "MX" || record.mx_priority == req.mx_priority)
+}
+
+fn txt_tag(content: &str) -> Option<String> {
+    let content = txt::decode(content);
+    let tag = content.trim().split([';', ' ']).next()?.to_ascii_lowercase();
+    tag.starts_with("v=").then_some(tag)
+}
+
+#[tauri::command]
+#[allow(clippy::too_many_arguments)]
+pub async fn mail_preset_apply(
+    app: AppHandle,
+    master_password: String,
+    provider: Provider,
+    domain_id: String,
+    domain_name: String,
+    preset: String,
+    params: HashMap<String, String>,
+    conflict_strategy: ConflictStrategy,
+) -> Result<Vec<PresetApplyResult>, AppError> {
+    let (_, plain) = vault::decrypt_vault(&app, &master_password)?;
+    let domain_name = idn::to_ascii(&domain_name)?;
+    run_mail_preset_apply(plain, provider, domain_id, domain_name, &preset, &params, conflict_strategy)
+        .await
+        .map_err(|e| e.in_context(provider, "mail_preset_apply"))
+}
+
+async fn run_mail_preset_apply(
+    plain: PlainVault,
+    provider: Provider,
+    domain_id: String,
+    domain_name: String,
+    preset: &str,
+    params: &HashMap<String, String>,
+    conflict_strategy: ConflictStrategy,
+) -> Result<Vec<PresetApplyResult>, AppError> {
+    let items =
+        run_mail_preset_preview(plain.clone(), provider, domain_id.clone(), domain_name.clone(), preset, params).await?;
+    let replace = matches!(conflict_strategy, ConflictStrategy::Overwrite | ConflictStrategy::ReplaceAll);
+    let mut results = Vec::new();
+    // 要清理的旧记录，以及顶替它的那一项在 results 里的位置
+    let mut stale: Vec<(String, usize)> = Vec::new();
+    for PresetPreviewItem { mut request, action, conflicts } in items {
+        let outcome = match action {
+            PresetAction::Skip => Ok(None),
+            PresetAction::Conflict if matches!(conflict_strategy, ConflictStrategy::DoNotCreate) => Err(AppError::new(
+                "conflict",
+                format!("{} {} 已有不同的记录", request.record_type, request.name),
+            )),
+            // CNAME 不能和同名记录共存，直接改写原有的那条
+            PresetAction::Conflict if replace && request.record_type == "CNAME" => {
+                request.conflict_strategy = ConflictStrategy::Overwrite;
+                run_record_create(plain.clone(), provider, domain_id.clone(), domain_name.clone(), request.clone())
+                    .await
+                    .map(Some)
+            }
+            _ => {
+                request.conflict_strategy = ConflictStrategy::Append;
+                run_record_create(plain.clone(), provider, domain_id.clone(), domain_name.clone(), request.clone())
+                    .await
+                    .map(Some)
+            }
+        };
+        // 只有这条写成功了才清理它顶替的旧记录，避免邮件无处投递
+        if replace {
+            if let Ok(created) = &outcome {
+                let kept = created.as_ref().map(|r| r.id.as_str());
+                for record in &conflicts {
+                    if Some(record.id.as_str()) != kept && !stale.iter().any(|(id, _)| id == &record.id) {
+                        stale.push((record.id.clone(), results.len()));
+                    }
+                }
+            }
+        }
+        results.push(match outcome {
+            Ok(record) => PresetApplyResult {
+                request,
+                action,
+                record: record.map(present_record),
+                error: None,
+            },
+            Err(e) => PresetApplyResult {
+                request,
+                action,
+                record: None,
+                error: Some(e.message),
+            },
+        });
+    }
+    // 删除失败记在对应的那一项上，其余旧记录照常清理
+    for (id, index) in stale {
+        if let Err(e) = run_record_delete(plain.clone(), provider, domain_id.clone(), domain_name.clone(), id.clone()).await {
+            let message = format!("新记录已写入，但旧记录 {id} 删除失败: {}", e.message);
+            let result = &mut results[index];
+            result.error = Some(match result.error.take() {
+                Some(previous) => format!("{previous}；{message}"),
+                None => message,
+            });
+        }
+    }
+    Ok(results)
+}
+
+#[tauri::command]
+pub async fn zone_lint(
+    app: AppHandle,
+    master_password: String,
+    provider: Provider,
+    domain_id: String,
+    domain_name: String,
+    options: ZoneLintOptions,
+) -> Result<Vec<LintFinding>, AppError> {
+    let (_, plain) = vault::decrypt_vault(&app, &master_password)?;
+    let domain_name = idn::to_ascii(&domain_name)?;
+    run_zone_lint(plain, provider, domain_id, domain_name, options)
+        .await
+        .map_err(|e| e.in_context(provider, "zone_lint"))
+}
+
+async fn run_zone_lint(
+    plain: PlainVault,
+    provider: Provider,
+    domain_id: String,
+    domain_name: String,
+    options: ZoneLintOptions,
+) -> Result<Vec<LintFinding>, AppError> {
+    let resolver = Resolver::new(options.resolver.as_deref())?;
+    let records = present_records(run_records_list(plain, provider, domain_id, domain_name.clone()).await?);
+    Ok(lint::lint_zone(&domain_name, &records, &resolver, &options.issuers).await)
+}
+
+#[tauri::command]
+pub async fn records_search(
+    app: AppHandle,
+    master_password: String,
+    query: RecordSearchQuery,
+) -> Result<RecordSearchResult, AppError> {
+    let (_, plain) = vault::decrypt_vault(&app, &master_password)?;
+    run_records_search(plain, query).await
+}
+
+async fn run_records_search(plain: PlainVault, query: RecordSearchQuery) -> Result<RecordSearchResult, AppError> {
+    let matcher = search::Matcher::new(&query)?;
+    let mut failures = Vec::new();
+    let mut domains = Vec::new();
+    for domain in run_domains_list(plain.clone(), query.provider, None).await? {
+        match domain.status {
+            DomainStatus::Ok if matcher.matches_domain(&domain.name, domain.display_name.as_deref()) => {
+                domains.push(domain)
+            }
+            DomainStatus::Ok | DomainStatus::NotConfigured => {}
+            // 列域名失败时整个服务商都搜不到，错误信息已经在 name 里
+            _ => failures.push(RecordSearchFailure {
+                provider: domain.provider,
+                domain_name: String::new(),
+                message: domain.name,
+            }),
+        }
+    }
+    let domains_searched = domains.len() as u32;
+
+    let listed: Vec<(DomainItem, Result<Vec<DnsRecord>, AppError>)> = stream::iter(domains)
+        .map(|domain| {
+            let plain = plain.clone();
+            async move {
+                let records = search_domain_records(plain, &domain, query.refresh).await;
+                (domain, records)
+            }
+        })
+        .buffer_unordered(search::SEARCH_CONCURRENCY)
+        .collect()
+        .await;
+
+    let mut hits = Vec::new();
+    for (domain, records) in listed {
+        match records {
+            Ok(records) => hits.extend(
+                records
+                    .into_iter()
+                    .filter(|r| matcher.matches(&domain.name, r))
+                    .map(|record| RecordSearchHit {
+                        domain_id: domain.provider_id.clone(),
+                        domain_name: domain.name.clone(),
+                        domain_display_name: domain.display_name.clone(),
+                        record,
+                    }),
+            ),
+            Err(e) => failures.push(RecordSearchFailure {
+                provider: domain.provider,
+                domain_name: domain.name.clone(),
+                message: e.message,
+            }),
+        }
+    }
+    hits.sort_by(|a, b| {
+        (&a.domain_name, &a.record.name, &a.record.record_type).cmp(&(&b.domain_name, &b.record.name, &b.record.record_type))
+    });
+    Ok(RecordSearchResult { hits, failures, domains_searched })
+}
+
+async fn search_domain_records(plain: PlainVault, domain: &DomainItem, refresh: bool) -> Result<Vec<DnsRecord>, AppError> {
+    if !refresh {
+        if let Some(records) = search::cached(&plain, domain.provider, &domain.provider_id) {
+            return Ok(records);
+        }
+    }
+    let records = run_records_list(plain.clone(), domain.provider, domain.provider_id.clone(), domain.name.clone())
+        .await
+        .map(present_records)?;
+    search::store(&plain, domain.provider, &domain.provider_id, records.clone());
+    Ok(records)
+}
+
+#[tauri::command]
+pub async fn records_bulk_replace(
+    app: AppHandle,
+    master_password: String,
+    req: BulkReplaceRequest,
+) -> Result<BulkReplaceResult, AppError> {
+    let (_, plain) = vault::decrypt_vault(&app, &master_password)?;
+    run_records_bulk_replace(plain, req).await
+}
+
+async fn run_records_bulk_replace(plain: PlainVault, req: BulkReplaceRequest) -> Result<BulkReplaceResult, AppError> {
+    if !req.dry_run {
+        if req.plan.is_empty() {
+            return Err(AppError::new("missing_field", "请先预览，再按预览结果执行替换"));
+        }
+        return Ok(BulkReplaceResult {
+            changes: apply_bulk_plan(plain, req.plan, req.rollback_on_failure).await,
+            failures: Vec::new(),
+        });
+    }
+
+    let old_content = req.old_content.trim();
+    if old_content.is_empty() {
+        return Err(AppError::new("missing_field", "要替换的记录值不能为空"));
+    }
+    let new_content = req.new_content.as_deref().map(str::trim).filter(|c| !c.is_empty());
+    if new_content.is_none() && req.new_ttl.is_none() {
+        return Err(AppError::new("missing_field", "新的记录值和 TTL 至少填写一项"));
+    }
+
+    // 批量修改前总是重新拉取，不用缓存里可能过期的记录
+    let query = RecordSearchQuery {
+        provider: req.provider,
+        domain: req.domain.clone(),
+        content: Some(old_content.trim_end_matches('.').to_string()),
+        record_type: req.record_type.clone(),
+        refresh: true,
+        ..Default::default()
+    };
+    let found = run_records_search(plain.clone(), query).await?;
+    let changes: Vec<BulkChange> = found
+        .hits
+        .into_iter()
+        .filter(|hit| same_content(&hit.record.content, old_content))
+        .map(|hit| {
+            let mut after = hit.record.clone();
+            if let Some(content) = new_content {
+                after.content = content.to_string();
+            }
+            if let Some(ttl) = req.new_ttl {
+                after.ttl = ttl;
+            }
+            BulkChange {
+                domain_id: hit.domain_id,
+                domain_name: hit.domain_name,
+                before: hit.record,
+                after,
+                status: BulkChangeStatus::Planned,
+                error: None,
+            }
+        })
+        .filter(|change| change.after.content != change.before.content || change.after.ttl != change.before.ttl)
+        .collect();
+    Ok(BulkReplaceResult {
+        changes,
+        failures: found.failures,
+    })
+}
+
+/// 按预览时确认过的计划逐条修改。每个域名重新拉取一次记录，
+/// 和预览时不一致的记录不再处理，避免覆盖别人刚改过的值。
+/// 计划里只取新的记录值和 TTL，其余字段都以服务商当前的记录为准。
+async fn apply_bulk_plan(plain: PlainVault, mut changes: Vec<BulkChange>, rollback_on_failure: bool) -> Vec<BulkChange> {
+    let mut current: HashMap<(Provider, String), Result<Vec<DnsRecord>, String>> = HashMap::new();
+    let mut failed = false;
+    for change in &mut changes {
+        change.error = None;
+        if failed && rollback_on_failure {
+            change.status = BulkChangeStatus::Skipped;
+            continue;
+        }
+        let provider = change.before.provider;
+        let key = (provider, change.domain_id.clone());
+        if !current.contains_key(&key) {
+            let listed = run_records_list(plain.clone(), provider, change.domain_id.clone(), change.domain_name.clone())
+                .await
+                .map(present_records)
+                .map_err(|e| e.in_context(provider, "records_bulk_replace").message);
+            current.insert(key.clone(), listed);
+        }
+        let unchanged = match &current[&key] {
+            Ok(records) => records.iter().find(|r| r.id == change.before.id && same_state(r, &change.before)),
+            Err(message) => {
+                change.status = BulkChangeStatus::Failed;
+                change.error = Some(message.clone());
+                failed = true;
+                continue;
+            }
+        };
+        let Some(record) = unchanged else {
+            change.status = BulkChangeStatus::Skipped;
+            change.error = Some("预览之后记录已被修改或删除，未处理".to_string());
+            continue;
+        };
+        change.after = DnsRecord {
+            content: change.after.content.clone(),
+            ttl: change.after.ttl,
+            ..record.clone()
+        };
+        let update = update_request_from(&change.after);
+        match run_record_update(plain.clone(), provider, change.domain_id.clone(), change.domain_name.clone(), update).await {
+            Ok(_) => change.status = BulkChangeStatus::Updated,
+            Err(e) => {
+                change.status = BulkChangeStatus::Failed;
+                change.error = Some(e.in_context(provider, "records_bulk_replace").message);
+                failed = true;
+            }
+        }
+    }
+    if failed && rollback_on_failure {
+        changes = run_records_bulk_rollback(plain, changes).await;
+    }
+    changes
+}
+
+fn same_state(current: &DnsRecord, before: &DnsRecord) -> bool {
+    current.record_type == before.record_type
+        && current.name == before.name
+        && current.content == before.content
+        && current.ttl == before.ttl
+}
+
+// 记录值按整值比较，域名类的值忽略大小写和末尾的点
+fn same_content(a: &str, b: &str) -> bool {
+    a.trim().trim_end_matches('.').eq_ignore_ascii_case(b.trim().trim_end_matches('.'))
+}
+
+/// 把已经更新过的记录恢复成修改前的样子，其余记录原样返回。
+#[tauri::command]
+pub async fn records_bulk_rollback(
+    app: AppHandle,
+    master_password: String,
+    changes: Vec<BulkChange>,
+) -> Result<Vec<BulkChange>, AppError> {
+    let (_, plain) = vault::decrypt_vault(&app, &master_password)?;
+    Ok(run_records_bulk_rollback(plain, changes).await)
+}
+
+async fn run_records_bulk_rollback(plain: PlainVault, mut changes: Vec<BulkChange>) -> Vec<BulkChange> {
+    for change in changes.iter_mut().filter(|c| c.status == BulkChangeStatus::Updated) {
+        let provider = change.before.provider;
+        let restore = update_request_from(&change.before);
+        let restored = write_record_update(
+            plain.clone(),
+            provider,
+            change.domain_id.clone(),
+            change.domain_name.clone(),
+            restore,
+            false,
+        )
+        .await;
+        match restored {
+            Ok(_) => change.status = BulkChangeStatus::RolledBack,
+            Err(e) => {
+                change.status = BulkChangeStatus::RollbackFailed;
+                change.error = Some(e.in_context(provider, "records_bulk_rollback").message);
+            }
+        }
+    }
+    changes
+}
+
+fn find_record<'a>(records: &'a [DnsRecord], id: &str) -> Result<&'a DnsRecord, AppError> {
+    records
+        .iter()
+        .find(|r| r.id == id)
+        .ok_or_else(|| AppError::new("not_found", format!("Record {id} not found")))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::providers::mock::{self, MockServer};
+    use crate::types::UrlRedirect;
+    use serde_json::json;
+
+    fn record(id: &str, content: &str) -> DnsRecord {
+        mock::record(id, "A", "www", content)
+    }
+
+    fn create_request(content: &str, conflict_strategy: ConflictStrategy) -> RecordCreateRequest {
+        RecordCreateRequest {
+            conflict_strategy,
+            ..mock::create_request("A", "www", content)
+        }
+    }
+
+    #[test]
+    fn overwrite_picks_matching_value_in_multi_value_set() {
+        let a = record("1", "1.1.1.1");
+        let b = record("2", "2.2.2.2");
+        let req = create_request("2.2.2.2", ConflictStrategy::Overwrite);
+
+        match plan_record_create(&[&a, &b], &req).unwrap() {
+            CreatePlan::Update(update) => assert_eq!(update.id, "2"),
+            _ => panic!("expected update"),
+        }
+
+        let req = create_request("3.3.3.3", ConflictStrategy::Overwrite);
+        let err = plan_record_create(&[&a, &b], &req).err().unwrap();
+        assert_eq!(err.code, "conflict");
+    }
+
+    #[test]
+    fn append_rejects_duplicate_value() {
+        let a = record("1", "1.1.1.1");
+
+        let req = create_request("2.2.2.2", ConflictStrategy::Append);
+        assert!(matches!(plan_record_create(&[&a], &req).unwrap(), CreatePlan::Create));
+
+        let req = create_request("1.1.1.1", ConflictStrategy::Append);
+        assert!(plan_record_create(&[&a], &req).is_err());
+    }
+
+    #[test]
+    fn replace_all_keeps_one_record_and_deletes_the_rest() {
+        let a = record("1", "1.1.1.1");
+        let b = record("2", "2.2.2.2");
+        let c = record("3", "3.3.3.3");
+        let req = create_request("2.2.2.2", ConflictStrategy::ReplaceAll);
+
+        match plan_record_create(&[&a, &b, &c], &req).unwrap() {
+            CreatePlan::ReplaceAll { update, delete_ids } => {
+                assert_eq!(update.id, "2");
+                assert_eq!(delete_ids, vec!["1".to_string(), "3".to_string()]);
+            }
+            _ => panic!("expected replace_all"),
+        }
+    }
+
+    #[tokio::test]
+    async fn rejected_credentials_mark_every_provider_auth_failed() {
+        let cloudflare = MockServer::start(|_| {
+            (403, json!({ "success": false, "errors": [{ "code": 10000, "message": "Authentication error" }], "result": null }))
+        })
+        .await;
+        let dnspod = MockServer::start(|_| (200, json!({ "status": { "code": "-1", "message": "Login failed" } }))).await;
+        let aliyun = MockServer::start(|_| {
+            (404, json!({ "Code": "InvalidAccessKeyId.NotFound", "Message": "Specified access key is not found." }))
+        })
+        .await;
+        let huawei = MockServer::start(|_| {
+            (401, json!({ "error_code": "APIGW.0301", "error_msg": "Incorrect IAM authentication information" }))
+        })
+        .await;
+        let baidu = MockServer::start(|_| (403, json!({ "code": "SignatureDoesNotMatch", "message": "signature mismatch" }))).await;
+        let dnscom = MockServer::start(|_| (200, json!({ "code": 1, "message": "api_key 无效" }))).await;
+        let rainyun = MockServer::start(|_| (401, json!({ "code": 40100, "message": "unauthorized" }))).await;
+        let tencentcloud = MockServer::start(|_| {
+            (200, json!({ "Response": { "Error": { "Code": "AuthFailure.SignatureFailure", "Message": "bad signature" } } }))
+        })
+        .await;
+
+        let plain = PlainVault {
+            cloudflare: Some(CloudflareCreds {
+                api_token: "token".to_string(),
+                endpoint: Some(cloudflare.url.clone()),
+                last_verified_at: None,
+            }),
+            dnspod: Some(DnspodCreds {
+                token_id: "1".to_string(),
+                token: "token".to_string(),
+                secret_id: String::new(),
+                secret_key: String::new(),
+                endpoint: Some(dnspod.url.clone()),
+                last_verified_at: None,
+            }),
+            aliyun: Some(AliyunCreds {
+                access_key_id: "id".to_string(),
+                access_key_secret: "secret".to_string(),
+                security_token: None,
+                role_arn: None,
+                endpoint: Some(aliyun.url.clone()),
+                last_verified_at: None,
+            }),
+            huawei: Some(HuaweiCreds {
+                token: "token".to_string(),
+                access_key_id: String::new(),
+                secret_access_key: String::new(),
+                region: None,
+                project_id: None,
+                endpoint: Some(huawei.url.clone()),
+                last_verified_at: None,
+            }),
+            baidu: Some(BaiduCreds {
+                access_key_id: "id".to_string(),
+                secret_access_key: "secret".to_string(),
+                security_token: None,
+                endpoint: Some(baidu.url.clone()),
+                last_verified_at: None,
+            }),
+            dnscom: Some(DnscomCreds {
+                api_key: "key".to_string(),
+                api_secret: "secret".to_string(),
+                endpoint: Some(dnscom.url.clone()),
+                last_verified_at: None,
+            }),
+            rainyun: Some(RainyunCreds {
+                api_key: "key".to_string(),
+                endpoint: Some(rainyun.url.clone()),
+                last_verified_at: None,
+            }),
+            tencentcloud: Some(TencentCloudCreds {
+                secret_key: "secret".to_string(),
+                secret_id: "id".to_string(),
+                token: None,
+                role_arn: None,
+                endpoint: Some(tencentcloud.url.clone()),
+                last_verified_at: None,
+            }),
+            proxy: None,
+        };
+
+        let items = run_domains_list(plain, None, None).await.unwrap();
+
+        assert_eq!(items.len(), 8);
+        for item in &items {
+            assert!(matches!(item.status, DomainStatus::AuthFailed), "{:?}: {}", item.provider, item.name);
+        }
+    }
+
+    fn dnspod_vault(server: &MockServer) -> PlainVault {
+        PlainVault {
+            dnspod: Some(DnspodCreds {
+                token_id: "1".to_string(),
+                token: "token".to_string(),
+                secret_id: String::new(),
+                secret_key: String::new(),
+                endpoint: Some(server.url.clone()),
+                last_verified_at: None,
+            }),
+            ..PlainVault::default()
+        }
+    }
+
+    fn dnspod_ok(extra: serde_json::Value) -> (u16, serde_json::Value) {
+        let mut body = json!({ "status": { "code": "1", "message": "ok" } });
+        if let (Some(body), Some(extra)) = (body.as_object_mut(), extra.as_object()) {
+            body.extend(extra.clone());
+        }
+        (200, body)
+    }
+
+    #[test]
+    fn dnspod_api3_keys_come_in_pairs() {
+        let creds = |secret_id: &str, secret_key: &str| DnspodCreds {
+            token_id: "1".to_string(),
+            token: "token".to_string(),
+            secret_id: secret_id.to_string(),
+            secret_key: secret_key.to_string(),
+            endpoint: None,
+            last_verified_at: None,
+        };
+        assert_eq!(dnspod_client(&creds("AKID", "")).err().map(|e| e.code), Some("missing_field".to_string()));
+        assert_eq!(dnspod_client(&creds("", "secret")).err().map(|e| e.code), Some("missing_field".to_string()));
+        assert!(dnspod_client(&creds("AKID", "secret")).is_ok());
+        assert!(dnspod_client(&creds("", "")).is_ok());
+    }
+
+    #[tokio::test]
+    async fn weights_are_range_checked_before_any_write() {
+        let server = MockServer::start(|_| dnspod_ok(json!({}))).await;
+        let weights = vec![
+            RecordWeight { id: "1".to_string(), weight: 50 },
+            RecordWeight { id: "2".to_string(), weight: 150 },
+        ];
+
+        let err = run_record_set_weights(dnspod_vault(&server), Provider::Dnspod, "42".to_string(), "example.com".to_string(), weights)
+            .await
+            .unwrap_err();
+
+        assert_eq!(err.code, "invalid_weight");
+        assert_eq!(server.requests().len(), 0);
+    }
+
+    async fn dnspod_with_existing_records() -> MockServer {
+        MockServer::start(|req| match req.path.as_str() {
+            "/Record.List" => dnspod_ok(json!({
+                "info": { "record_total": "3" },
+                "records": [
+                    { "id": "1", "name": "www", "type": "A", "value": "1.1.1.1", "ttl": "600", "enabled": "1" },
+                    { "id": "2", "name": "www", "type": "A", "value": "2.2.2.2", "ttl": "600", "enabled": "1" },
+                    { "id": "3", "name": "mail", "type": "A", "value": "3.3.3.3", "ttl": "600", "enabled": "1" }
+                ]
+            })),
+            "/Record.Modify" => dnspod_ok(json!({ "record": { "id": req.param("record_id"), "name": "www", "status": "enable" } })),
+            _ => dnspod_ok(json!({})),
+        })
+        .await
+    }
+
+    #[tokio::test]
+    async fn replace_all_rewrites_one_record_and_removes_the_rest() {
+        let server = dnspod_with_existing_records().await;
+        let req = create_request("2.2.2.2", ConflictStrategy::ReplaceAll);
+
+        let record = run_record_create(dnspod_vault(&server), Provider::Dnspod, "42".into(), "example.com".into(), req)
+            .await
+            .unwrap();
+
+        assert_eq!(record.id, "2");
+        let requests = server.requests();
+        let paths: Vec<&str> = requests.iter().map(|r| r.path.as_str()).collect();
+        assert_eq!(paths, ["/Record.List", "/Record.Modify", "/Record.Remove"]);
+        assert_eq!(requests[1].param("record_id").as_deref(), Some("2"));
+        assert_eq!(requests[2].param("record_id").as_deref(), Some("1"));
+    }
+
+    #[tokio::test]
+    async fn replace_all_keeps_deleting_after_a_failure() {
+        let server = MockServer::start(|req| match req.path.as_str() {
+            "/Record.List" => dnspod_ok(json!({
+                "records": [
+                    { "id": "1", "name": "www", "type": "A", "value": "1.1.1.1", "ttl": "600", "enabled": "1" },
+                    { "id": "2", "name": "www", "type": "A", "value": "2.2.2.2", "ttl": "600", "enabled": "1" },
+                    { "id": "4", "name": "www", "type": "A", "value": "4.4.4.4", "ttl": "600", "enabled": "1" }
+                ]
+            })),
+            "/Record.Modify" => dnspod_ok(json!({ "record": { "id": "2", "name": "www", "status": "enable" } })),
+            "/Record.Remove" if req.param("record_id").as_deref() == Some("1") => {
+                (200, json!({ "status": { "code": "500", "message": "记录被锁定" } }))
+            }
+            _ => dnspod_ok(json!({})),
+        })
+        .await;
+        let req = create_request("2.2.2.2", ConflictStrategy::ReplaceAll);
+
+        let err = run_record_create(dnspod_vault(&server), Provider::Dnspod, "42".into(), "example.com".into(), req)
+            .await
+            .unwrap_err();
+
+        assert_eq!(err.code, "partial_failure");
+        assert!(err.message.contains("1 ("), "{}", err.message);
+        let removed: Vec<Option<String>> = server
+            .requests()
+            .iter()
+            .filter(|r| r.path == "/Record.Remove")
+            .map(|r| r.param("record_id"))
+            .collect();
+        assert_eq!(removed, [Some("1".to_string()), Some("4".to_string())]);
+    }
+
+    #[tokio::test]
+    async fn ambiguous_overwrite_makes_no_changes() {
+        let server = dnspod_with_existing_records().await;
+        let req = create_request("9.9.9.9", ConflictStrategy::Overwrite);
+
+        let err = run_record_create(dnspod_vault(&server), Provider::Dnspod, "42".into(), "example.com".into(), req)
+            .await
+            .unwrap_err();
+
+        assert_eq!(err.code, "conflict");
+        assert_eq!(server.count(|r| r.path != "/Record.List"), 0);
+    }
+
+    #[tokio::test]
+    async fn unicode_names_match_existing_records() {
+        let server = MockServer::start(|req| match req.path.as_str() {
+            "/Record.List" => dnspod_ok(json!({
+                "records": [{ "id": "7", "name": "中文", "type": "A", "value": "1.1.1.1", "ttl": "600", "enabled": "1" }]
+            })),
+            _ => dnspod_ok(json!({ "record": { "id": "7", "name": req.param("sub_domain"), "status": "enable" } })),
+        })
+        .await;
+        let mut req = create_request("2.2.2.2", ConflictStrategy::Overwrite);
+        req.name = "中文".to_string();
+
+        let record = run_record_create(dnspod_vault(&server), Provider::Dnspod, "42".into(), "例子.中国".into(), req)
+            .await
+            .map(idn::display_record)
+            .unwrap();
+
+        let requests = server.requests();
+        assert_eq!(requests[1].path, "/Record.Modify");
+        assert_eq!(requests[1].param("sub_domain").as_deref(), Some("xn--fiq228c"));
+        assert_eq!(record.name, "xn--fiq228c");
+        assert_eq!(record.domain, "xn--fsqu00a.xn--fiqs8s");
+        assert_eq!(record.display_name.as_deref(), Some("中文"));
+    }
+
+    #[tokio::test]
+    async fn update_checks_conflicts_only_when_renamed() {
+        let server = dnspod_with_existing_records().await;
+        let mut req = update_request_from(&record("3", "3.3.3.3"));
+        req.name = "mail".to_string();
+        req.original_name = Some("mail".to_string());
+        req.content = "4.4.4.4".to_string();
+
+        run_record_update(dnspod_vault(&server), Provider::Dnspod, "42".into(), "example.com".into(), req.clone())
+            .await
+            .unwrap();
+        assert_eq!(server.count(|r| r.path == "/Record.List"), 0);
+
+        req.record_type = "CNAME".to_string();
+        req.name = "www".to_string();
+        req.content = "cdn.example.net".to_string();
+        let err = run_record_update(dnspod_vault(&server), Provider::Dnspod, "42".into(), "example.com".into(), req)
+            .await
+            .unwrap_err();
+        assert_eq!(err.code, "cname_conflict");
+        assert_eq!(server.count(|r| r.path == "/Record.List"), 1);
+    }
+
+    #[tokio::test]
+    async fn url_forwards_use_native_types() {
+        let server = MockServer::start(|req| match req.path.as_str() {
+            "/Record.List" => dnspod_ok(json!({ "records": [] })),
+            _ => dnspod_ok(json!({ "record": { "id": "8", "name": "go", "status": "enable" } })),
+        })
+        .await;
+        let mut req = create_request("https://example.net/landing", ConflictStrategy::DoNotCreate);
+        req.record_type = "URL".to_string();
+        req.name = "go".to_string();
+        req.extra.url_redirect = Some(UrlRedirect::Implicit);
+
+        let record = run_record_create(dnspod_vault(&server), Provider::Dnspod, "42".into(), "example.com".into(), req)
+            .await
+            .map(forwarding::from_native)
+            .unwrap();
+
+        let requests = server.requests();
+        assert_eq!(requests[1].param("record_type").as_deref(), Some("隐性URL"));
+        assert_eq!(record.record_type, "URL");
+        assert_eq!(record.extra.url_redirect, Some(UrlRedirect::Implicit));
+    }
+
+    #[tokio::test]
+    async fn cloudflare_replace_all_uses_one_batch_call() {
+        let server = MockServer::start(|req| {
+            if req.method == "GET" {
+                return (
+                    200,
+                    json!({
+                        "success": true, "errors": [],
+                        "result": [
+                            { "id": "a", "type": "A", "name": "www.example.com", "content": "1.1.1.1", "ttl": 300 },
+                            { "id": "b", "type": "A", "name": "www.example.com", "content": "2.2.2.2", "ttl": 300 }
+                        ]
+                    }),
+                );
+            }
+            let mut record = req.json()["puts"][0].clone();
+            record["id"] = json!("a");
+            (200, json!({ "success": true, "errors": [], "result": { "puts": [record] } }))
+        })
+        .await;
+        let plain = PlainVault {
+            cloudflare: Some(CloudflareCreds {
+                api_token: "token".to_string(),
+                endpoint: Some(server.url.clone()),
+                last_verified_at: None,
+            }),
+            ..PlainVault::default()
+        };
+        let req = create_request("3.3.3.3", ConflictStrategy::ReplaceAll);
+
+        let record = run_record_create(plain, Provider::Cloudflare, "zone1".into(), "example.com".into(), req)
+            .await
+            .unwrap();
+
+        assert_eq!(record.content, "3.3.3.3");
+        let requests = server.requests();
+        assert_eq!(requests.len(), 2);
+        assert_eq!(requests[0].param("name").as_deref(), Some("www.example.com"));
+        assert!(requests[1].is("POST", "/zones/zone1/dns_records/batch"));
+        let batch = requests[1].json();
+        assert_eq!(batch["deletes"], json!([{ "id": "b" }]));
+        assert_eq!(batch["puts"][0]["id"], "a");
+    }
+
+    #[tokio::test]
+    async fn apex_alias_is_written_as_flattened_cname() {
+        let server = MockServer::start(|req| {
+            if req.method == "GET" {
+                return (200, json!({ "success": true, "errors": [], "result": [] }));
+            }
+            let mut record = req.json();
+            record["id"] = json!("r1");
+            (200, json!({ "success": true, "errors": [], "result": record }))
+        })
+        .await;
+        let plain = PlainVault {
+            cloudflare: Some(CloudflareCreds {
+                api_token: "token".to_string(),
+                endpoint: Some(server.url.clone()),
+                last_verified_at: None,
+            }),
+            ..PlainVault::default()
+        };
+        let mut req = create_request("例子.中国", ConflictStrategy::DoNotCreate);
+        req.record_type = "ALIAS".to_string();
+        req.name = "@".to_string();
+
+        let record = run_record_create(plain.clone(), Provider::Cloudflare, "zone1".into(), "example.com".into(), req.clone())
+            .await
+            .map(present_record)
+            .unwrap();
+
+        let sent = server.requests()[1].json();
+        assert_eq!((sent["type"].as_str(), sent["name"].as_str()), (Some("CNAME"), Some("example.com")));
+        assert_eq!(sent["content"], "xn--fsqu00a.xn--fiqs8s");
+        assert_eq!((record.record_type.as_str(), record.name.as_str()), ("ALIAS", "@"));
+
+        req.name = "www".to_string();
+        let err = run_record_create(plain, Provider::Cloudflare, "zone1".into(), "example.com".into(), req)
+            .await
+            .unwrap_err();
+        assert_eq!(err.code, "invalid_name");
+        assert_eq!(server.requests().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn mail_preset_replaces_only_what_it_supersedes() {
+        let server = MockServer::start(|req| match req.path.as_str() {
+            "/Record.List" => dnspod_ok(json!({
+                "info": { "record_total": "4" },
+                "records": [
+                    { "id": "1", "name": "@", "type": "MX", "value": "mx.old.example.net.", "mx": "10", "ttl": "600", "enabled": "1" },
+                    { "id": "2", "name": "@", "type": "TXT", "value": "v=spf1 include:old.example.net -all", "ttl": "600", "enabled": "1" },
+                    { "id": "3", "name": "@", "type": "TXT", "value": "google-site-verification=abc", "ttl": "600", "enabled": "1" },
+                    { "id": "4", "name": "_dmarc", "type": "TXT", "value": "v=DMARC1; p=none", "ttl": "600", "enabled": "1" }
+                ]
+            })),
+            "/Record.Create" => dnspod_ok(json!({ "record": { "id": "9", "name": req.param("sub_domain"), "status": "enable" } })),
+            "/Record.Remove" if req.param("record_id").as_deref() == Some("1") => {
+                (200, json!({ "status": { "code": "500", "message": "记录被锁定" } }))
+            }
+            _ => dnspod_ok(json!({})),
+        })
+        .await;
+        let params = HashMap::new();
+
+        let vault = dnspod_vault(&server);
+        let preview = run_mail_preset_preview(vault, Provider::Dnspod, "42".into(), "example.com".into(), "google_workspace", &params)
+            .await
+            .unwrap();
+        let actions: Vec<PresetAction> = preview.iter().map(|item| item.action).collect();
+        assert_eq!(actions, [PresetAction::Conflict, PresetAction::Conflict, PresetAction::Skip]);
+        assert_eq!(preview[1].conflicts.len(), 1);
+        assert_eq!(preview[1].conflicts[0].id, "2");
+
+        let results = run_mail_preset_apply(
+            dnspod_vault(&server),
+            Provider::Dnspod,
+            "42".into(),
+            "example.com".into(),
+            "google_workspace",
+            &params,
+            ConflictStrategy::DoNotCreate,
+        )
+        .await
+        .unwrap();
+        assert!(results.iter().take(2).all(|r| r.error.is_some()));
+        assert_eq!(server.count(|r| r.is("POST", "/Record.Create")), 0);
+
+        let results = run_mail_preset_apply(
+            dnspod_vault(&server),
+            Provider::Dnspod,
+            "42".into(),
+            "example.com".into(),
+            "google_workspace",
+            &params,
+            ConflictStrategy::ReplaceAll,
+        )
+        .await
+        .unwrap();
+        // 旧 MX 删除失败记在 MX 那一项上，不影响 SPF 的清理
+        assert!(results[0].record.is_some());
+        assert!(results[0].error.as_deref().is_some_and(|e| e.contains("旧记录 1")));
+        assert!(results.iter().skip(1).all(|r| r.error.is_none()));
+        assert_eq!(server.count(|r| r.is("POST", "/Record.Create")), 2);
+        let removed: Vec<_> = server
+            .requests()
+            .into_iter()
+            .filter(|r| r.is("POST", "/Record.Remove"))
+            .filter_map(|r| r.param("record_id"))
+            .collect();
+        assert_eq!(removed, ["1", "2"]);
+    }
+
+    #[tokio::test]
+    async fn records_search_fans_out_and_caches() {
+        let server = MockServer::start(|req| match req.path.as_str() {
+            "/Domain.List" => dnspod_ok(json!({
+                "domains": [
+                    { "id": 11, "name": "example.com", "records": "2", "updated_on": "2024-01-01 00:00:00" },
+                    { "id": 12, "name": "example.net", "records": "1", "updated_on": "2024-01-01 00:00:00" }
+                ]
+            })),
+            "/Record.List" if req.param("domain_id").as_deref() == Some("11") => dnspod_ok(json!({
+                "info": { "record_total": "2" },
+                "records": [
+                    { "id": "1", "name": "www", "type": "A", "value": "1.2.3.4", "ttl": "600", "enabled": "1" },
+                    { "id": "2", "name": "@", "type": "TXT", "value": "v=spf1 ip4:1.2.3.4 -all", "ttl": "600", "enabled": "1" }
+                ]
+            })),
+            "/Record.List" => dnspod_ok(json!({
+                "info": { "record_total": "1" },
+                "records": [
+                    { "id": "3", "name": "api", "type": "A", "value": "1.2.3.4", "ttl": "600", "enabled": "1" }
+                ]
+            })),
+            _ => dnspod_ok(json!({})),
+        })
+        .await;
+        let query = RecordSearchQuery {
+            content: Some("1.2.3.4".to_string()),
+            record_type: Some("A".to_string()),
+            ..Default::default()
+        };
+
+        let result = run_records_search(dnspod_vault(&server), query.clone()).await.unwrap();
+        assert_eq!(result.domains_searched, 2);
+        let hits: Vec<(&str, &str)> = result.hits.iter().map(|h| (h.domain_name.as_str(), h.record.id.as_str())).collect();
+        assert_eq!(hits, [("example.com", "1"), ("example.net", "3")]);
+        assert_eq!(result.hits[0].domain_id, "11");
+
+        run_records_search(dnspod_vault(&server), query.clone()).await.unwrap();
+        assert_eq!(server.count(|r| r.is("POST", "/Record.List")), 2);
+        drop(search::invalidate_on_exit(&dnspod_vault(&server), Provider::Dnspod, "11"));
+        run_records_search(dnspod_vault(&server), RecordSearchQuery { domain: Some("example.com".to_string()), ..query })
+            .await
+            .unwrap();
+        assert_eq!(server.count(|r| r.is("POST", "/Record.List")), 3);
+    }
+
+    #[tokio::test]
+    async fn bulk_replace_plans_applies_and_rolls_back() {
+        let server = MockServer::start(|req| match req.path.as_str() {
+            "/Domain.List" => dnspod_ok(json!({
+                "domains": [
+                    { "id": 21, "name": "example.com", "records": "2", "updated_on": "2024-01-01 00:00:00" },
+                    { "id": 22, "name": "example.net", "records": "1", "updated_on": "2024-01-01 00:00:00" }
+                ]
+            })),
+            "/Record.List" if req.param("domain_id").as_deref() == Some("21") => dnspod_ok(json!({
+                "info": { "record_total": "2" },
+                "records": [
+                    { "id": "1", "name": "www", "type": "A", "value": "1.2.3.4", "ttl": "600", "enabled": "1" },
+                    { "id": "2", "name": "old", "type": "A", "value": "1.2.3.40", "ttl": "600", "enabled": "1" }
+                ]
+            })),
+            "/Record.List" => dnspod_ok(json!({
+                "info": { "record_total": "1" },
+                "records": [
+                    { "id": "3", "name": "api", "type": "A", "value": "1.2.3.4", "ttl": "600", "enabled": "1" }
+                ]
+            })),
+            "/Record.Modify" if req.param("record_id").as_deref() == Some("3") => {
+                (200, json!({ "status": { "code": "500", "message": "记录被锁定" } }))
+            }
+            "/Record.Modify" => dnspod_ok(json!({ "record": { "id": req.param("record_id"), "name": "www", "status": "enable" } })),
+            _ => dnspod_ok(json!({})),
+        })
+        .await;
+        let mut req = BulkReplaceRequest {
+            record_type: Some("A".to_string()),
+            old_content: "1.2.3.4".to_string(),
+            new_content: Some("5.6.7.8".to_string()),
+            dry_run: true,
+            ..Default::default()
+        };
+
+        let plan = run_records_bulk_replace(dnspod_vault(&server), req.clone()).await.unwrap();
+        let planned: Vec<(&str, &str)> = plan.changes.iter().map(|c| (c.before.id.as_str(), c.after.content.as_str())).collect();
+        assert_eq!(planned, [("1", "5.6.7.8"), ("3", "5.6.7.8")]);
+        assert!(plan.changes.iter().all(|c| c.status == BulkChangeStatus::Planned));
+        assert_eq!(server.count(|r| r.is("POST", "/Record.Modify")), 0);
+
+        req.dry_run = false;
+        req.rollback_on_failure = true;
+        let err = run_records_bulk_replace(dnspod_vault(&server), req.clone()).await.unwrap_err();
+        assert_eq!(err.code, "missing_field");
+
+        req.plan = plan.changes.clone();
+        let result = run_records_bulk_replace(dnspod_vault(&server), req.clone()).await.unwrap();
+        let statuses: Vec<BulkChangeStatus> = result.changes.iter().map(|c| c.status).collect();
+        assert_eq!(statuses, [BulkChangeStatus::RolledBack, BulkChangeStatus::Failed]);
+        assert!(result.changes[1].error.is_some());
+        let modified: Vec<(Option<String>, Option<String>)> = server
+            .requests()
+            .into_iter()
+            .filter(|r| r.is("POST", "/Record.Modify"))
+            .map(|r| (r.param("record_id"), r.param("value")))
+            .collect();
+        assert_eq!(
+            modified,
+            [
+                (Some("1".to_string()), Some("5.6.7.8".to_string())),
+                (Some("3".to_string()), Some("5.6.7.8".to_string())),
+                (Some("1".to_string()), Some("1.2.3.4".to_string())),
+            ]
+        );
+
+        // 预览之后被改过的记录不再处理；计划里除记录值和 TTL 以外的改动不会写出去
+        req.plan[0].before.ttl = 300;
+        req.plan[1].after.name = "other".to_string();
+        req.plan[1].after.record_type = "CNAME".to_string();
+        req.rollback_on_failure = false;
+        let result = run_records_bulk_replace(dnspod_vault(&server), req).await.unwrap();
+        let statuses: Vec<BulkChangeStatus> = result.changes.iter().map(|c| c.status).collect();
+        assert_eq!(statuses, [BulkChangeStatus::Skipped, BulkChangeStatus::Failed]);
+        assert_eq!(server.count(|r| r.is("POST", "/Record.Modify")), 4);
+        let last = server.requests().into_iter().rfind(|r| r.is("POST", "/Record.Modify")).unwrap();
+        assert_eq!(last.param("sub_domain").as_deref(), Some("api"));
+        assert_eq!(last.param("record_type").as_deref(), Some("A"));
+        assert_eq!(last.param("value").as_deref(), Some("5.6.7.8"));
+    }
+}
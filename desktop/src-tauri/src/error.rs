@@ -1,23 +1,124 @@
+use crate::types::Provider;
 use serde::Serialize;
 
 #[derive(Debug, Serialize)]
 pub struct AppError {
     pub code: String,
     pub message: String,
+    pub retryable: bool,
+    // 上下文放在堆上，保持 Result<_, AppError> 足够小
+    #[serde(flatten)]
+    context: Option<Box<ErrorContext>>,
+}
+
+#[derive(Debug, Default, Serialize)]
+struct ErrorContext {
+    provider: Option<Provider>,
+    operation: Option<String>,
+    http_status: Option<u16>,
+    provider_code: Option<String>,
+    request_id: Option<String>,
 }
 
 impl AppError {
     pub fn new(code: impl Into<String>, message: impl Into<String>) -> Self {
+        let code = code.into();
+        let retryable = matches!(code.as_str(), "timeout" | "unreachable" | "network_error");
         Self {
-            code: code.into(),
+            code,
             message: message.into(),
+            retryable,
+            context: None,
         }
     }
+
+    /// 服务商接口返回的错误，限流和 5xx 视为可重试。
+    pub fn http(code: impl Into<String>, status: u16, message: impl Into<String>) -> Self {
+        let mut err = Self::new(code, message);
+        err.context_mut().http_status = Some(status);
+        err.retryable = status == 429 || status >= 500;
+        err
+    }
+
+    fn context_mut(&mut self) -> &mut ErrorContext {
+        self.context.get_or_insert_with(Default::default)
+    }
+
+    pub fn provider(&self) -> Option<Provider> {
+        self.context.as_ref().and_then(|c| c.provider)
+    }
+
+    pub fn operation(&self) -> Option<&str> {
+        self.context.as_ref().and_then(|c| c.operation.as_deref())
+    }
+
+    pub fn http_status(&self) -> Option<u16> {
+        self.context.as_ref().and_then(|c| c.http_status)
+    }
+
+    pub fn provider_code(&self) -> Option<&str> {
+        self.context.as_ref().and_then(|c| c.provider_code.as_deref())
+    }
+
+    pub fn request_id(&self) -> Option<&str> {
+        self.context.as_ref().and_then(|c| c.request_id.as_deref())
+    }
+
+    pub fn with_provider_code(mut self, provider_code: impl Into<String>) -> Self {
+        self.context_mut().provider_code = Some(provider_code.into()).filter(|c| !c.is_empty());
+        self
+    }
+
+    pub fn with_request_id(mut self, request_id: Option<String>) -> Self {
+        self.context_mut().request_id = request_id.filter(|id| !id.is_empty());
+        self
+    }
+
+    pub fn with_retryable(mut self, retryable: bool) -> Self {
+        self.retryable = retryable;
+        self
+    }
+
+    /// 在命令层补上服务商和操作名，已有的值不覆盖。
+    pub fn in_context(mut self, provider: Provider, operation: &str) -> Self {
+        let context = self.context_mut();
+        context.provider.get_or_insert(provider);
+        context.operation.get_or_insert_with(|| operation.to_string());
+        self
+    }
+
+    /// 记录已经创建，之后补设的字段（备注、权重）失败时改报部分成功，调用方不能当作没创建。
+    pub fn partially_created(self, record_id: &str, field: &str) -> Self {
+        self.partially_written(record_id, "created", field)
+    }
+
+    pub fn partially_updated(self, record_id: &str, field: &str) -> Self {
+        self.partially_written(record_id, "updated", field)
+    }
+
+    fn partially_written(self, record_id: &str, action: &str, field: &str) -> Self {
+        Self {
+            code: "partial_failure".to_string(),
+            message: format!("Record {record_id} was {action}, but its {field} could not be set: {}", self.message),
+            retryable: false,
+            context: self.context,
+        }
+    }
+
+    pub fn is_auth_failure(&self) -> bool {
+        matches!(self.http_status(), Some(401) | Some(403)) || self.code == "auth_failed"
+    }
+
+    // 只看连接失败和超时；服务商在响应体里报的限流虽然也可重试，但请求是到达了的
+    pub fn is_unreachable(&self) -> bool {
+        matches!(self.code.as_str(), "timeout" | "unreachable")
+    }
 }
 
 impl From<anyhow::Error> for AppError {
     fn from(value: anyhow::Error) -> Self {
-        Self::new("internal_error", value.to_string())
+        // 带上整条 context 链，否则只剩最外层的描述
+        Self::new("internal_error", format!("{value:#}"))
     }
 }
 
@@ -29,6 +130,38 @@ impl From<reqwest::Error> for AppError {
         if value.is_connect() {
             return Self::new("unreachable", value.to_string());
         }
-        Self::new("network_error", value.to_string())
+        if value.is_decode() {
+            return Self::new("json_decode_failed", value.to_string());
+        }
+        match value.status() {
+            Some(status) => Self::http("network_error", status.as_u16(), value.to_string()),
+            None => Self::new("network_error", value.to_string()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn classifies_http_failures() {
+        let throttled = AppError::http("fetch_failed", 429, "slow down");
+        assert!(throttled.retryable);
+        assert!(!throttled.is_unreachable());
+
+        let denied = AppError::http("fetch_failed", 403, "forbidden").in_context(Provider::Aliyun, "domains_list");
+        assert!(denied.is_auth_failure());
+        assert!(!denied.retryable);
+        assert_eq!(denied.provider(), Some(Provider::Aliyun));
+        assert_eq!(denied.operation(), Some("domains_list"));
+
+        assert!(AppError::new("timeout", "timed out").is_unreachable());
+
+        // 腾讯云等在 HTTP 200 的响应体里报限流，不能当成网络不通
+        let limited = AppError::new("fetch_failed", "RequestLimitExceeded")
+            .with_provider_code("RequestLimitExceeded")
+            .with_retryable(true);
+        assert!(!limited.is_unreachable());
     }
 }
@@ -0,0 +1,129 @@
+//! URL 转发记录。对外统一为 `URL` 类型，跳转方式放在 `url_redirect` 里，
+//! 发给服务商之前换成各家的原生类型，读回来时再换回 `URL`。
+
+use crate::error::AppError;
+use crate::types::{DnsRecord, Provider, RecordExtra, UrlRedirect};
+
+pub const URL_TYPE: &str = "URL";
+
+// 各家只有一种显性转发：DNSPod、腾讯云和 DNS.COM 的显性 URL 固定 301，阿里云 API 的显性 URL 是 302。
+// 没有的组合不做替换，校验时直接报错并列出可用的跳转方式
+fn native_types(provider: Provider) -> &'static [(UrlRedirect, &'static str)] {
+    match provider {
+        Provider::Dnspod | Provider::Tencentcloud | Provider::Dnscom => {
+            &[(UrlRedirect::Permanent, "显性URL"), (UrlRedirect::Implicit, "隐性URL")]
+        }
+        Provider::Aliyun => &[(UrlRedirect::Temporary, "REDIRECT_URL"), (UrlRedirect::Implicit, "FORWARD_URL")],
+        _ => &[],
+    }
+}
+
+pub fn supported(provider: Provider) -> bool {
+    !native_types(provider).is_empty()
+}
+
+pub fn native_type(provider: Provider, redirect: UrlRedirect) -> Option<&'static str> {
+    native_types(provider)
+        .iter()
+        .find(|(r, _)| *r == redirect)
+        .map(|(_, native)| *native)
+}
+
+/// 服务商支持的跳转方式，用于错误提示，如 `301 显性转发、隐性转发`。
+pub fn supported_labels(provider: Provider) -> String {
+    native_types(provider)
+        .iter()
+        .map(|(redirect, _)| redirect_label(*redirect))
+        .collect::<Vec<_>>()
+        .join("、")
+}
+
+pub fn redirect_label(redirect: UrlRedirect) -> &'static str {
+    match redirect {
+        UrlRedirect::Permanent => "301 显性转发",
+        UrlRedirect::Temporary => "302 显性转发",
+        UrlRedirect::Implicit => "隐性转发",
+    }
+}
+
+/// `URL` 类型换成原生类型，其他类型不变。
+pub fn to_native(provider: Provider, record_type: &mut String, extra: &RecordExtra) -> Result<(), AppError> {
+    if record_type != URL_TYPE {
+        return Ok(());
+    }
+    let redirect = extra
+        .url_redirect
+        .ok_or_else(|| AppError::new("missing_field", "URL 转发记录必须设置跳转方式"))?;
+    let native = native_type(provider, redirect).ok_or_else(|| {
+        AppError::new(
+            "unsupported",
+            format!("该服务商不支持{}，可用的跳转方式: {}", redirect_label(redirect), supported_labels(provider)),
+        )
+    })?;
+    *record_type = native.to_string();
+    Ok(())
+}
+
+pub fn from_native(mut record: DnsRecord) -> DnsRecord {
+    let native = native_types(record.provider)
+        .iter()
+        .find(|(_, native)| record.record_type.eq_ignore_ascii_case(native));
+    if let Some((redirect, _)) = native {
+        record.record_type = URL_TYPE.to_string();
+        record.extra.url_redirect = Some(*redirect);
+    }
+    record
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::providers::mock::{create_request, record};
+
+    #[test]
+    fn maps_redirects_per_provider() {
+        let mut req = create_request("URL", "go", "https://example.net/landing");
+        req.extra.url_redirect = Some(UrlRedirect::Implicit);
+        let mut record_type = req.record_type.clone();
+        to_native(Provider::Aliyun, &mut record_type, &req.extra).unwrap();
+        assert_eq!(record_type, "FORWARD_URL");
+
+        let mut record_type = req.record_type.clone();
+        to_native(Provider::Dnspod, &mut record_type, &req.extra).unwrap();
+        assert_eq!(record_type, "隐性URL");
+
+        req.extra.url_redirect = Some(UrlRedirect::Temporary);
+        let mut record_type = req.record_type.clone();
+        let err = to_native(Provider::Dnspod, &mut record_type, &req.extra).unwrap_err();
+        assert_eq!(err.code, "unsupported");
+        assert!(err.message.ends_with("301 显性转发、隐性转发"), "{}", err.message);
+        req.extra.url_redirect = Some(UrlRedirect::Permanent);
+        let mut record_type = req.record_type.clone();
+        assert!(to_native(Provider::Aliyun, &mut record_type, &req.extra).is_err());
+        assert_eq!(supported_labels(Provider::Aliyun), "302 显性转发、隐性转发");
+        assert_eq!(native_type(Provider::Cloudflare, UrlRedirect::Permanent), None);
+
+        let mut a = create_request("A", "www", "192.0.2.1").record_type;
+        to_native(Provider::Cloudflare, &mut a, &RecordExtra::default()).unwrap();
+        assert_eq!(a, "A");
+    }
+
+    fn native(provider: Provider, record_type: &str) -> DnsRecord {
+        DnsRecord {
+            provider,
+            ..record("1", record_type, "go", "https://example.net")
+        }
+    }
+
+    #[test]
+    fn native_records_read_back_as_url() {
+        let record = from_native(native(Provider::Tencentcloud, "显性URL"));
+        assert_eq!(record.record_type, URL_TYPE);
+        assert_eq!(record.extra.url_redirect, Some(UrlRedirect::Permanent));
+
+        let record = from_native(native(Provider::Aliyun, "forward_url"));
+        assert_eq!(record.extra.url_redirect, Some(UrlRedirect::Implicit));
+
+        assert_eq!(from_native(native(Provider::Cloudflare, "REDIRECT_URL")).record_type, "REDIRECT_URL");
+    }
+}
@@ -0,0 +1,118 @@
+//! 国际化域名在 Unicode 和 xn-- 形式之间的转换，统一按 UTS-46 处理。
+//! 发给服务商和参与比较的一律用 ASCII 形式，Unicode 形式只用于展示。
+
+use crate::error::AppError;
+use crate::types::{DnsRecord, DomainItem, DomainStatus};
+
+/// 转成 ASCII（xn--）形式。纯 ASCII 的名字原样返回，`@` 表示根域也保持不变。
+pub fn to_ascii(name: &str) -> Result<String, AppError> {
+    let name = name.trim();
+    if name.is_ascii() {
+        return Ok(name.to_string());
+    }
+    idna::domain_to_ascii(name).map_err(|_| AppError::new("invalid_name", format!("无效的国际化域名: {name}")))
+}
+
+/// 转成 Unicode 形式，没有 xn-- 标签或解码失败时原样返回。
+pub fn to_unicode(name: &str) -> String {
+    let has_punycode = name
+        .split('.')
+        .any(|label| label.get(..4).is_some_and(|prefix| prefix.eq_ignore_ascii_case("xn--")));
+    if !has_punycode {
+        return name.to_string();
+    }
+    match idna::domain_to_unicode(name) {
+        (unicode, Ok(())) => unicode,
+        _ => name.to_string(),
+    }
+}
+
+/// 按 ASCII 形式、忽略大小写比较两个名字。
+pub fn same_name(a: &str, b: &str) -> bool {
+    let a = to_ascii(a).unwrap_or_else(|_| a.to_string());
+    let b = to_ascii(b).unwrap_or_else(|_| b.to_string());
+    a.trim_end_matches('.').eq_ignore_ascii_case(b.trim_end_matches('.'))
+}
+
+fn display_name(ascii: &str) -> Option<String> {
+    let unicode = to_unicode(ascii);
+    (unicode != ascii).then_some(unicode)
+}
+
+pub fn display_domains(items: Vec<DomainItem>) -> Vec<DomainItem> {
+    items
+        .into_iter()
+        .map(|mut item| {
+            // 出错的条目 name 里是提示文字，不是域名
+            if matches!(item.status, DomainStatus::Ok) {
+                if let Ok(ascii) = to_ascii(&item.name) {
+                    item.name = ascii;
+                }
+                item.display_name = display_name(&item.name);
+            }
+            item
+        })
+        .collect()
+}
+
+pub fn display_record(mut record: DnsRecord) -> DnsRecord {
+    if let Ok(ascii) = to_ascii(&record.name) {
+        record.name = ascii;
+    }
+    if let Ok(ascii) = to_ascii(&record.domain) {
+        record.domain = ascii;
+    }
+    record.display_name = display_name(&record.name);
+    record
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::providers::mock::record;
+    use crate::types::Provider;
+
+    #[test]
+    fn converts_both_ways() {
+        assert_eq!(to_ascii("中文.com").unwrap(), "xn--fiq228c.com");
+        assert_eq!(to_ascii("例子.中国").unwrap(), "xn--fsqu00a.xn--fiqs8s");
+        assert_eq!(to_ascii("*._dmarc.中文").unwrap(), "*._dmarc.xn--fiq228c");
+        assert_eq!(to_ascii("@").unwrap(), "@");
+        assert_eq!(to_ascii("WWW").unwrap(), "WWW");
+        assert_eq!(to_unicode("xn--fsqu00a.xn--fiqs8s"), "例子.中国");
+        assert_eq!(to_unicode("www.XN--FIQ228C.com"), "www.中文.com");
+        assert_eq!(to_unicode("xn--zz.com"), "xn--zz.com");
+    }
+
+    #[test]
+    fn compares_across_forms() {
+        assert!(same_name("中文", "xn--fiq228c"));
+        assert!(same_name("WWW.中文.com.", "www.XN--FIQ228C.com"));
+        assert!(!same_name("中文", "英文"));
+    }
+
+    #[test]
+    fn records_carry_both_names() {
+        let record = DnsRecord {
+            domain: "例子.中国".to_string(),
+            ..record("1", "A", "中文", "192.0.2.1")
+        };
+
+        let record = display_record(record);
+
+        assert_eq!(record.name, "xn--fiq228c");
+        assert_eq!(record.domain, "xn--fsqu00a.xn--fiqs8s");
+        assert_eq!(record.display_name.as_deref(), Some("中文"));
+
+        let items = display_domains(vec![DomainItem {
+            provider: Provider::Cloudflare,
+            name: "xn--fiq228c.com".to_string(),
+            provider_id: "z".to_string(),
+            status: DomainStatus::Ok,
+            records_count: None,
+            last_changed_at: None,
+            display_name: None,
+        }]);
+        assert_eq!(items[0].display_name.as_deref(), Some("中文.com"));
+    }
+}
@@ -1,7 +1,17 @@
+mod alias;
 mod commands;
 mod error;
+mod forwarding;
+mod idn;
+mod lint;
+mod presets;
 mod providers;
+mod resolver;
+mod search;
+mod svcb;
+mod txt;
 mod types;
+mod validation;
 mod vault;
 
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
@@ -10,11 +20,19 @@ pub fn run() {
         .plugin(tauri_plugin_fs::init())
         .plugin(tauri_plugin_os::init())
         .plugin(tauri_plugin_opener::init())
+        .setup(|app| {
+            // 设置文件损坏时退回默认网络设置，用户可以在设置页重新保存
+            providers::http::configure(vault::read_network_settings(app.handle()).unwrap_or_default());
+            Ok(())
+        })
         .invoke_handler(tauri::generate_handler![
             commands::vault_status,
             commands::vault_initialize,
             commands::vault_unlock,
             commands::integrations_get,
+            commands::network_settings_get,
+            commands::network_settings_save,
+            commands::network_test,
             commands::cloudflare_test,
             commands::cloudflare_save,
             commands::cloudflare_clear,
@@ -43,7 +61,16 @@ pub fn run() {
             commands::records_list,
             commands::record_create,
             commands::record_update,
-            commands::record_delete
+            commands::record_delete,
+            commands::record_set_status,
+            commands::record_set_weights,
+            commands::mail_presets_list,
+            commands::mail_preset_preview,
+            commands::mail_preset_apply,
+            commands::zone_lint,
+            commands::records_search,
+            commands::records_bulk_replace,
+            commands::records_bulk_rollback
         ])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
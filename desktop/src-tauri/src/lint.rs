@@ -0,0 +1,585 @@
+//! 解析记录体检：找出 CNAME 冲突、悬空 CNAME、SPF/DMARC/CAA 配置问题、
+//! 被遮挡的通配符和偏离太多的 TTL。悬空 CNAME、MX 指向 CNAME 和 SPF 查询次数
+//! 需要查公网解析，解析器出错时跳过这几项并在结果里注明。
+
+use crate::resolver::{Lookup, Resolver, TYPE_CNAME};
+use crate::txt;
+use crate::types::{DnsRecord, LintFinding, LintSeverity, RecordStatus};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+
+// RFC 7208 4.6.4
+const SPF_LOOKUP_LIMIT: usize = 10;
+// 递归展开 include 时最多查询的域名数，防止循环引用
+const SPF_MAX_QUERIES: usize = 30;
+const TTL_OUTLIER_RATIO: u32 = 10;
+const TTL_MIN_SAMPLES: usize = 5;
+
+struct Zone<'a> {
+    domain: String,
+    records: &'a [DnsRecord],
+    findings: Vec<LintFinding>,
+    lookups: HashMap<String, Result<Lookup, String>>,
+    resolve_errors: BTreeSet<String>,
+}
+
+pub async fn lint_zone(
+    domain_name: &str,
+    records: &[DnsRecord],
+    resolver: &Resolver,
+    issuers: &[String],
+) -> Vec<LintFinding> {
+    // 暂停的记录不参与解析，不算进冲突和配置检查
+    let enabled: Vec<DnsRecord> = records
+        .iter()
+        .filter(|r| r.status == RecordStatus::Enabled)
+        .cloned()
+        .collect();
+    let mut zone = Zone {
+        domain: domain_name.trim_end_matches('.').to_ascii_lowercase(),
+        records: &enabled,
+        findings: Vec::new(),
+        lookups: HashMap::new(),
+        resolve_errors: BTreeSet::new(),
+    };
+    zone.check_cname_conflicts();
+    zone.check_dangling_cnames(resolver).await;
+    zone.check_mx_targets(resolver).await;
+    zone.check_spf(resolver).await;
+    zone.check_dmarc();
+    zone.check_caa(issuers);
+    zone.check_wildcards();
+    zone.check_ttls();
+    if !zone.resolve_errors.is_empty() {
+        let names: Vec<String> = zone.resolve_errors.into_iter().collect();
+        zone.findings.push(finding(
+            "resolver_failed",
+            LintSeverity::Info,
+            &zone.domain,
+            Vec::new(),
+            format!("以下域名查询失败，相关检查已跳过: {}", names.join(", ")),
+            "检查网络设置或更换解析器后重新体检",
+        ));
+    }
+    zone.findings.sort_by_key(|f| f.severity);
+    zone.findings
+}
+
+fn finding(
+    check: &str,
+    severity: LintSeverity,
+    name: &str,
+    record_ids: Vec<String>,
+    message: String,
+    suggestion: impl Into<String>,
+) -> LintFinding {
+    LintFinding {
+        check: check.to_string(),
+        severity,
+        name: name.to_string(),
+        record_ids,
+        message,
+        suggestion: suggestion.into(),
+    }
+}
+
+fn ids(records: &[&DnsRecord]) -> Vec<String> {
+    records.iter().map(|r| r.id.clone()).collect()
+}
+
+fn normalize_host(host: &str) -> String {
+    host.trim().trim_end_matches('.').to_ascii_lowercase()
+}
+
+fn is_spf(content: &str) -> bool {
+    let content = content.trim_start().to_ascii_lowercase();
+    content == "v=spf1" || content.starts_with("v=spf1 ")
+}
+
+// 本条 SPF 记录自身消耗的查询次数，以及需要继续展开的 include/redirect 目标
+fn spf_terms(spf: &str) -> (usize, Vec<String>) {
+    let mut lookups = 0;
+    let mut targets = Vec::new();
+    for term in spf.split_whitespace().skip(1) {
+        let term = term.trim_start_matches(['+', '-', '~', '?']);
+        let (mechanism, arg) = match term.find([':', '=', '/']) {
+            Some(i) => (&term[..i], &term[i + 1..]),
+            None => (term, ""),
+        };
+        match mechanism.to_ascii_lowercase().as_str() {
+            "a" | "mx" | "ptr" | "exists" => lookups += 1,
+            "include" | "redirect" => {
+                lookups += 1;
+                if !arg.is_empty() && !arg.contains('%') {
+                    targets.push(normalize_host(arg));
+                }
+            }
+            _ => {}
+        }
+    }
+    (lookups, targets)
+}
+
+impl<'a> Zone<'a> {
+    fn fqdn(&self, name: &str) -> String {
+        let name = normalize_host(name);
+        if name.is_empty() || name == "@" {
+            self.domain.clone()
+        } else if name == self.domain || name.ends_with(&format!(".{}", self.domain)) {
+            name
+        } else {
+            format!("{name}.{}", self.domain)
+        }
+    }
+
+    // 按完整域名分组，BTreeMap 让输出顺序稳定
+    fn by_name(&self) -> BTreeMap<String, Vec<&'a DnsRecord>> {
+        let mut groups: BTreeMap<String, Vec<&'a DnsRecord>> = BTreeMap::new();
+        for record in self.records {
+            groups.entry(self.fqdn(&record.name)).or_default().push(record);
+        }
+        groups
+    }
+
+    fn of_type(&self, record_type: &str) -> Vec<&'a DnsRecord> {
+        self.records.iter().filter(|r| r.record_type == record_type).collect()
+    }
+
+    fn in_zone(&self, host: &str) -> bool {
+        host == self.domain || host.ends_with(&format!(".{}", self.domain))
+    }
+
+    async fn lookup(&mut self, resolver: &Resolver, host: &str) -> Option<Lookup> {
+        let key = host.to_string();
+        if !self.lookups.contains_key(&key) {
+            let result = resolver.lookup(host, "A").await.map_err(|e| e.message);
+            self.lookups.insert(key.clone(), result);
+        }
+        match &self.lookups[&key] {
+            Ok(lookup) => Some(lookup.clone()),
+            Err(_) => {
+                self.resolve_errors.insert(key);
+                None
+            }
+        }
+    }
+
+    fn check_cname_conflicts(&mut self) {
+        let mut found = Vec::new();
+        for (name, group) in self.by_name() {
+            let cnames = group.iter().filter(|r| r.record_type == "CNAME").count();
+            if cnames == 0 || (cnames == 1 && group.len() == 1) {
+                continue;
+            }
+            let others: BTreeSet<&str> = group
+                .iter()
+                .filter(|r| r.record_type != "CNAME")
+                .map(|r| r.record_type.as_str())
+                .collect();
+            let message = if others.is_empty() {
+                format!("{name} 有 {cnames} 条 CNAME 记录")
+            } else {
+                let others: Vec<&str> = others.into_iter().collect();
+                format!("{name} 的 CNAME 与 {} 记录共存", others.join("/"))
+            };
+            let suggestion = if name == self.domain {
+                "根域不能使用 CNAME，改用 ALIAS 或 A/AAAA 记录"
+            } else {
+                "CNAME 不能和同名的其他记录共存，删除多余的记录或把 CNAME 换成 A/AAAA"
+            };
+            found.push(finding("cname_conflict", LintSeverity::Error, &name, ids(&group), message, suggestion));
+        }
+        self.findings.extend(found);
+    }
+
+    async fn check_dangling_cnames(&mut self, resolver: &Resolver) {
+        let names: HashSet<String> = self.records.iter().map(|r| self.fqdn(&r.name)).collect();
+        let records = self.records;
+        for record in records.iter().filter(|r| matches!(r.record_type.as_str(), "CNAME" | "ALIAS")) {
+            let target = normalize_host(&record.content);
+            let name = self.fqdn(&record.name);
+            // 区域里有这个名称就不用再查；没有时可能被通配符或子域委派覆盖，交给解析器判断
+            let dangling = !names.contains(&target)
+                && self.lookup(resolver, &target).await.is_some_and(|lookup| lookup.nxdomain);
+            if dangling {
+                self.findings.push(finding(
+                    "dangling_cname",
+                    LintSeverity::Error,
+                    &name,
+                    vec![record.id.clone()],
+                    format!("{name} 指向的 {target} 不存在 (NXDOMAIN)"),
+                    "删除这条记录，或改为指向仍在使用的目标，避免子域名被他人接管",
+                ));
+            }
+        }
+    }
+
+    async fn check_mx_targets(&mut self, resolver: &Resolver) {
+        let records = self.records;
+        for record in records.iter().filter(|r| r.record_type == "MX") {
+            let target = normalize_host(&record.content);
+            let name = self.fqdn(&record.name);
+            let is_cname = if self.in_zone(&target) {
+                self.records
+                    .iter()
+                    .any(|r| r.record_type == "CNAME" && self.fqdn(&r.name) == target)
+            } else {
+                self.lookup(resolver, &target)
+                    .await
+                    .is_some_and(|lookup| lookup.answers.iter().any(|a| a.record_type == TYPE_CNAME))
+            };
+            if is_cname {
+                self.findings.push(finding(
+                    "mx_cname",
+                    LintSeverity::Error,
+                    &name,
+                    vec![record.id.clone()],
+                    format!("MX 记录指向的 {target} 是 CNAME"),
+                    "RFC 2181 不允许 MX 指向别名，改为指向有 A/AAAA 记录的主机名",
+                ));
+            }
+        }
+    }
+
+    async fn check_spf(&mut self, resolver: &Resolver) {
+        let mut by_name: BTreeMap<String, Vec<&DnsRecord>> = BTreeMap::new();
+        for record in self.of_type("TXT") {
+            if is_spf(&txt::decode(&record.content)) {
+                by_name.entry(self.fqdn(&record.name)).or_default().push(record);
+            }
+        }
+        for (name, spf) in by_name {
+            if spf.len() > 1 {
+                self.findings.push(finding(
+                    "spf_multiple",
+                    LintSeverity::Error,
+                    &name,
+                    ids(&spf),
+                    format!("{name} 有 {} 条 SPF 记录，收件方会直接判定 SPF 失败", spf.len()),
+                    "把所有 include 合并到一条 v=spf1 记录里",
+                ));
+            }
+            for record in spf {
+                let (mut lookups, mut pending) = spf_terms(&txt::decode(&record.content));
+                let mut seen = HashSet::new();
+                while let Some(target) = pending.pop() {
+                    if seen.len() >= SPF_MAX_QUERIES || !seen.insert(target.clone()) {
+                        continue;
+                    }
+                    match resolver.txt(&target).await {
+                        Ok(values) => {
+                            if let Some(nested) = values.iter().find(|v| is_spf(v)) {
+                                let (count, targets) = spf_terms(nested);
+                                lookups += count;
+                                pending.extend(targets);
+                            }
+                        }
+                        Err(_) => {
+                            self.resolve_errors.insert(target);
+                        }
+                    }
+                }
+                if lookups > SPF_LOOKUP_LIMIT {
+                    self.findings.push(finding(
+                        "spf_lookups",
+                        LintSeverity::Error,
+                        &name,
+                        vec![record.id.clone()],
+                        format!("SPF 展开后需要 {lookups} 次 DNS 查询，超过 {SPF_LOOKUP_LIMIT} 次的上限"),
+                        "去掉不再使用的 include，或把 a/mx 机制换成 ip4/ip6",
+                    ));
+                }
+            }
+        }
+    }
+
+    fn check_dmarc(&mut self) {
+        let dmarc_name = format!("_dmarc.{}", self.domain);
+        let has_dmarc = self.of_type("TXT").iter().any(|r| {
+            self.fqdn(&r.name) == dmarc_name
+                && txt::decode(&r.content).trim_start().to_ascii_lowercase().starts_with("v=dmarc1")
+        });
+        if has_dmarc {
+            return;
+        }
+        let sends_mail = self
+            .records
+            .iter()
+            .any(|r| r.record_type == "MX" || (r.record_type == "TXT" && is_spf(&txt::decode(&r.content))));
+        let (severity, suggestion) = if sends_mail {
+            (LintSeverity::Warning, "添加 _dmarc TXT 记录，例如 v=DMARC1; p=quarantine; rua=mailto:dmarc@你的域名")
+        } else {
+            (LintSeverity::Info, "域名不收发邮件时，可添加 _dmarc TXT 记录 v=DMARC1; p=reject 防止被冒用")
+        };
+        self.findings.push(finding(
+            "dmarc_missing",
+            severity,
+            &dmarc_name,
+            Vec::new(),
+            format!("{} 没有 DMARC 记录", self.domain),
+            suggestion,
+        ));
+    }
+
+    fn check_caa(&mut self, issuers: &[String]) {
+        let issuers: Vec<String> = issuers.iter().map(|i| normalize_host(i)).filter(|i| !i.is_empty()).collect();
+        let caa: Vec<&DnsRecord> = self
+            .of_type("CAA")
+            .into_iter()
+            .filter(|r| self.fqdn(&r.name) == self.domain)
+            .collect();
+        if caa.is_empty() {
+            let suggestion = match issuers.first() {
+                Some(issuer) => format!("添加根域 CAA 记录，例如 0 issue \"{issuer}\""),
+                None => "添加根域 CAA 记录，只允许实际使用的证书签发机构".to_string(),
+            };
+            let message = format!("{} 没有 CAA 记录，任何证书签发机构都可以为它签发证书", self.domain);
+            let domain = self.domain.clone();
+            self.findings
+                .push(finding("caa_missing", LintSeverity::Info, &domain, Vec::new(), message, suggestion));
+            return;
+        }
+        // 某个标签一条都没有时不受它限制（RFC 8659）
+        let allowed = |tag: &str| -> Option<HashSet<String>> {
+            let values: HashSet<String> = caa
+                .iter()
+                .filter(|r| r.caa_tag.as_deref().is_some_and(|t| t.eq_ignore_ascii_case(tag)))
+                .map(|r| normalize_host(r.content.trim().trim_matches('"').split(';').next().unwrap_or_default()))
+                .collect();
+            (!values.is_empty()).then_some(values)
+        };
+        let issue = allowed("issue");
+        // 有 issuewild 时通配符证书只看它，没有时沿用 issue
+        let issuewild = allowed("issuewild").or_else(|| issue.clone());
+        let blocked = |set: &Option<HashSet<String>>| -> Vec<&str> {
+            issuers
+                .iter()
+                .filter(|i| set.as_ref().is_some_and(|s| !s.contains(*i)))
+                .map(String::as_str)
+                .collect()
+        };
+        let missing = blocked(&issue);
+        let missing_wild: Vec<&str> = blocked(&issuewild).into_iter().filter(|i| !missing.contains(i)).collect();
+        if !missing.is_empty() {
+            self.findings.push(finding(
+                "caa_missing",
+                LintSeverity::Error,
+                &self.domain,
+                ids(&caa),
+                format!("CAA 记录没有授权 {}，这些机构无法为域名签发证书", missing.join(", ")),
+                format!("添加 CAA 记录 0 issue \"{}\"", missing[0]),
+            ));
+        }
+        if !missing_wild.is_empty() {
+            self.findings.push(finding(
+                "caa_missing",
+                LintSeverity::Warning,
+                &self.domain,
+                ids(&caa),
+                format!("CAA 的 issuewild 没有授权 {}，这些机构无法签发通配符证书", missing_wild.join(", ")),
+                format!("需要通配符证书时添加 CAA 记录 0 issuewild \"{}\"", missing_wild[0]),
+            ));
+        }
+    }
+
+    fn check_wildcards(&mut self) {
+        let groups = self.by_name();
+        let mut found = Vec::new();
+        for (wildcard, records) in &groups {
+            let Some(parent) = wildcard.strip_prefix("*.") else {
+                continue;
+            };
+            let covered: BTreeSet<&str> = records.iter().map(|r| r.record_type.as_str()).collect();
+            // parent 下一级的名称都会让通配符失效，包括只因下级有记录而存在的空节点
+            let mut shadowing: BTreeMap<String, Vec<&DnsRecord>> = BTreeMap::new();
+            for (name, group) in &groups {
+                if name == wildcard {
+                    continue;
+                }
+                let Some(prefix) = name.strip_suffix(&format!(".{parent}")) else {
+                    continue;
+                };
+                let label = prefix.rsplit('.').next().unwrap_or(prefix);
+                let child = format!("{label}.{parent}");
+                let entry = shadowing.entry(child.clone()).or_default();
+                if *name == child {
+                    entry.extend(group.iter().copied());
+                }
+            }
+            for (name, group) in shadowing {
+                let present: BTreeSet<&str> = group.iter().map(|r| r.record_type.as_str()).collect();
+                if present.contains("CNAME") {
+                    continue;
+                }
+                let lost: Vec<&str> = covered.difference(&present).copied().collect();
+                if lost.is_empty() {
+                    continue;
+                }
+                found.push(finding(
+                    "wildcard_shadowing",
+                    LintSeverity::Warning,
+                    &name,
+                    ids(&group),
+                    format!("{name} 已有其他记录，{wildcard} 的 {} 记录不会对它生效", lost.join("/")),
+                    format!("如果 {name} 也需要这些解析，为它单独添加 {} 记录", lost.join("/")),
+                ));
+            }
+        }
+        self.findings.extend(found);
+    }
+
+    fn check_ttls(&mut self) {
+        // Cloudflare 的 1 表示自动，不参与统计
+        let mut ttls: Vec<u32> = self.records.iter().map(|r| r.ttl).filter(|ttl| *ttl > 1).collect();
+        if ttls.len() < TTL_MIN_SAMPLES {
+            return;
+        }
+        ttls.sort_unstable();
+        let median = ttls[ttls.len() / 2];
+        let mut found = Vec::new();
+        for record in self.records.iter().filter(|r| r.ttl > 1) {
+            let low = record.ttl.saturating_mul(TTL_OUTLIER_RATIO) < median;
+            let high = record.ttl > median.saturating_mul(TTL_OUTLIER_RATIO);
+            if !low && !high {
+                continue;
+            }
+            let name = self.fqdn(&record.name);
+            let message = format!(
+                "{name} 的 {} 记录 TTL 为 {} 秒，与区域中位数 {median} 秒相差 {TTL_OUTLIER_RATIO} 倍以上",
+                record.record_type, record.ttl
+            );
+            let suggestion = if low {
+                "过低的 TTL 会增加解析查询量，迁移完成后调回常规值"
+            } else {
+                "过高的 TTL 会让修改迟迟不生效，变更前先调低"
+            };
+            found.push(finding("ttl_outlier", LintSeverity::Info, &name, vec![record.id.clone()], message, suggestion));
+        }
+        self.findings.extend(found);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::providers::mock::{record, MockServer};
+    use serde_json::json;
+
+    fn checks(findings: &[LintFinding]) -> Vec<(&str, &str)> {
+        findings.iter().map(|f| (f.check.as_str(), f.name.as_str())).collect()
+    }
+
+    async fn resolver(server: &MockServer) -> Resolver {
+        Resolver::new(Some(&format!("{}/resolve", server.url))).unwrap()
+    }
+
+    #[tokio::test]
+    async fn reports_static_zone_problems() {
+        let server = MockServer::start(|_| (200, json!({ "Status": 0, "Answer": [] }))).await;
+        let mut caa = record("9", "CAA", "@", "letsencrypt.org");
+        caa.caa_tag = Some("issue".to_string());
+        let mut slow = record("10", "A", "legacy", "192.0.2.10");
+        slow.ttl = 86400;
+        let records = vec![
+            record("1", "CNAME", "www", "cdn.example.com"),
+            record("2", "TXT", "www", "hello"),
+            record("3", "A", "cdn", "192.0.2.1"),
+            record("4", "A", "*.app", "192.0.2.2"),
+            record("5", "TXT", "_acme-challenge.api.app", "token"),
+            record("6", "TXT", "@", "v=spf1 include:_spf.example.net ~all"),
+            record("7", "TXT", "@", "\"v=spf1 -all\""),
+            record("8", "A", "@", "192.0.2.3"),
+            caa,
+            slow,
+        ];
+
+        let findings = lint_zone("example.com", &records, &resolver(&server).await, &["pki.goog".to_string()]).await;
+        let found = checks(&findings);
+        assert!(found.contains(&("cname_conflict", "www.example.com")));
+        assert!(found.contains(&("spf_multiple", "example.com")));
+        assert!(found.contains(&("dmarc_missing", "_dmarc.example.com")));
+        assert!(found.contains(&("caa_missing", "example.com")));
+        assert!(found.contains(&("wildcard_shadowing", "api.app.example.com")));
+        assert!(found.contains(&("ttl_outlier", "legacy.example.com")));
+        assert!(!found.iter().any(|(check, _)| *check == "dangling_cname" || *check == "resolver_failed"));
+        assert_eq!(findings[0].severity, LintSeverity::Error);
+        let dmarc = findings.iter().find(|f| f.check == "dmarc_missing").unwrap();
+        assert_eq!(dmarc.severity, LintSeverity::Warning);
+    }
+
+    #[tokio::test]
+    async fn caa_honours_issuewild_and_skips_disabled_records() {
+        let server = MockServer::start(|_| (200, json!({ "Status": 0, "Answer": [] }))).await;
+        let caa = |id: &str, tag: &str, issuer: &str| DnsRecord {
+            caa_tag: Some(tag.to_string()),
+            ..record(id, "CAA", "@", issuer)
+        };
+        let disabled_txt = DnsRecord {
+            status: RecordStatus::Disabled,
+            ..record("4", "TXT", "www", "hello")
+        };
+        let records = vec![
+            caa("1", "issue", "letsencrypt.org"),
+            caa("2", "issue", "pki.goog"),
+            caa("3", "issuewild", "letsencrypt.org"),
+            record("5", "CNAME", "www", "cdn.example.net"),
+            disabled_txt,
+        ];
+        let issuers = ["letsencrypt.org".to_string(), "pki.goog".to_string()];
+
+        let findings = lint_zone("example.com", &records, &resolver(&server).await, &issuers).await;
+        let caa_findings: Vec<&LintFinding> = findings.iter().filter(|f| f.check == "caa_missing").collect();
+        assert_eq!(caa_findings.len(), 1);
+        assert_eq!(caa_findings[0].severity, LintSeverity::Warning);
+        assert!(caa_findings[0].message.contains("issuewild 没有授权 pki.goog"));
+        assert!(!findings.iter().any(|f| f.check == "cname_conflict"));
+
+        // 只有 iodef 时签发不受限制
+        let records = vec![caa("1", "iodef", "mailto:security@example.com")];
+        let findings = lint_zone("example.com", &records, &resolver(&server).await, &issuers).await;
+        assert!(!findings.iter().any(|f| f.check == "caa_missing"));
+    }
+
+    #[tokio::test]
+    async fn resolves_targets_and_spf_includes() {
+        let server = MockServer::start(|req| {
+            let name = req.param("name").unwrap_or_default();
+            match (name.as_str(), req.param("type").as_deref()) {
+                ("gone.example.net" | "missing.example.com", _) => (200, json!({ "Status": 3 })),
+                ("mail.example.net", _) => (200, json!({ "Status": 0, "Answer": [
+                    { "name": "mail.example.net.", "type": 5, "data": "mx.hosting.example." },
+                    { "name": "mx.hosting.example.", "type": 1, "data": "192.0.2.5" }
+                ] })),
+                ("broken.example.net", _) => (502, json!({})),
+                (_, Some("TXT")) => {
+                    // 每一层 include 都带两个 a/mx 查询，再 include 下一层
+                    let depth: usize = name.trim_start_matches("spf").split('.').next().unwrap_or("0").parse().unwrap_or(0);
+                    let data = format!("\"v=spf1 a mx include:spf{}.example.net ~all\"", depth + 1);
+                    (200, json!({ "Status": 0, "Answer": [{ "name": name, "type": 16, "data": data }] }))
+                }
+                _ => (200, json!({ "Status": 0, "Answer": [] })),
+            }
+        })
+        .await;
+        let mut mx = record("3", "MX", "@", "mail.example.net.");
+        mx.mx_priority = Some(10);
+        let records = vec![
+            record("1", "CNAME", "old", "gone.example.net."),
+            record("2", "CNAME", "shop", "broken.example.net"),
+            mx,
+            record("4", "TXT", "@", "v=spf1 include:spf0.example.net -all"),
+            record("5", "CNAME", "blog", "missing.example.com"),
+            record("6", "TXT", "_dmarc", "v=DMARC1; p=none"),
+        ];
+
+        let findings = lint_zone("example.com", &records, &resolver(&server).await, &[]).await;
+        let found = checks(&findings);
+        assert!(found.contains(&("dangling_cname", "old.example.com")));
+        assert!(found.contains(&("dangling_cname", "blog.example.com")));
+        assert!(found.contains(&("mx_cname", "example.com")));
+        assert!(found.contains(&("spf_lookups", "example.com")));
+        assert!(!found.iter().any(|(check, _)| *check == "dmarc_missing"));
+        let failed = findings.iter().find(|f| f.check == "resolver_failed").unwrap();
+        assert!(failed.message.contains("broken.example.net"));
+        assert_eq!(server.count(|r| r.param("name").as_deref() == Some("mail.example.net")), 1);
+    }
+}
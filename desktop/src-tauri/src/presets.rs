@@ -0,0 +1,233 @@
+//! 常见企业邮箱的 DNS 模板（MX、SPF、DKIM、DMARC 等）。
+//! 模板里的 `{key}` 用参数替换，`{domain}` 和 `{domain_token}` 由域名生成，
+//! 引用了可选参数但没有填写的记录会被跳过。
+
+use crate::error::AppError;
+use crate::types::{ConflictStrategy, MailPresetInfo, MailPresetParam, RecordCreateRequest, RecordExtra};
+use std::collections::HashMap;
+
+const PRESET_TTL: u32 = 600;
+
+struct Param {
+    key: &'static str,
+    label: &'static str,
+    required: bool,
+    default: Option<&'static str>,
+}
+
+struct Template {
+    record_type: &'static str,
+    name: &'static str,
+    content: &'static str,
+    mx_priority: Option<u16>,
+}
+
+struct Preset {
+    id: &'static str,
+    label: &'static str,
+    params: &'static [Param],
+    records: &'static [Template],
+}
+
+const fn param(key: &'static str, label: &'static str, required: bool, default: Option<&'static str>) -> Param {
+    Param { key, label, required, default }
+}
+
+const fn mx(content: &'static str, priority: u16) -> Template {
+    Template { record_type: "MX", name: "@", content, mx_priority: Some(priority) }
+}
+
+const fn record(record_type: &'static str, name: &'static str, content: &'static str) -> Template {
+    Template { record_type, name, content, mx_priority: None }
+}
+
+const DKIM_VALUE: Param = param("dkim_value", "DKIM 公钥记录值", false, None);
+const DMARC: Template = record("TXT", "_dmarc", "v=DMARC1; p=none");
+const DKIM: Template = record("TXT", "{dkim_selector}._domainkey", "{dkim_value}");
+
+const PRESETS: &[Preset] = &[
+    Preset {
+        id: "tencent_exmail",
+        label: "腾讯企业邮箱",
+        params: &[param("dkim_selector", "DKIM 选择器", true, Some("qq")), DKIM_VALUE],
+        records: &[
+            mx("mxbiz1.qq.com", 5),
+            mx("mxbiz2.qq.com", 10),
+            record("TXT", "@", "v=spf1 include:spf.mail.qq.com ~all"),
+            DKIM,
+            DMARC,
+        ],
+    },
+    Preset {
+        id: "aliyun_qiye",
+        label: "阿里云企业邮箱",
+        params: &[param("dkim_selector", "DKIM 选择器", true, Some("aliyun-cn-hangzhou")), DKIM_VALUE],
+        records: &[
+            mx("mx1.qiye.aliyun.com", 5),
+            mx("mx2.qiye.aliyun.com", 10),
+            mx("mx3.qiye.aliyun.com", 15),
+            record("TXT", "@", "v=spf1 include:spf.qiye.aliyun.com -all"),
+            DKIM,
+            DMARC,
+        ],
+    },
+    Preset {
+        id: "netease_qiye",
+        label: "网易企业邮箱",
+        params: &[param("dkim_selector", "DKIM 选择器", true, Some("default")), DKIM_VALUE],
+        records: &[
+            mx("qiye163mx01.mxmail.netease.com", 5),
+            mx("qiye163mx02.mxmail.netease.com", 10),
+            record("TXT", "@", "v=spf1 include:spf.163.com -all"),
+            DKIM,
+            DMARC,
+        ],
+    },
+    Preset {
+        id: "google_workspace",
+        label: "Google Workspace",
+        params: &[param("dkim_selector", "DKIM 选择器", true, Some("google")), DKIM_VALUE],
+        records: &[
+            mx("smtp.google.com", 1),
+            record("TXT", "@", "v=spf1 include:_spf.google.com ~all"),
+            DKIM,
+            DMARC,
+        ],
+    },
+    Preset {
+        id: "microsoft_365",
+        label: "Microsoft 365",
+        params: &[param("tenant", "租户名（xxx.onmicrosoft.com 中的 xxx）", true, None)],
+        records: &[
+            mx("{domain_token}.mail.protection.outlook.com", 0),
+            record("TXT", "@", "v=spf1 include:spf.protection.outlook.com -all"),
+            record("CNAME", "autodiscover", "autodiscover.outlook.com"),
+            record("CNAME", "selector1._domainkey", "selector1-{domain_token}._domainkey.{tenant}.onmicrosoft.com"),
+            record("CNAME", "selector2._domainkey", "selector2-{domain_token}._domainkey.{tenant}.onmicrosoft.com"),
+            DMARC,
+        ],
+    },
+];
+
+pub fn list() -> Vec<MailPresetInfo> {
+    PRESETS
+        .iter()
+        .map(|preset| MailPresetInfo {
+            id: preset.id.to_string(),
+            label: preset.label.to_string(),
+            params: preset
+                .params
+                .iter()
+                .map(|p| MailPresetParam {
+                    key: p.key.to_string(),
+                    label: p.label.to_string(),
+                    required: p.required,
+                    default_value: p.default.map(|d| d.to_string()),
+                })
+                .collect(),
+        })
+        .collect()
+}
+
+/// 按参数展开模板，生成待创建的记录。`domain_name` 为 ASCII 形式。
+pub fn render(
+    preset_id: &str,
+    domain_name: &str,
+    params: &HashMap<String, String>,
+) -> Result<Vec<RecordCreateRequest>, AppError> {
+    let preset = PRESETS
+        .iter()
+        .find(|p| p.id == preset_id)
+        .ok_or_else(|| AppError::new("invalid_preset", format!("未知的邮箱模板: {preset_id}")))?;
+
+    let domain = domain_name.trim_end_matches('.').to_ascii_lowercase();
+    let mut values = HashMap::from([("domain_token".to_string(), domain.replace('.', "-")), ("domain".to_string(), domain)]);
+    for p in preset.params {
+        let value = params
+            .get(p.key)
+            .map(|v| v.trim().to_string())
+            .filter(|v| !v.is_empty())
+            .or_else(|| p.default.map(|d| d.to_string()));
+        match value {
+            Some(value) => {
+                values.insert(p.key.to_string(), value);
+            }
+            None if p.required => {
+                return Err(AppError::new("missing_field", format!("{} 不能为空", p.label)));
+            }
+            None => {}
+        }
+    }
+
+    let mut out = Vec::new();
+    for template in preset.records {
+        let (Some(name), Some(content)) = (fill(template.name, &values), fill(template.content, &values)) else {
+            continue;
+        };
+        out.push(RecordCreateRequest {
+            record_type: template.record_type.to_string(),
+            name,
+            content,
+            ttl: PRESET_TTL,
+            conflict_strategy: ConflictStrategy::DoNotCreate,
+            mx_priority: template.mx_priority,
+            srv_priority: None,
+            srv_weight: None,
+            srv_port: None,
+            caa_flags: None,
+            caa_tag: None,
+            comment: Some(preset.label.to_string()),
+            tags: None,
+            weight: None,
+            extra: RecordExtra::default(),
+        });
+    }
+    Ok(out)
+}
+
+// 有未填写的占位符时返回 None
+fn fill(template: &str, values: &HashMap<String, String>) -> Option<String> {
+    let mut out = String::new();
+    let mut rest = template;
+    while let Some(start) = rest.find('{') {
+        let end = rest[start..].find('}')? + start;
+        out.push_str(&rest[..start]);
+        out.push_str(values.get(&rest[start + 1..end])?);
+        rest = &rest[end + 1..];
+    }
+    out.push_str(rest);
+    Some(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn params(pairs: &[(&str, &str)]) -> HashMap<String, String> {
+        pairs.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect()
+    }
+
+    #[test]
+    fn optional_records_are_skipped_until_filled() {
+        let records = render("google_workspace", "example.com", &params(&[])).unwrap();
+        assert_eq!(records.len(), 3);
+        assert!(records.iter().all(|r| !r.name.ends_with("_domainkey")));
+
+        let records = render("google_workspace", "example.com", &params(&[("dkim_value", "v=DKIM1; p=MIIB")])).unwrap();
+        let dkim = records.iter().find(|r| r.name == "google._domainkey").unwrap();
+        assert_eq!(dkim.content, "v=DKIM1; p=MIIB");
+    }
+
+    #[test]
+    fn derived_and_required_params() {
+        let err = render("microsoft_365", "example.com", &params(&[])).unwrap_err();
+        assert_eq!(err.code, "missing_field");
+
+        let records = render("microsoft_365", "Example.co.uk.", &params(&[("tenant", "contoso")])).unwrap();
+        assert_eq!(records[0].content, "example-co-uk.mail.protection.outlook.com");
+        assert_eq!(records[0].mx_priority, Some(0));
+        assert_eq!(records[3].content, "selector1-example-co-uk._domainkey.contoso.onmicrosoft.com");
+
+        assert_eq!(render("unknown", "example.com", &params(&[])).unwrap_err().code, "invalid_preset");
+    }
+}
@@ -1,32 +1,68 @@
 use crate::error::AppError;
-use crate::types::{DnsRecord, DomainItem, DomainStatus, Provider, RecordCreateRequest, RecordUpdateRequest};
+use crate::providers::http::{self, Call};
+use crate::providers::sts::{self, TemporaryCredentials};
+use crate::txt;
+use crate::types::{
+    DnsRecord, DomainItem, DomainStatus, Provider, RecordCreateRequest, RecordExtra, RecordStatus,
+    RecordUpdateRequest, RecordWeight,
+};
 use base64::Engine;
 use chrono::{DateTime, Utc};
 use hmac::{Hmac, Mac};
 use serde::Deserialize;
 use sha1::Sha1;
 use std::collections::BTreeMap;
-use std::time::Duration;
 
-const API_BASE: &str = "https://alidns.aliyuncs.com";
+pub const API_BASE: &str = "https://alidns.aliyuncs.com";
+const API_VERSION: &str = "2015-01-09";
+const STS_BASE: &str = "https://sts.aliyuncs.com";
+const STS_VERSION: &str = "2015-04-01";
+const RECORDS_PER_PAGE: u32 = 500;
 
 pub struct AliyunClient {
     client: reqwest::Client,
+    base_url: String,
     access_key_id: String,
     access_key_secret: String,
+    security_token: Option<String>,
 }
 
 impl AliyunClient {
     pub fn new(access_key_id: String, access_key_secret: String) -> Result<Self, AppError> {
-        let client = reqwest::Client::builder()
-            .user_agent("LaoChenDNS/0.1.0")
-            .timeout(Duration::from_secs(30))
-            .build()
-            .map_err(AppError::from)?;
+        let client = http::client()?;
         Ok(Self {
             client,
+            base_url: API_BASE.to_string(),
             access_key_id,
             access_key_secret,
+            security_token: None,
+        })
+    }
+
+    pub fn with_endpoint(mut self, endpoint: Option<&str>) -> Result<Self, AppError> {
+        self.base_url = http::endpoint_or(endpoint, API_BASE)?;
+        Ok(self)
+    }
+
+    pub fn with_security_token(mut self, security_token: Option<String>) -> Self {
+        self.security_token = security_token.filter(|t| !t.trim().is_empty());
+        self
+    }
+
+    pub async fn assume_role(&self, role_arn: &str) -> Result<TemporaryCredentials, AppError> {
+        let mut params = self.common_params("AssumeRole");
+        params.insert("Version".to_string(), STS_VERSION.to_string());
+        params.insert("RoleArn".to_string(), role_arn.to_string());
+        params.insert("RoleSessionName".to_string(), sts::ROLE_SESSION_NAME.to_string());
+        params.insert("DurationSeconds".to_string(), sts::ROLE_DURATION_SECS.to_string());
+        let resp: AliyunAssumeRoleResponse = self.request_to(STS_BASE, params).await?;
+        let expires_at = parse_aliyun_time(&resp.credentials.expiration)
+            .map_err(|e| AppError::new("json_decode_failed", e.to_string()))?;
+        Ok(TemporaryCredentials {
+            access_key_id: resp.credentials.access_key_id,
+            access_key_secret: resp.credentials.access_key_secret,
+            security_token: resp.credentials.security_token,
+            expires_at,
         })
     }
 
@@ -47,33 +83,33 @@ impl AliyunClient {
                     name: d.domain_name.clone(),
                     provider_id: d.domain_id.clone(),
                     status: DomainStatus::Ok,
-                    records_count: d.record_count.and_then(|v| v.parse::<u32>().ok()),
+                    records_count: d.record_count.as_ref().and_then(count_value),
                     last_changed_at: d
                         .update_time
                         .as_deref()
                         .and_then(|s| parse_aliyun_time(s).ok())
                         .map(|d| d.to_rfc3339()),
+                    display_name: None,
                 });
             }
         }
         Ok(items)
     }
 
-    pub async fn list_records(&self, domain_id: &str, domain_name: &str) -> Result<Vec<DnsRecord>, AppError> {
+    pub async fn list_records(&self, _domain_id: &str, domain_name: &str) -> Result<Vec<DnsRecord>, AppError> {
+        let items = http::paginate(|page| self.records_page(domain_name, page)).await?;
+        Ok(items.into_iter().map(|item| item.to_dns_record(domain_name)).collect())
+    }
+
+    async fn records_page(&self, domain_name: &str, page: u32) -> Result<(Vec<AliyunRecord>, bool), AppError> {
         let mut params = self.common_params("DescribeDomainRecords");
         params.insert("DomainName".to_string(), domain_name.to_string());
-        params.insert("PageSize".to_string(), "500".to_string());
+        params.insert("PageNumber".to_string(), page.to_string());
+        params.insert("PageSize".to_string(), RECORDS_PER_PAGE.to_string());
         let parsed: AliyunRecordListResponse = self.request(params).await?;
-        let mut records = Vec::new();
-        if let Some(items) = parsed.domain_records.and_then(|r| r.record) {
-            for item in items {
-                records.push(item.to_dns_record(domain_name));
-            }
-        }
-        if records.is_empty() {
-            let _ = domain_id;
-        }
-        Ok(records)
+        let records = parsed.domain_records.and_then(|r| r.record).unwrap_or_default();
+        let more = http::more_pages(page, RECORDS_PER_PAGE, records.len(), parsed.total_count);
+        Ok((records, more))
     }
 
     pub async fn create_record(&self, _domain_id: &str, domain_name: &str, req: &RecordCreateRequest) -> Result<DnsRecord, AppError> {
@@ -109,6 +145,17 @@ impl AliyunClient {
         }
         let parsed: AliyunRecordCreateResponse = self.request(params).await?;
         let record_id = parsed.record_id.unwrap_or_default();
+        // AddDomainRecord 没有备注和权重参数，只能创建后再设置
+        if let Some(comment) = req.comment.as_deref().filter(|c| !c.is_empty()) {
+            self.set_remark(&record_id, comment)
+                .await
+                .map_err(|e| e.partially_created(&record_id, "remark"))?;
+        }
+        if let Some(weight) = req.weight {
+            self.set_weight(domain_name, &req.name, &record_id, weight)
+                .await
+                .map_err(|e| e.partially_created(&record_id, "weight"))?;
+        }
         Ok(DnsRecord {
             id: record_id,
             provider: Provider::Aliyun,
@@ -123,41 +170,76 @@ impl AliyunClient {
             srv_port: req.srv_port,
             caa_flags: req.caa_flags,
             caa_tag: req.caa_tag.clone(),
+            status: RecordStatus::Enabled,
+            comment: req.comment.clone().filter(|c| !c.is_empty()),
+            tags: Vec::new(),
+            weight: req.weight,
+            display_name: None,
+            extra: RecordExtra::default(),
         })
     }
 
     pub async fn update_record(&self, _domain_id: &str, domain_name: &str, req: &RecordUpdateRequest) -> Result<DnsRecord, AppError> {
-        let mut params = self.common_params("UpdateDomainRecord");
-        params.insert("RecordId".to_string(), req.id.clone());
-        params.insert("RR".to_string(), req.name.clone());
-        params.insert("Type".to_string(), req.record_type.clone());
-        params.insert(
-            "Value".to_string(),
-            aliyun_value(
-                &req.record_type,
-                &req.content,
-                req.srv_priority,
-                req.srv_weight,
-                req.srv_port,
-                req.caa_flags,
-                req.caa_tag.as_deref(),
-            ),
+        // 记录值、备注和权重是三个接口，只调用确实有变化的那几个
+        let current = self.current_record(domain_name, req).await?;
+        let value = aliyun_value(
+            &req.record_type,
+            &req.content,
+            req.srv_priority,
+            req.srv_weight,
+            req.srv_port,
+            req.caa_flags,
+            req.caa_tag.as_deref(),
         );
-        params.insert("TTL".to_string(), req.ttl.to_string());
-        if req.record_type == "MX" || req.record_type == "SRV" {
-            if let Some(priority) = req.mx_priority.or(req.srv_priority) {
+        let priority = match req.record_type.as_str() {
+            "MX" | "SRV" => req.mx_priority.or(req.srv_priority),
+            _ => None,
+        };
+        let current_value = aliyun_value(
+            &current.record_type,
+            &current.content,
+            current.srv_priority,
+            current.srv_weight,
+            current.srv_port,
+            current.caa_flags,
+            current.caa_tag.as_deref(),
+        );
+        let unchanged = current.record_type == req.record_type
+            && current.name == req.name
+            && current_value == value
+            && current.ttl == req.ttl
+            && (priority.is_none() || current.mx_priority == priority);
+        if !unchanged {
+            let mut params = self.common_params("UpdateDomainRecord");
+            params.insert("RecordId".to_string(), req.id.clone());
+            params.insert("RR".to_string(), req.name.clone());
+            params.insert("Type".to_string(), req.record_type.clone());
+            params.insert("Value".to_string(), value);
+            params.insert("TTL".to_string(), req.ttl.to_string());
+            if let Some(priority) = priority {
                 params.insert("Priority".to_string(), priority.to_string());
             }
+            if req.record_type == "SRV" {
+                if let Some(weight) = req.srv_weight {
+                    params.insert("Weight".to_string(), weight.to_string());
+                }
+                if let Some(port) = req.srv_port {
+                    params.insert("Port".to_string(), port.to_string());
+                }
+            }
+            let _: AliyunRecordCreateResponse = self.request(params).await?;
         }
-        if req.record_type == "SRV" {
-            if let Some(weight) = req.srv_weight {
-                params.insert("Weight".to_string(), weight.to_string());
+        if let Some(comment) = req.comment.as_deref() {
+            if current.comment.as_deref().unwrap_or_default() != comment {
+                self.set_remark(&req.id, comment).await?;
             }
-            if let Some(port) = req.srv_port {
-                params.insert("Port".to_string(), port.to_string());
+        }
+        // 普通修改不开启负载均衡，只在已有权重时改权重；开启由 set_record_weights 负责
+        if let Some(weight) = req.weight {
+            if current.weight != Some(weight) {
+                self.update_weight(&req.id, weight).await?;
             }
         }
-        let _: AliyunRecordCreateResponse = self.request(params).await?;
         Ok(DnsRecord {
             id: req.id.clone(),
             provider: Provider::Aliyun,
@@ -172,9 +254,32 @@ impl AliyunClient {
             srv_port: req.srv_port,
             caa_flags: req.caa_flags,
             caa_tag: req.caa_tag.clone(),
+            status: current.status,
+            comment: req.comment.clone().or(current.comment).filter(|c| !c.is_empty()),
+            tags: Vec::new(),
+            weight: req.weight.or(current.weight),
+            display_name: None,
+            extra: RecordExtra::default(),
         })
     }
 
+    // 按修改前的主机记录查询，改名时也能找到原记录
+    async fn current_record(&self, domain_name: &str, req: &RecordUpdateRequest) -> Result<DnsRecord, AppError> {
+        let rr = req.original_name.as_deref().unwrap_or(&req.name);
+        let mut params = self.common_params("DescribeSubDomainRecords");
+        params.insert("SubDomain".to_string(), sub_domain(rr, domain_name));
+        params.insert("PageSize".to_string(), RECORDS_PER_PAGE.to_string());
+        let parsed: AliyunRecordListResponse = self.request(params).await?;
+        parsed
+            .domain_records
+            .and_then(|r| r.record)
+            .unwrap_or_default()
+            .into_iter()
+            .find(|r| r.record_id == req.id)
+            .map(|r| r.to_dns_record(domain_name))
+            .ok_or_else(|| AppError::new("not_found", format!("Record {} not found", req.id)))
+    }
+
     pub async fn delete_record(&self, _domain_id: &str, record_id: &str) -> Result<(), AppError> {
         let mut params = self.common_params("DeleteDomainRecord");
         params.insert("RecordId".to_string(), record_id.to_string());
@@ -182,34 +287,127 @@ impl AliyunClient {
         Ok(())
     }
 
+    pub async fn set_record_status(&self, _domain_id: &str, record_id: &str, status: RecordStatus) -> Result<(), AppError> {
+        let mut params = self.common_params("SetDomainRecordStatus");
+        params.insert("RecordId".to_string(), record_id.to_string());
+        let status = match status {
+            RecordStatus::Enabled => "Enable",
+            RecordStatus::Disabled => "Disable",
+        };
+        params.insert("Status".to_string(), status.to_string());
+        let _: AliyunRecordCreateResponse = self.request(params).await?;
+        Ok(())
+    }
+
+    pub async fn set_record_weights(&self, domain_name: &str, weights: &[RecordWeight]) -> Result<(), AppError> {
+        let records = self.list_records("", domain_name).await?;
+        for item in weights {
+            let record = records
+                .iter()
+                .find(|r| r.id == item.id)
+                .ok_or_else(|| AppError::new("not_found", format!("Record {} not found", item.id)))?;
+            self.set_weight(domain_name, &record.name, &item.id, item.weight).await?;
+        }
+        Ok(())
+    }
+
+    async fn set_weight(&self, domain_name: &str, rr: &str, record_id: &str, weight: u16) -> Result<(), AppError> {
+        // 权重只在开启负载均衡（SLB）的子域名上生效
+        let mut params = self.common_params("SetDNSSLBStatus");
+        params.insert("SubDomain".to_string(), sub_domain(rr, domain_name));
+        params.insert("Open".to_string(), "true".to_string());
+        let _: serde_json::Value = self.request(params).await?;
+        self.update_weight(record_id, weight).await
+    }
+
+    async fn update_weight(&self, record_id: &str, weight: u16) -> Result<(), AppError> {
+        let mut params = self.common_params("UpdateDNSSLBWeight");
+        params.insert("RecordId".to_string(), record_id.to_string());
+        params.insert("Weight".to_string(), weight.to_string());
+        let _: AliyunRecordCreateResponse = self.request(params).await?;
+        Ok(())
+    }
+
+    async fn set_remark(&self, record_id: &str, remark: &str) -> Result<(), AppError> {
+        let mut params = self.common_params("UpdateDomainRecordRemark");
+        params.insert("RecordId".to_string(), record_id.to_string());
+        params.insert("Remark".to_string(), remark.to_string());
+        let _: AliyunRecordCreateResponse = self.request(params).await?;
+        Ok(())
+    }
+
     fn common_params(&self, action: &str) -> BTreeMap<String, String> {
         let mut params = BTreeMap::new();
         params.insert("Action".to_string(), action.to_string());
         params.insert("Format".to_string(), "JSON".to_string());
-        params.insert("Version".to_string(), "2015-01-09".to_string());
+        params.insert("Version".to_string(), API_VERSION.to_string());
         params.insert("AccessKeyId".to_string(), self.access_key_id.clone());
         params.insert("SignatureMethod".to_string(), "HMAC-SHA1".to_string());
         params.insert("SignatureVersion".to_string(), "1.0".to_string());
-        params.insert("SignatureNonce".to_string(), format!("{:x}", rand::random::<u128>()));
-        params.insert("Timestamp".to_string(), Utc::now().format("%Y-%m-%dT%H:%M:%SZ").to_string());
+        if let Some(token) = &self.security_token {
+            params.insert("SecurityToken".to_string(), token.clone());
+        }
         params
     }
 
-    async fn request<T: serde::de::DeserializeOwned>(&self, mut params: BTreeMap<String, String>) -> Result<T, AppError> {
-        let signature = self.sign(&params)?;
-        params.insert("Signature".to_string(), signature);
-        let res = self
-            .client
-            .get(API_BASE)
-            .query(&params)
-            .send()
-            .await
-            .map_err(AppError::from)?;
+    async fn request<T: serde::de::DeserializeOwned>(&self, params: BTreeMap<String, String>) -> Result<T, AppError> {
+        self.request_to(&self.base_url, params).await
+    }
+
+    async fn request_to<T: serde::de::DeserializeOwned>(
+        &self,
+        endpoint: &str,
+        params: BTreeMap<String, String>,
+    ) -> Result<T, AppError> {
+        let call = match params.get("Action") {
+            Some(action) if action.starts_with("Describe") => Call::Read,
+            _ => Call::Write,
+        };
+        http::retry(call, || self.request_once(endpoint, call, &params)).await
+    }
+
+    async fn request_once<T: serde::de::DeserializeOwned>(
+        &self,
+        endpoint: &str,
+        call: Call,
+        params: &BTreeMap<String, String>,
+    ) -> Result<T, AppError> {
+        let res = http::send(Provider::Aliyun, call, || {
+            // SignatureNonce 不能重复使用，每次重试都重新签名
+            let mut params = params.clone();
+            sign_rpc(&self.access_key_secret, &mut params, &format!("{:x}", rand::random::<u128>()), Utc::now());
+            Ok(self.client.get(endpoint).query(&params))
+        })
+        .await?;
         let status = res.status();
         let text = res.text().await.map_err(AppError::from)?;
+        // 阿里云的错误响应不论 HTTP 状态码都带 Code 和 RequestId
+        if let Ok(err) = serde_json::from_str::<AliyunErrorResponse>(&text) {
+            if let Some(code) = err.code {
+                let message = err.message.unwrap_or_else(|| code.clone());
+                let error_code = if code.contains("AccessKey") || code.contains("Signature") || code.contains("SecurityToken") {
+                    "auth_failed"
+                } else {
+                    "fetch_failed"
+                };
+                let retryable = code.starts_with("Throttling")
+                    || code.starts_with("ServiceUnavailable")
+                    || code.starts_with("InternalError");
+                let error = if status.is_success() {
+                    AppError::new(error_code, message)
+                } else {
+                    AppError::http(error_code, status.as_u16(), message)
+                };
+                return Err(error
+                    .with_provider_code(code)
+                    .with_request_id(err.request_id)
+                    .with_retryable(retryable));
+            }
+        }
         if !status.is_success() {
-            return Err(AppError::new(
+            return Err(AppError::http(
                 "fetch_failed",
+                status.as_u16(),
                 format!(
                     "HTTP {}: {} (response text: {})",
                     status.as_u16(),
@@ -218,17 +416,6 @@ impl AliyunClient {
                 ),
             ));
         }
-        if let Ok(err) = serde_json::from_str::<AliyunErrorResponse>(&text) {
-            if let Some(code) = err.code {
-                let message = err.message.unwrap_or_else(|| code.clone());
-                let error_code = if code.contains("AccessKey") || code.contains("Signature") {
-                    "auth_failed"
-                } else {
-                    "fetch_failed"
-                };
-                return Err(AppError::new(error_code, message));
-            }
-        }
         serde_json::from_str::<T>(&text).map_err(|e| {
             AppError::new(
                 "json_decode_failed",
@@ -236,19 +423,14 @@ impl AliyunClient {
             )
         })
     }
+}
 
-    fn sign(&self, params: &BTreeMap<String, String>) -> Result<String, AppError> {
-        let mut canonicalized: Vec<String> = Vec::new();
-        for (k, v) in params {
-            canonicalized.push(format!("{}={}", percent_encode(k), percent_encode(v)));
-        }
-        let canonicalized_query = canonicalized.join("&");
-        let string_to_sign = format!("GET&%2F&{}", percent_encode(&canonicalized_query));
-        let key = format!("{}&", self.access_key_secret);
-        let mut mac = Hmac::<Sha1>::new_from_slice(key.as_bytes()).map_err(|e| AppError::new("invalid_key", e.to_string()))?;
-        mac.update(string_to_sign.as_bytes());
-        let signature = base64::engine::general_purpose::STANDARD.encode(mac.finalize().into_bytes());
-        Ok(signature)
+// RecordCount 文档里是数字，这里也兼容字符串
+fn count_value(value: &serde_json::Value) -> Option<u32> {
+    match value {
+        serde_json::Value::Number(n) => n.as_u64().and_then(|n| u32::try_from(n).ok()),
+        serde_json::Value::String(s) => s.parse::<u32>().ok(),
+        _ => None,
     }
 }
 
@@ -265,6 +447,14 @@ fn parse_aliyun_time(value: &str) -> Result<DateTime<Utc>, anyhow::Error> {
     Ok(dt.with_timezone(&Utc))
 }
 
+fn sub_domain(rr: &str, domain_name: &str) -> String {
+    if rr == "@" || rr.is_empty() {
+        domain_name.to_string()
+    } else {
+        format!("{rr}.{domain_name}")
+    }
+}
+
 fn aliyun_value(
     record_type: &str,
     content: &str,
@@ -292,12 +482,50 @@ fn aliyun_value(
     }
 }
 
+/// 阿里云 RPC 风格签名（HMAC-SHA1）：补上 SignatureNonce、Timestamp 后计算 Signature 并写回参数。
+fn sign_rpc(access_key_secret: &str, params: &mut BTreeMap<String, String>, nonce: &str, now: DateTime<Utc>) {
+    params.insert("SignatureNonce".to_string(), nonce.to_string());
+    params.insert("Timestamp".to_string(), now.format("%Y-%m-%dT%H:%M:%SZ").to_string());
+    params.remove("Signature");
+    let canonicalized_query = params
+        .iter()
+        .map(|(k, v)| format!("{}={}", percent_encode(k), percent_encode(v)))
+        .collect::<Vec<_>>()
+        .join("&");
+    let string_to_sign = format!("GET&%2F&{}", percent_encode(&canonicalized_query));
+    let key = format!("{access_key_secret}&");
+    let mut mac = Hmac::<Sha1>::new_from_slice(key.as_bytes()).expect("hmac can take key of any size");
+    mac.update(string_to_sign.as_bytes());
+    let signature = base64::engine::general_purpose::STANDARD.encode(mac.finalize().into_bytes());
+    params.insert("Signature".to_string(), signature);
+}
+
 #[derive(Debug, Deserialize)]
 struct AliyunErrorResponse {
     #[serde(rename = "Code")]
     code: Option<String>,
     #[serde(rename = "Message")]
     message: Option<String>,
+    #[serde(rename = "RequestId")]
+    request_id: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct AliyunAssumeRoleResponse {
+    #[serde(rename = "Credentials")]
+    credentials: AliyunStsCredentials,
+}
+
+#[derive(Debug, Deserialize)]
+struct AliyunStsCredentials {
+    #[serde(rename = "AccessKeyId")]
+    access_key_id: String,
+    #[serde(rename = "AccessKeySecret")]
+    access_key_secret: String,
+    #[serde(rename = "SecurityToken")]
+    security_token: String,
+    #[serde(rename = "Expiration")]
+    expiration: String,
 }
 
 #[derive(Debug, Deserialize)]
@@ -319,13 +547,15 @@ struct AliyunDomain {
     #[serde(rename = "DomainName")]
     domain_name: String,
     #[serde(rename = "RecordCount")]
-    record_count: Option<String>,
+    record_count: Option<serde_json::Value>,
     #[serde(rename = "UpdateTime")]
     update_time: Option<String>,
 }
 
 #[derive(Debug, Deserialize)]
 struct AliyunRecordListResponse {
+    #[serde(rename = "TotalCount")]
+    total_count: Option<u64>,
     #[serde(rename = "DomainRecords")]
     domain_records: Option<AliyunDomainRecords>,
 }
@@ -350,6 +580,12 @@ struct AliyunRecord {
     ttl: u32,
     #[serde(rename = "Priority")]
     priority: Option<u16>,
+    #[serde(rename = "Status")]
+    status: Option<String>,
+    #[serde(rename = "Remark")]
+    remark: Option<String>,
+    #[serde(rename = "Weight")]
+    weight: Option<u16>,
 }
 
 impl AliyunRecord {
@@ -370,6 +606,15 @@ impl AliyunRecord {
             srv_port,
             caa_flags,
             caa_tag,
+            status: match self.status.as_deref() {
+                Some("DISABLE") => RecordStatus::Disabled,
+                _ => RecordStatus::Enabled,
+            },
+            comment: self.remark.filter(|r| !r.is_empty()),
+            tags: Vec::new(),
+            weight: self.weight,
+            display_name: None,
+            extra: RecordExtra::default(),
         }
     }
 }
@@ -408,6 +653,205 @@ fn parse_aliyun_record_value(
                 (value.to_string(), None, None, None, None, None)
             }
         }
+        "TXT" => (txt::decode(value), None, None, None, None, None),
         _ => (value.to_string(), None, None, None, None, None),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::providers::mock::{self, create_request, update_request, MockServer};
+    use serde_json::json;
+
+    // 云解析 DNS 签名机制文档中的示例请求
+    #[test]
+    fn signs_request_like_documented_example() {
+        let mut params: BTreeMap<String, String> = [
+            ("Format", "XML"),
+            ("AccessKeyId", "testid"),
+            ("Action", "DescribeDomainRecords"),
+            ("SignatureMethod", "HMAC-SHA1"),
+            ("DomainName", "example.com"),
+            ("SignatureVersion", "1.0"),
+            ("Version", "2015-01-09"),
+        ]
+        .into_iter()
+        .map(|(k, v)| (k.to_string(), v.to_string()))
+        .collect();
+        let now = DateTime::parse_from_rfc3339("2016-03-24T16:41:54Z").unwrap().with_timezone(&Utc);
+
+        sign_rpc("testsecret", &mut params, "f59ed6a9-83fc-473b-9cc6-99c95df3856e", now);
+
+        assert_eq!(params["Timestamp"], "2016-03-24T16:41:54Z");
+        assert_eq!(params["Signature"], "uRpHwaSEt3J+6KQD//svCh/x+pI=");
+    }
+
+    #[test]
+    fn percent_encodes_like_rfc3986() {
+        assert_eq!(percent_encode("a b*c~d+e/f"), "a%20b%2Ac~d%2Be%2Ff");
+        assert_eq!(percent_encode("中"), "%E4%B8%AD");
+    }
+
+    fn client(server: &MockServer) -> AliyunClient {
+        server.client(|endpoint| AliyunClient::new("testid".to_string(), "testsecret".to_string())?.with_endpoint(endpoint))
+    }
+
+    fn record(id: u32, rr: &str, record_type: &str, value: &str) -> serde_json::Value {
+        json!({ "RecordId": id.to_string(), "RR": rr, "Type": record_type, "Value": value, "TTL": 600, "Status": "ENABLE" })
+    }
+
+    #[tokio::test]
+    async fn lists_records_across_pages() {
+        let server = MockServer::start(|req| {
+            let page = req.param("PageNumber").unwrap_or_default();
+            let records = mock::two_pages(
+                page == "1",
+                RECORDS_PER_PAGE,
+                |i| record(i, "www", "A", "192.0.2.1"),
+                vec![
+                    record(9001, "_sip._tcp", "SRV", "10 20 5060 sip.example.com"),
+                    json!({ "RecordId": "9002", "RR": "@", "Type": "CAA", "Value": "128 issuewild \"pki.goog\"", "TTL": 600, "Status": "DISABLE", "Remark": "证书" }),
+                    json!({ "RecordId": "9003", "RR": "@", "Type": "MX", "Value": "mx.example.com", "TTL": 600, "Priority": 10, "Weight": 3 }),
+                ],
+            );
+            (200, json!({ "TotalCount": 503, "PageNumber": page, "DomainRecords": { "Record": records } }))
+        })
+        .await;
+
+        let records = client(&server).list_records("", "example.com").await.unwrap();
+
+        assert_eq!(server.count(|r| r.param("Action").as_deref() == Some("DescribeDomainRecords")), 2);
+        assert_eq!(records.len(), 503);
+        let srv = &records[500];
+        assert_eq!((srv.srv_priority, srv.srv_weight, srv.srv_port), (Some(10), Some(20), Some(5060)));
+        assert_eq!(srv.content, "sip.example.com");
+        let caa = &records[501];
+        assert_eq!((caa.caa_flags, caa.caa_tag.as_deref()), (Some(128), Some("issuewild")));
+        assert_eq!(caa.status, RecordStatus::Disabled);
+        assert_eq!(caa.comment.as_deref(), Some("证书"));
+        assert_eq!((records[502].mx_priority, records[502].weight), (Some(10), Some(3)));
+    }
+
+    #[tokio::test]
+    async fn counts_records_from_numeric_field() {
+        let server = MockServer::start(|_| {
+            (
+                200,
+                json!({ "TotalCount": 1, "Domains": { "Domain": [
+                    { "DomainId": "d1", "DomainName": "example.com", "RecordCount": 3, "UpdateTime": "2024-01-02T03:04Z" }
+                ] } }),
+            )
+        })
+        .await;
+
+        let domains = client(&server).list_domains().await.unwrap();
+
+        assert_eq!(domains[0].records_count, Some(3));
+    }
+
+    #[tokio::test]
+    async fn writes_use_rpc_actions() {
+        let server = MockServer::start(|req| match req.param("Action").as_deref() {
+            Some("DescribeSubDomainRecords") => (
+                200,
+                json!({ "TotalCount": 1, "DomainRecords": { "Record": [record(9001, "@", "CAA", "0 issue pki.goog")] } }),
+            ),
+            _ => (200, json!({ "RequestId": "req-1", "RecordId": "9001" })),
+        })
+        .await;
+        let client = client(&server);
+
+        let mut req = create_request("SRV", "_sip._tcp", "sip.example.com");
+        req.srv_priority = Some(10);
+        req.srv_weight = Some(20);
+        req.srv_port = Some(5060);
+        req.comment = Some("voip".to_string());
+        let created = client.create_record("", "example.com", &req).await.unwrap();
+        assert_eq!(created.id, "9001");
+
+        let mut update = update_request("9001", "CAA", "@", "letsencrypt.org");
+        update.caa_flags = Some(0);
+        update.caa_tag = Some("issue".to_string());
+        client.update_record("", "example.com", &update).await.unwrap();
+        client.set_record_status("", "9001", RecordStatus::Disabled).await.unwrap();
+        client.delete_record("", "9001").await.unwrap();
+
+        let requests = server.requests();
+        let actions: Vec<String> = requests.iter().map(|r| r.param("Action").unwrap_or_default()).collect();
+        assert_eq!(
+            actions,
+            [
+                "AddDomainRecord",
+                "UpdateDomainRecordRemark",
+                "DescribeSubDomainRecords",
+                "UpdateDomainRecord",
+                "SetDomainRecordStatus",
+                "DeleteDomainRecord"
+            ]
+        );
+        assert_eq!(requests[0].param("Value").as_deref(), Some("10 20 5060 sip.example.com"));
+        assert_eq!(requests[0].param("Priority").as_deref(), Some("10"));
+        assert_eq!(requests[2].param("SubDomain").as_deref(), Some("example.com"));
+        assert_eq!(requests[3].param("Value").as_deref(), Some("0 issue letsencrypt.org"));
+        assert_eq!(requests[4].param("Status").as_deref(), Some("Disable"));
+        assert!(requests.iter().all(|r| r.param("Signature").is_some()));
+    }
+
+    #[tokio::test]
+    async fn updates_only_send_what_changed() {
+        let server = MockServer::start(|req| match req.param("Action").as_deref() {
+            Some("DescribeSubDomainRecords") => (
+                200,
+                json!({ "TotalCount": 1, "DomainRecords": { "Record": [
+                    { "RecordId": "9001", "RR": "www", "Type": "A", "Value": "192.0.2.1", "TTL": 600, "Remark": "web", "Weight": 3 }
+                ] } }),
+            ),
+            _ => (200, json!({ "RequestId": "req-1", "RecordId": "9001" })),
+        })
+        .await;
+
+        let mut update = update_request("9001", "A", "www", "192.0.2.1");
+        update.comment = Some("web".to_string());
+        update.weight = Some(5);
+        let updated = client(&server).update_record("", "example.com", &update).await.unwrap();
+
+        let actions: Vec<String> = server.requests().iter().map(|r| r.param("Action").unwrap_or_default()).collect();
+        assert_eq!(actions, ["DescribeSubDomainRecords", "UpdateDNSSLBWeight"]);
+        assert_eq!((updated.weight, updated.comment.as_deref()), (Some(5), Some("web")));
+    }
+
+    #[tokio::test]
+    async fn failed_remark_after_create_is_a_partial_failure() {
+        let server = MockServer::start(|req| match req.param("Action").as_deref() {
+            Some("AddDomainRecord") => (200, json!({ "RequestId": "req-1", "RecordId": "9001" })),
+            _ => (400, json!({ "RequestId": "req-2", "Code": "InvalidRemark", "Message": "The remark is invalid." })),
+        })
+        .await;
+
+        let mut req = create_request("A", "www", "192.0.2.1");
+        req.comment = Some("ops".to_string());
+        let err = client(&server).create_record("", "example.com", &req).await.unwrap_err();
+
+        assert_eq!(err.code, "partial_failure");
+        assert!(err.message.contains("9001"));
+        assert_eq!(err.provider_code(), Some("InvalidRemark"));
+    }
+
+    #[tokio::test]
+    async fn unknown_access_key_is_an_auth_failure() {
+        let server = MockServer::start(|_| {
+            (
+                404,
+                json!({ "RequestId": "req-2", "Code": "InvalidAccessKeyId.NotFound", "Message": "Specified access key is not found." }),
+            )
+        })
+        .await;
+
+        let err = client(&server).list_domains().await.unwrap_err();
+
+        assert_eq!(err.code, "auth_failed");
+        assert_eq!(err.provider_code(), Some("InvalidAccessKeyId.NotFound"));
+        assert_eq!(err.request_id(), Some("req-2"));
+    }
+}
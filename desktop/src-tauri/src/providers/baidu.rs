@@ -1,51 +1,63 @@
 use crate::error::AppError;
-use crate::types::{DnsRecord, DomainItem, DomainStatus, Provider, RecordCreateRequest, RecordUpdateRequest};
+use crate::providers::http::{self, Call};
+use crate::txt;
+use crate::types::{
+    DnsRecord, DomainItem, DomainStatus, Provider, RecordCreateRequest, RecordExtra, RecordStatus,
+    RecordUpdateRequest,
+};
 use chrono::{DateTime, Utc};
 use hmac::{Hmac, Mac};
 use reqwest::header::{HeaderMap, HeaderValue};
 use serde::Deserialize;
 use sha2::Sha256;
 use std::collections::BTreeMap;
-use std::time::Duration;
 
-const API_BASE: &str = "https://dns.baidubce.com";
+pub const API_BASE: &str = "https://dns.baidubce.com";
 const SIGN_EXPIRES: u32 = 1800;
 
 pub struct BaiduClient {
     client: reqwest::Client,
+    base_url: String,
     access_key_id: String,
     secret_access_key: String,
+    security_token: Option<String>,
 }
 
 impl BaiduClient {
     pub fn new(access_key_id: String, secret_access_key: String) -> Result<Self, AppError> {
-        let client = reqwest::Client::builder()
-            .user_agent("LaoChenDNS/0.1.0")
-            .timeout(Duration::from_secs(30))
-            .build()
-            .map_err(AppError::from)?;
+        let client = http::client()?;
         Ok(Self {
             client,
+            base_url: API_BASE.to_string(),
             access_key_id,
             secret_access_key,
+            security_token: None,
         })
     }
 
+    pub fn with_endpoint(mut self, endpoint: Option<&str>) -> Result<Self, AppError> {
+        self.base_url = http::endpoint_or(endpoint, API_BASE)?;
+        Ok(self)
+    }
+
+    pub fn with_security_token(mut self, security_token: Option<String>) -> Self {
+        self.security_token = security_token.filter(|t| !t.trim().is_empty());
+        self
+    }
+
     pub async fn test(&self) -> Result<(), AppError> {
         let _ = self.list_domains().await?;
         Ok(())
     }
 
     pub async fn list_domains(&self) -> Result<Vec<DomainItem>, AppError> {
-        let url = format!("{API_BASE}/v1/zone");
+        let url = format!("{}/v1/zone", self.base_url);
         let headers = self.base_headers("GET", "/v1/zone", &BTreeMap::new())?;
-        let res = self
-            .client
-            .get(url)
-            .headers(headers)
-            .send()
-            .await
-            .map_err(AppError::from)?;
+        let res = http::send(Provider::Baidu, Call::Read, || {
+            Ok(self.client.get(&url).headers(headers.clone()))
+        })
+        .await?;
+        let res = ensure_success(res, "fetch_failed").await?;
         let parsed: BaiduZoneListResponse = res.json().await.map_err(AppError::from)?;
         let mut items = Vec::new();
         for zone in parsed.zones.unwrap_or_default() {
@@ -60,33 +72,49 @@ impl BaiduClient {
                     .as_deref()
                     .and_then(|s| parse_baidu_time(s).ok())
                     .map(|d| d.to_rfc3339()),
+                display_name: None,
             });
         }
         Ok(items)
     }
 
     pub async fn list_records(&self, zone_id: &str, domain_name: &str) -> Result<Vec<DnsRecord>, AppError> {
-        let path = format!("/v1/zone/{zone_id}/record");
-        let url = format!("{API_BASE}{path}");
-        let headers = self.base_headers("GET", &path, &BTreeMap::new())?;
-        let res = self
-            .client
-            .get(url)
-            .headers(headers)
-            .send()
-            .await
-            .map_err(AppError::from)?;
-        let parsed: BaiduRecordListResponse = res.json().await.map_err(AppError::from)?;
         let mut items = Vec::new();
-        for record in parsed.records.unwrap_or_default() {
-            items.push(record.to_dns_record(domain_name));
+        let mut marker: Option<String> = None;
+        loop {
+            let (records, next_marker) = self.records_page(zone_id, marker.as_deref()).await?;
+            items.extend(records.into_iter().map(|r| r.to_dns_record(domain_name)));
+            // 翻页靠 marker，服务商返回同一个 marker 时停下，避免死循环
+            match next_marker {
+                Some(next) if marker.as_deref() != Some(next.as_str()) => marker = Some(next),
+                _ => return Ok(items),
+            }
         }
-        Ok(items)
+    }
+
+    async fn records_page(&self, zone_id: &str, marker: Option<&str>) -> Result<(Vec<BaiduRecord>, Option<String>), AppError> {
+        let path = format!("/v1/zone/{zone_id}/record");
+        let url = format!("{}{path}", self.base_url);
+        let mut query = BTreeMap::new();
+        if let Some(marker) = marker {
+            query.insert("marker".to_string(), marker.to_string());
+        }
+        let headers = self.base_headers("GET", &path, &query)?;
+        let res = http::send(Provider::Baidu, Call::Read, || {
+            Ok(self.client.get(&url).headers(headers.clone()).query(&query))
+        })
+        .await?;
+        let res = ensure_success(res, "fetch_failed").await?;
+        let parsed: BaiduRecordListResponse = res.json().await.map_err(AppError::from)?;
+        let next_marker = parsed
+            .next_marker
+            .filter(|m| parsed.is_truncated && !m.is_empty());
+        Ok((parsed.records.unwrap_or_default(), next_marker))
     }
 
     pub async fn create_record(&self, zone_id: &str, domain_name: &str, req: &RecordCreateRequest) -> Result<DnsRecord, AppError> {
         let path = format!("/v1/zone/{zone_id}/record");
-        let url = format!("{API_BASE}{path}");
+        let url = format!("{}{path}", self.base_url);
         let value = baidu_value(
             &req.record_type,
             &req.content,
@@ -102,19 +130,17 @@ impl BaiduClient {
             value,
             ttl: req.ttl,
             priority: req.mx_priority,
+            description: req.comment.clone(),
         };
         let headers = self.base_headers("POST", &path, &BTreeMap::new())?;
-        let res = self
-            .client
-            .post(url)
-            .headers(headers)
-            .json(&payload)
-            .send()
-            .await
-            .map_err(AppError::from)?;
+        let res = http::send(Provider::Baidu, Call::Write, || {
+            Ok(self.client.post(&url).headers(headers.clone()).json(&payload))
+        })
+        .await?;
+        let res = ensure_success(res, "create_failed").await?;
         let parsed: BaiduRecord = res.json().await.map_err(AppError::from)?;
         let (content, srv_priority, srv_weight, srv_port, caa_flags, caa_tag) =
-            parse_baidu_record_value(&req.record_type, &req.content);
+            parse_baidu_record_value(&parsed.record_type, &parsed.value);
         Ok(DnsRecord {
             id: parsed.id,
             provider: Provider::Baidu,
@@ -129,12 +155,18 @@ impl BaiduClient {
             srv_port,
             caa_flags,
             caa_tag,
+            status: RecordStatus::Enabled,
+            comment: parsed.description.filter(|d| !d.is_empty()),
+            tags: Vec::new(),
+            weight: None,
+            display_name: None,
+            extra: RecordExtra::default(),
         })
     }
 
     pub async fn update_record(&self, zone_id: &str, domain_name: &str, req: &RecordUpdateRequest) -> Result<DnsRecord, AppError> {
         let path = format!("/v1/zone/{zone_id}/record/{}", req.id);
-        let url = format!("{API_BASE}{path}");
+        let url = format!("{}{path}", self.base_url);
         let value = baidu_value(
             &req.record_type,
             &req.content,
@@ -150,19 +182,17 @@ impl BaiduClient {
             value,
             ttl: req.ttl,
             priority: req.mx_priority,
+            description: req.comment.clone(),
         };
         let headers = self.base_headers("PUT", &path, &BTreeMap::new())?;
-        let res = self
-            .client
-            .put(url)
-            .headers(headers)
-            .json(&payload)
-            .send()
-            .await
-            .map_err(AppError::from)?;
+        let res = http::send(Provider::Baidu, Call::Write, || {
+            Ok(self.client.put(&url).headers(headers.clone()).json(&payload))
+        })
+        .await?;
+        let res = ensure_success(res, "update_failed").await?;
         let parsed: BaiduRecord = res.json().await.map_err(AppError::from)?;
         let (content, srv_priority, srv_weight, srv_port, caa_flags, caa_tag) =
-            parse_baidu_record_value(&req.record_type, &req.content);
+            parse_baidu_record_value(&parsed.record_type, &parsed.value);
         Ok(DnsRecord {
             id: parsed.id,
             provider: Provider::Baidu,
@@ -177,80 +207,124 @@ impl BaiduClient {
             srv_port,
             caa_flags,
             caa_tag,
+            status: RecordStatus::Enabled,
+            comment: parsed.description.filter(|d| !d.is_empty()),
+            tags: Vec::new(),
+            weight: None,
+            display_name: None,
+            extra: RecordExtra::default(),
         })
     }
 
     pub async fn delete_record(&self, zone_id: &str, record_id: &str) -> Result<(), AppError> {
         let path = format!("/v1/zone/{zone_id}/record/{record_id}");
-        let url = format!("{API_BASE}{path}");
+        let url = format!("{}{path}", self.base_url);
         let headers = self.base_headers("DELETE", &path, &BTreeMap::new())?;
-        let res = self
-            .client
-            .delete(url)
-            .headers(headers)
-            .send()
-            .await
-            .map_err(AppError::from)?;
-        if !res.status().is_success() {
-            let status = res.status();
-            let text = res.text().await.unwrap_or_default();
-            return Err(AppError::new(
-                "delete_failed",
-                format!(
-                    "HTTP {}: {} (response text: {})",
-                    status.as_u16(),
-                    status.canonical_reason().unwrap_or("Unknown"),
-                    text
-                ),
-            ));
-        }
+        let res = http::send(Provider::Baidu, Call::Write, || {
+            Ok(self.client.delete(&url).headers(headers.clone()))
+        })
+        .await?;
+        ensure_success(res, "delete_failed").await?;
         Ok(())
     }
 
     fn base_headers(&self, method: &str, path: &str, query: &BTreeMap<String, String>) -> Result<HeaderMap, AppError> {
-        let host = "dns.baidubce.com";
+        let host = http::authority(&self.base_url)?;
+        let path = format!("{}{path}", http::base_path(&self.base_url)?);
         let timestamp = Utc::now().format("%Y-%m-%dT%H:%M:%SZ").to_string();
         let mut headers = HeaderMap::new();
-        headers.insert("host", HeaderValue::from_static(host));
+        headers.insert(
+            "host",
+            HeaderValue::from_str(&host).map_err(|e| AppError::new("invalid_header", e.to_string()))?,
+        );
         headers.insert(
             "x-bce-date",
             HeaderValue::from_str(&timestamp).map_err(|e| AppError::new("invalid_header", e.to_string()))?,
         );
-        let authorization = self.sign(method, path, query, &headers, &timestamp)?;
+        if let Some(token) = &self.security_token {
+            headers.insert(
+                "x-bce-security-token",
+                HeaderValue::from_str(token).map_err(|e| AppError::new("invalid_header", e.to_string()))?,
+            );
+        }
+        let authorization = sign_request(
+            &self.access_key_id,
+            &self.secret_access_key,
+            method,
+            &path,
+            query,
+            &headers,
+            &timestamp,
+        );
         headers.insert(
             "Authorization",
             HeaderValue::from_str(&authorization).map_err(|e| AppError::new("invalid_header", e.to_string()))?,
         );
         Ok(headers)
     }
+}
 
-    fn sign(
-        &self,
-        method: &str,
-        path: &str,
-        query: &BTreeMap<String, String>,
-        headers: &HeaderMap,
-        timestamp: &str,
-    ) -> Result<String, AppError> {
-        let auth_string = format!("bce-auth-v1/{}/{}/{}", self.access_key_id, timestamp, SIGN_EXPIRES);
-        let signing_key = hmac_sha256(self.secret_access_key.as_bytes(), auth_string.as_bytes());
-        let canonical_uri = percent_encode(path);
-        let canonical_query = canonical_query_string(query);
-        let canonical_headers = canonical_headers(headers);
-        let signed_headers = "host;x-bce-date";
-        let string_to_sign = format!(
-            "{}\n{}\n{}\n{}",
-            method.to_uppercase(),
-            canonical_uri,
-            canonical_query,
-            canonical_headers
-        );
-        let signature = hmac_sha256_hex(&signing_key, string_to_sign.as_bytes());
-        Ok(format!(
-            "{}//{}/{}",
-            auth_string, signed_headers, signature
-        ))
+async fn ensure_success(res: reqwest::Response, code: &str) -> Result<reqwest::Response, AppError> {
+    let status = res.status();
+    if status.is_success() {
+        return Ok(res);
     }
+    let header_request_id = res
+        .headers()
+        .get("x-bce-request-id")
+        .and_then(|v| v.to_str().ok())
+        .map(|v| v.to_string());
+    let text = res.text().await.unwrap_or_default();
+    let parsed: BaiduErrorResponse = serde_json::from_str(&text).unwrap_or_default();
+    let message = parsed
+        .message
+        .unwrap_or_else(|| format!("HTTP {}: {}", status.as_u16(), text));
+    let code = if status.as_u16() == 401 || status.as_u16() == 403 {
+        "auth_failed"
+    } else {
+        code
+    };
+    Err(AppError::http(code, status.as_u16(), message)
+        .with_provider_code(parsed.code.unwrap_or_default())
+        .with_request_id(parsed.request_id.or(header_request_id)))
+}
+
+#[derive(Debug, Deserialize, Default)]
+struct BaiduErrorResponse {
+    code: Option<String>,
+    message: Option<String>,
+    #[serde(rename = "requestId")]
+    request_id: Option<String>,
+}
+
+/// 百度智能云 bce-auth-v1 签名，返回 Authorization 头的值。`headers` 里的头全部参与签名。
+fn sign_request(
+    access_key_id: &str,
+    secret_access_key: &str,
+    method: &str,
+    path: &str,
+    query: &BTreeMap<String, String>,
+    headers: &HeaderMap,
+    timestamp: &str,
+) -> String {
+    let auth_string = format!("bce-auth-v1/{access_key_id}/{timestamp}/{SIGN_EXPIRES}");
+    let signing_key = hmac_sha256_hex(secret_access_key.as_bytes(), auth_string.as_bytes());
+    // 路径里的 / 不编码
+    let canonical_uri = path.split('/').map(percent_encode).collect::<Vec<_>>().join("/");
+    let canonical_query = canonical_query_string(query);
+    let canonical_headers = canonical_headers(headers);
+    let mut signed_headers: Vec<String> = headers.keys().map(|k| k.as_str().to_lowercase()).collect();
+    signed_headers.sort();
+    let signed_headers = signed_headers.join(";");
+    let string_to_sign = format!(
+        "{}\n{}\n{}\n{}",
+        method.to_uppercase(),
+        canonical_uri,
+        canonical_query,
+        canonical_headers
+    );
+    let signature = hmac_sha256_hex(signing_key.as_bytes(), string_to_sign.as_bytes());
+    format!("{auth_string}/{signed_headers}/{signature}")
 }
 
 fn percent_encode(value: &str) -> String {
@@ -285,12 +359,6 @@ fn canonical_headers(headers: &HeaderMap) -> String {
         .join("\n")
 }
 
-fn hmac_sha256(key: &[u8], data: &[u8]) -> Vec<u8> {
-    let mut mac = Hmac::<Sha256>::new_from_slice(key).expect("hmac can take key of any size");
-    mac.update(data);
-    mac.finalize().into_bytes().to_vec()
-}
-
 fn hmac_sha256_hex(key: &[u8], data: &[u8]) -> String {
     let mut mac = Hmac::<Sha256>::new_from_slice(key).expect("hmac can take key of any size");
     mac.update(data);
@@ -318,6 +386,10 @@ struct BaiduZone {
 #[derive(Debug, Deserialize)]
 struct BaiduRecordListResponse {
     records: Option<Vec<BaiduRecord>>,
+    #[serde(rename = "isTruncated", default)]
+    is_truncated: bool,
+    #[serde(rename = "nextMarker")]
+    next_marker: Option<String>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -329,6 +401,7 @@ struct BaiduRecord {
     value: String,
     ttl: u32,
     priority: Option<u16>,
+    description: Option<String>,
 }
 
 impl BaiduRecord {
@@ -349,6 +422,12 @@ impl BaiduRecord {
             srv_port,
             caa_flags,
             caa_tag,
+            status: RecordStatus::Enabled,
+            comment: self.description.filter(|d| !d.is_empty()),
+            tags: Vec::new(),
+            weight: None,
+            display_name: None,
+            extra: RecordExtra::default(),
         }
     }
 }
@@ -361,6 +440,8 @@ struct BaiduRecordCreateRequest {
     value: String,
     ttl: u32,
     priority: Option<u16>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    description: Option<String>,
 }
 
 fn baidu_value(
@@ -418,6 +499,136 @@ fn parse_baidu_record_value(
                 (value.to_string(), None, None, None, None, None)
             }
         }
+        "TXT" => (txt::decode(value), None, None, None, None, None),
         _ => (value.to_string(), None, None, None, None, None),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::providers::mock::{create_request, update_request, MockServer};
+    use serde_json::json;
+
+    // 鉴权认证文档“生成认证字符串”一节的示例请求
+    #[test]
+    fn signs_request_like_documented_example() {
+        let mut headers = HeaderMap::new();
+        headers.insert("host", HeaderValue::from_static("bj.bcebos.com"));
+        headers.insert("content-type", HeaderValue::from_static("text/plain"));
+        headers.insert("content-length", HeaderValue::from_static("8"));
+        headers.insert("content-md5", HeaderValue::from_static("NFzcPqhviddjRNnSOGo4rw=="));
+        headers.insert("x-bce-date", HeaderValue::from_static("2015-04-27T08:23:49Z"));
+        let query: BTreeMap<String, String> = [("partNumber", "9"), ("uploadId", "a44cc9bab11cbd156984767aad637851")]
+            .into_iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect();
+
+        let authorization = sign_request(
+            "aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa",
+            "bbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbb",
+            "PUT",
+            "/v1/test/myfolder/readme.txt",
+            &query,
+            &headers,
+            "2015-04-27T08:23:49Z",
+        );
+
+        assert_eq!(
+            authorization,
+            "bce-auth-v1/aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa/2015-04-27T08:23:49Z/1800/\
+             content-length;content-md5;content-type;host;x-bce-date/\
+             d74a04362e6a848f5b39b15421cb449427f419c95a480fd6b8cf9fc783e2999e"
+        );
+    }
+
+    fn client(server: &MockServer) -> BaiduClient {
+        server.client(|endpoint| BaiduClient::new("testak".to_string(), "testsk".to_string())?.with_endpoint(endpoint))
+    }
+
+    #[tokio::test]
+    async fn follows_markers_across_pages() {
+        let server = MockServer::start(|req| match req.param("marker").as_deref() {
+            None => (
+                200,
+                json!({
+                    "isTruncated": true,
+                    "nextMarker": "r2",
+                    "records": [{ "id": "r1", "rr": "@", "type": "MX", "value": "mx.example.com", "ttl": 300, "priority": 10 }]
+                }),
+            ),
+            _ => (
+                200,
+                json!({
+                    "isTruncated": false,
+                    "records": [
+                        { "id": "r2", "rr": "_sip._tcp", "type": "SRV", "value": "10 20 5060 sip.example.com", "ttl": 300 },
+                        { "id": "r3", "rr": "@", "type": "CAA", "value": "0 issue letsencrypt.org", "ttl": 300, "description": "证书" }
+                    ]
+                }),
+            ),
+        })
+        .await;
+
+        let records = client(&server).list_records("example.com", "example.com").await.unwrap();
+
+        assert_eq!(server.count(|r| r.path == "/v1/zone/example.com/record"), 2);
+        assert_eq!(records.len(), 3);
+        assert_eq!(records[0].mx_priority, Some(10));
+        assert_eq!((records[1].srv_weight, records[1].content.as_str()), (Some(20), "sip.example.com"));
+        assert_eq!((records[2].caa_tag.as_deref(), records[2].comment.as_deref()), (Some("issue"), Some("证书")));
+    }
+
+    #[tokio::test]
+    async fn writes_map_values_back_from_response() {
+        let server = MockServer::start(|req| {
+            if req.method == "DELETE" {
+                return (200, json!({}));
+            }
+            let mut record = req.json();
+            record["id"] = json!("r9");
+            (200, record)
+        })
+        .await;
+        let client = client(&server);
+
+        let mut req = create_request("SRV", "_sip._tcp", "sip.example.com");
+        req.srv_priority = Some(10);
+        req.srv_weight = Some(20);
+        req.srv_port = Some(5060);
+        let created = client.create_record("example.com", "example.com", &req).await.unwrap();
+        assert_eq!((created.srv_priority, created.srv_port), (Some(10), Some(5060)));
+        assert_eq!(created.content, "sip.example.com");
+
+        let mut update = update_request("r9", "CAA", "@", "letsencrypt.org");
+        update.caa_flags = Some(0);
+        update.caa_tag = Some("issue".to_string());
+        let updated = client.update_record("example.com", "example.com", &update).await.unwrap();
+        assert_eq!((updated.caa_flags, updated.content.as_str()), (Some(0), "letsencrypt.org"));
+        client.delete_record("example.com", "r9").await.unwrap();
+
+        let requests = server.requests();
+        assert!(requests[0].is("POST", "/v1/zone/example.com/record"));
+        assert_eq!(requests[0].json()["value"], "10 20 5060 sip.example.com");
+        assert!(requests[1].is("PUT", "/v1/zone/example.com/record/r9"));
+        assert!(requests[2].is("DELETE", "/v1/zone/example.com/record/r9"));
+        assert!(requests[0].header("authorization").unwrap().starts_with("bce-auth-v1/testak/"));
+    }
+
+    #[tokio::test]
+    async fn rejected_signature_is_an_auth_failure() {
+        let server = MockServer::start(|_| {
+            (
+                403,
+                json!({ "code": "SignatureDoesNotMatch", "message": "The request signature we calculated does not match", "requestId": "req-4" }),
+            )
+        })
+        .await;
+
+        let err = client(&server).list_domains().await.unwrap_err();
+
+        assert_eq!(err.code, "auth_failed");
+        assert_eq!(err.provider_code(), Some("SignatureDoesNotMatch"));
+        assert_eq!(err.request_id(), Some("req-4"));
+    }
+}
@@ -1,32 +1,42 @@
 use crate::error::AppError;
-use crate::types::{DnsRecord, DomainItem, DomainStatus, Provider, RecordCreateRequest, RecordUpdateRequest};
+use crate::providers::http::{self, Call};
+use crate::svcb;
+use crate::txt;
+use crate::types::{
+    DnsRecord, DomainItem, DomainStatus, Provider, RecordCreateRequest, RecordExtra, RecordStatus,
+    RecordUpdateRequest, SvcParams,
+};
 use anyhow::Context;
 use chrono::{DateTime, Utc};
 use reqwest::header::{HeaderMap, HeaderValue};
+use serde::de::DeserializeOwned;
 use serde::Deserialize;
-use std::time::Duration;
 
-const API_BASE: &str = "https://api.cloudflare.com/client/v4";
+pub const API_BASE: &str = "https://api.cloudflare.com/client/v4";
+const RECORDS_PER_PAGE: u32 = 5000;
 
 pub struct CloudflareClient {
     client: reqwest::Client,
+    base_url: String,
     api_token: String,
 }
 
 impl CloudflareClient {
     pub fn new(api_token: String) -> Result<Self, AppError> {
-        let client = reqwest::Client::builder()
-            .user_agent("LaoChenDNS/0.1.0")
-            .timeout(Duration::from_secs(30))
-            .build()
-            .map_err(AppError::from)?;
+        let client = http::client()?;
 
         Ok(Self {
             client,
+            base_url: API_BASE.to_string(),
             api_token,
         })
     }
 
+    pub fn with_endpoint(mut self, endpoint: Option<&str>) -> Result<Self, AppError> {
+        self.base_url = http::endpoint_or(endpoint, API_BASE)?;
+        Ok(self)
+    }
+
     fn headers(&self) -> Result<HeaderMap, AppError> {
         let mut headers = HeaderMap::new();
         headers.insert(
@@ -43,35 +53,22 @@ impl CloudflareClient {
     }
 
     pub async fn test(&self) -> Result<(), AppError> {
-        let url = format!("{API_BASE}/zones?per_page=1");
-        let res = self
-            .client
-            .get(url)
-            .headers(self.headers()?)
-            .send()
-            .await
-            .map_err(AppError::from)?;
-        let parsed: CfResponse<Vec<CfZone>> = res.json().await.map_err(AppError::from)?;
-        if parsed.success {
-            Ok(())
-        } else {
-            Err(AppError::new("auth_failed", parsed.first_error_message()))
-        }
+        let url = format!("{}/zones?per_page=1", self.base_url);
+        let res = http::send(Provider::Cloudflare, Call::Read, || {
+            Ok(self.client.get(&url).headers(self.headers()?))
+        })
+        .await?;
+        let _: CfResponse<Vec<CfZone>> = parse_cf_response(res, "auth_failed").await?;
+        Ok(())
     }
 
     pub async fn list_domains(&self) -> Result<Vec<DomainItem>, AppError> {
-        let url = format!("{API_BASE}/zones?per_page=200");
-        let res = self
-            .client
-            .get(url)
-            .headers(self.headers()?)
-            .send()
-            .await
-            .map_err(AppError::from)?;
-        let parsed: CfResponse<Vec<CfZone>> = res.json().await.map_err(AppError::from)?;
-        if !parsed.success {
-            return Err(AppError::new("fetch_failed", parsed.first_error_message()));
-        }
+        let url = format!("{}/zones?per_page=200", self.base_url);
+        let res = http::send(Provider::Cloudflare, Call::Read, || {
+            Ok(self.client.get(&url).headers(self.headers()?))
+        })
+        .await?;
+        let parsed: CfResponse<Vec<CfZone>> = parse_cf_response(res, "fetch_failed").await?;
 
         let mut items = Vec::new();
         for zone in parsed.result {
@@ -86,84 +83,75 @@ impl CloudflareClient {
                     .modified_on
                     .and_then(|s| parse_rfc3339(&s).ok())
                     .map(|d| d.to_rfc3339()),
+                display_name: None,
             });
         }
         Ok(items)
     }
 
     async fn zone_records_count(&self, zone_id: &str) -> Result<Option<u32>, AppError> {
-        let url = format!("{API_BASE}/zones/{zone_id}/dns_records?per_page=1");
-        let res = self
-            .client
-            .get(url)
-            .headers(self.headers()?)
-            .send()
-            .await
-            .map_err(AppError::from)?;
-        let parsed: CfResponse<Vec<CfDnsRecord>> = res.json().await.map_err(AppError::from)?;
-        if !parsed.success {
-            return Err(AppError::new("fetch_failed", parsed.first_error_message()));
-        }
+        let url = format!("{}/zones/{zone_id}/dns_records?per_page=1", self.base_url);
+        let res = http::send(Provider::Cloudflare, Call::Read, || {
+            Ok(self.client.get(&url).headers(self.headers()?))
+        })
+        .await?;
+        let parsed: CfResponse<Vec<CfDnsRecord>> = parse_cf_response(res, "fetch_failed").await?;
         Ok(parsed.result_info.and_then(|i| i.total_count))
     }
 
     pub async fn list_records(&self, zone_id: &str, zone_name: &str) -> Result<Vec<DnsRecord>, AppError> {
-        let url = format!("{API_BASE}/zones/{zone_id}/dns_records?per_page=50000");
-        let res = self
-            .client
-            .get(url)
-            .headers(self.headers()?)
-            .send()
-            .await
-            .map_err(AppError::from)?;
-        let parsed: CfResponse<Vec<CfDnsRecord>> = res.json().await.map_err(AppError::from)?;
-        if !parsed.success {
-            return Err(AppError::new("fetch_failed", parsed.first_error_message()));
-        }
-
-        Ok(parsed
-            .result
+        let records = http::paginate(|page| self.records_page(zone_id, page)).await?;
+        Ok(records
             .into_iter()
-            .filter_map(|r| r.to_dns_record(zone_name).ok())
+            .map(|r| r.into_listed_record(zone_name))
             .collect())
     }
 
-    pub async fn find_conflict_ids(&self, zone_id: &str, zone_name: &str, record_type: &str, host: &str) -> Result<Vec<String>, AppError> {
+    async fn records_page(&self, zone_id: &str, page: u32) -> Result<(Vec<CfDnsRecord>, bool), AppError> {
+        let url = format!(
+            "{}/zones/{zone_id}/dns_records?per_page={RECORDS_PER_PAGE}&page={page}",
+            self.base_url
+        );
+        let res = http::send(Provider::Cloudflare, Call::Read, || {
+            Ok(self.client.get(&url).headers(self.headers()?))
+        })
+        .await?;
+        let parsed: CfResponse<Vec<CfDnsRecord>> = parse_cf_response(res, "fetch_failed").await?;
+        let more = match parsed.result_info.as_ref().and_then(|i| i.total_pages) {
+            Some(total_pages) => page < total_pages,
+            None => http::more_pages(page, RECORDS_PER_PAGE, parsed.result.len(), None),
+        };
+        Ok((parsed.result, more))
+    }
+
+    pub async fn find_conflicts(&self, zone_id: &str, zone_name: &str, record_type: &str, host: &str) -> Result<Vec<DnsRecord>, AppError> {
         let full_name = normalize_full_name(zone_name, host);
         let url = format!(
-            "{API_BASE}/zones/{zone_id}/dns_records?per_page=100&type={}&name={}",
+            "{}/zones/{zone_id}/dns_records?per_page=100&type={}&name={}",
+            self.base_url,
             urlencoding::encode(record_type),
             urlencoding::encode(&full_name)
         );
-        let res = self
-            .client
-            .get(url)
-            .headers(self.headers()?)
-            .send()
-            .await
-            .map_err(AppError::from)?;
-        let parsed: CfResponse<Vec<CfDnsRecord>> = res.json().await.map_err(AppError::from)?;
-        if !parsed.success {
-            return Err(AppError::new("fetch_failed", parsed.first_error_message()));
-        }
-        Ok(parsed.result.into_iter().map(|r| r.id).collect())
+        let res = http::send(Provider::Cloudflare, Call::Read, || {
+            Ok(self.client.get(&url).headers(self.headers()?))
+        })
+        .await?;
+        let parsed: CfResponse<Vec<CfDnsRecord>> = parse_cf_response(res, "fetch_failed").await?;
+        Ok(parsed
+            .result
+            .into_iter()
+            .map(|r| r.into_listed_record(zone_name))
+            .collect())
     }
 
     pub async fn create_record(&self, zone_id: &str, zone_name: &str, req: &RecordCreateRequest) -> Result<DnsRecord, AppError> {
-        let url = format!("{API_BASE}/zones/{zone_id}/dns_records");
+        let url = format!("{}/zones/{zone_id}/dns_records", self.base_url);
         let payload = build_cf_record_payload(zone_name, req)?;
-        let res = self
-            .client
-            .post(url)
-            .headers(self.headers()?)
-            .json(&payload)
-            .send()
-            .await
-            .map_err(AppError::from)?;
-        let parsed: CfResponse<CfDnsRecord> = res.json().await.map_err(AppError::from)?;
-        if !parsed.success {
-            return Err(AppError::new("create_failed", parsed.first_error_message()));
-        }
+        let res = http::send(Provider::Cloudflare, Call::Write, || {
+            Ok(self.client.post(&url).headers(self.headers()?).json(&payload))
+        })
+        .await?;
+        let parsed: CfResponse<CfDnsRecord> = parse_cf_response(res, "create_failed").await?;
         parsed
             .result
             .to_dns_record(zone_name)
@@ -172,20 +160,13 @@ impl CloudflareClient {
     }
 
     pub async fn update_record(&self, zone_id: &str, zone_name: &str, req: &RecordUpdateRequest) -> Result<DnsRecord, AppError> {
-        let url = format!("{API_BASE}/zones/{zone_id}/dns_records/{}", req.id);
+        let url = format!("{}/zones/{zone_id}/dns_records/{}", self.base_url, req.id);
         let payload = build_cf_record_payload_update(zone_name, req)?;
-        let res = self
-            .client
-            .put(url)
-            .headers(self.headers()?)
-            .json(&payload)
-            .send()
-            .await
-            .map_err(AppError::from)?;
-        let parsed: CfResponse<CfDnsRecord> = res.json().await.map_err(AppError::from)?;
-        if !parsed.success {
-            return Err(AppError::new("update_failed", parsed.first_error_message()));
-        }
+        let res = http::send(Provider::Cloudflare, Call::Write, || {
+            Ok(self.client.patch(&url).headers(self.headers()?).json(&payload))
+        })
+        .await?;
+        let parsed: CfResponse<CfDnsRecord> = parse_cf_response(res, "update_failed").await?;
         parsed
             .result
             .to_dns_record(zone_name)
@@ -193,19 +174,43 @@ impl CloudflareClient {
             .map_err(AppError::from)
     }
 
+    pub async fn replace_records(
+        &self,
+        zone_id: &str,
+        zone_name: &str,
+        req: &RecordUpdateRequest,
+        delete_ids: &[String],
+    ) -> Result<DnsRecord, AppError> {
+        let url = format!("{}/zones/{zone_id}/dns_records/batch", self.base_url);
+        let payload = CfBatchPayload {
+            deletes: delete_ids.iter().map(|id| CfBatchDelete { id: id.clone() }).collect(),
+            puts: vec![CfBatchPut {
+                id: req.id.clone(),
+                record: build_cf_record_payload_update(zone_name, req)?,
+            }],
+        };
+        let res = http::send(Provider::Cloudflare, Call::Write, || {
+            Ok(self.client.post(&url).headers(self.headers()?).json(&payload))
+        })
+        .await?;
+        let parsed: CfResponse<CfBatchResult> = parse_cf_response(res, "update_failed").await?;
+        parsed
+            .result
+            .puts
+            .into_iter()
+            .next()
+            .context("missing record")
+            .and_then(|r| r.to_dns_record(zone_name))
+            .map_err(AppError::from)
+    }
+
     pub async fn delete_record(&self, zone_id: &str, record_id: &str) -> Result<(), AppError> {
-        let url = format!("{API_BASE}/zones/{zone_id}/dns_records/{record_id}");
-        let res = self
-            .client
-            .delete(url)
-            .headers(self.headers()?)
-            .send()
-            .await
-            .map_err(AppError::from)?;
-        let parsed: CfResponse<serde_json::Value> = res.json().await.map_err(AppError::from)?;
-        if !parsed.success {
-            return Err(AppError::new("delete_failed", parsed.first_error_message()));
-        }
+        let url = format!("{}/zones/{zone_id}/dns_records/{record_id}", self.base_url);
+        let res = http::send(Provider::Cloudflare, Call::Write, || {
+            Ok(self.client.delete(&url).headers(self.headers()?))
+        })
+        .await?;
+        parse_cf_response::<serde_json::Value>(res, "delete_failed").await?;
         Ok(())
     }
 }
@@ -215,9 +220,11 @@ impl CloudflareClient {
 mod cloudflare_tests;
 
 fn normalize_full_name(zone_name: &str, host: &str) -> String {
-    if host == "@" || host == zone_name {
+    let lower = host.to_ascii_lowercase();
+    let zone = zone_name.to_ascii_lowercase();
+    if host == "@" || lower == zone {
         zone_name.to_string()
-    } else if host.ends_with(&format!(".{zone_name}")) || host == format!("{host}.{zone_name}") {
+    } else if lower.ends_with(&format!(".{zone}")) {
         host.to_string()
     } else {
         format!("{host}.{zone_name}")
@@ -230,30 +237,54 @@ fn parse_rfc3339(value: &str) -> Result<DateTime<Utc>, anyhow::Error> {
 
 #[derive(Debug, Deserialize)]
 struct CfResponse<T> {
-    success: bool,
     result: T,
-    errors: Option<Vec<CfError>>,
     result_info: Option<CfResultInfo>,
 }
 
-impl<T> CfResponse<T> {
-    fn first_error_message(&self) -> String {
-        self.errors
-            .as_ref()
-            .and_then(|e| e.first())
-            .map(|e| e.message.clone())
-            .unwrap_or_else(|| "Unknown error".to_string())
-    }
+// 失败时 result 为 null，先只解析 success 和 errors
+#[derive(Debug, Deserialize)]
+struct CfStatus {
+    success: bool,
+    errors: Option<Vec<CfError>>,
 }
 
 #[derive(Debug, Deserialize)]
 struct CfError {
+    #[serde(default)]
+    code: Option<i64>,
     message: String,
 }
 
+async fn parse_cf_response<T: DeserializeOwned>(res: reqwest::Response, code: &str) -> Result<T, AppError> {
+    let status = res.status().as_u16();
+    let ray_id = res
+        .headers()
+        .get("cf-ray")
+        .and_then(|v| v.to_str().ok())
+        .map(|v| v.to_string());
+    let text = res.text().await.map_err(AppError::from)?;
+    let decode_error = |e: serde_json::Error| {
+        AppError::http("json_decode_failed", status, format!("Failed to decode response: {e}"))
+            .with_request_id(ray_id.clone())
+    };
+    let parsed: CfStatus = serde_json::from_str(&text).map_err(decode_error)?;
+    if !parsed.success {
+        let first = parsed.errors.as_ref().and_then(|e| e.first());
+        let message = first.map(|e| e.message.clone()).unwrap_or_else(|| "Unknown error".to_string());
+        let code = if status == 401 || status == 403 { "auth_failed" } else { code };
+        let mut err = AppError::http(code, status, message).with_request_id(ray_id);
+        if let Some(provider_code) = first.and_then(|e| e.code) {
+            err = err.with_provider_code(provider_code.to_string());
+        }
+        return Err(err);
+    }
+    serde_json::from_str(&text).map_err(decode_error)
+}
+
 #[derive(Debug, Deserialize)]
 struct CfResultInfo {
     total_count: Option<u32>,
+    total_pages: Option<u32>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -273,10 +304,19 @@ struct CfDnsRecord {
     ttl: u32,
     priority: Option<u16>,
     data: Option<serde_json::Value>,
+    comment: Option<String>,
+    #[serde(default)]
+    tags: Vec<String>,
 }
 
 impl CfDnsRecord {
-    fn to_dns_record(self, zone_name: &str) -> Result<DnsRecord, anyhow::Error> {
+    // 列表和冲突检测里不能丢记录：data 解析不了时保留原始 content，结构化字段留空
+    fn into_listed_record(self, zone_name: &str) -> DnsRecord {
+        let raw = self.raw_record(zone_name);
+        self.to_dns_record(zone_name).unwrap_or(raw)
+    }
+
+    fn raw_record(&self, zone_name: &str) -> DnsRecord {
         let host = if self.name == zone_name {
             "@".to_string()
         } else if let Some(stripped) = self.name.strip_suffix(&format!(".{zone_name}")) {
@@ -285,8 +325,8 @@ impl CfDnsRecord {
             self.name.clone()
         };
 
-        let mut out = DnsRecord {
-            id: self.id,
+        DnsRecord {
+            id: self.id.clone(),
             provider: Provider::Cloudflare,
             domain: zone_name.to_string(),
             record_type: self.record_type.clone(),
@@ -299,17 +339,30 @@ impl CfDnsRecord {
             srv_port: None,
             caa_flags: None,
             caa_tag: None,
-        };
+            status: RecordStatus::Enabled,
+            comment: self.comment.clone().filter(|c| !c.is_empty()),
+            tags: self.tags.clone(),
+            weight: None,
+            display_name: None,
+            extra: RecordExtra::default(),
+        }
+    }
+
+    fn to_dns_record(self, zone_name: &str) -> Result<DnsRecord, anyhow::Error> {
+        let mut out = self.raw_record(zone_name);
 
         match self.record_type.as_str() {
             "MX" => {
                 out.mx_priority = self.priority;
             }
+            "TXT" => {
+                out.content = txt::decode(&out.content);
+            }
             "SRV" => {
                 if let Some(data) = self.data {
-                    out.srv_priority = data.get("priority").and_then(|v| v.as_u64()).map(|v| v as u16);
-                    out.srv_weight = data.get("weight").and_then(|v| v.as_u64()).map(|v| v as u16);
-                    out.srv_port = data.get("port").and_then(|v| v.as_u64()).map(|v| v as u16);
+                    out.srv_priority = data_int(&data, "priority")?;
+                    out.srv_weight = data_int(&data, "weight")?;
+                    out.srv_port = data_int(&data, "port")?;
                     if let Some(target) = data.get("target").and_then(|v| v.as_str()) {
                         out.content = target.to_string();
                     }
@@ -317,13 +370,63 @@ impl CfDnsRecord {
             }
             "CAA" => {
                 if let Some(data) = self.data {
-                    out.caa_flags = data.get("flags").and_then(|v| v.as_u64()).map(|v| v as u8);
+                    out.caa_flags = data_int(&data, "flags")?;
                     out.caa_tag = data.get("tag").and_then(|v| v.as_str()).map(|v| v.to_string());
                     if let Some(value) = data.get("value").and_then(|v| v.as_str()) {
                         out.content = value.to_string();
                     }
                 }
             }
+            "HTTPS" | "SVCB" => {
+                if let Some(data) = self.data {
+                    out.extra.svc_priority = data_int(&data, "priority")?;
+                    let params = data.get("value").and_then(|v| v.as_str()).unwrap_or("");
+                    // 解析不了的参数串原样保留，回写时不丢
+                    out.extra.svc_params = Some(svcb::parse_params(params).unwrap_or_else(|_| SvcParams {
+                        other: vec![params.to_string()],
+                        ..Default::default()
+                    }));
+                    if let Some(target) = data.get("target").and_then(|v| v.as_str()) {
+                        out.content = target.to_string();
+                    }
+                }
+            }
+            "SSHFP" | "TLSA" | "NAPTR" | "DS" => {
+                if let Some(data) = self.data {
+                    let str_of = |key: &str| data.get(key).and_then(|v| v.as_str()).map(|v| v.to_string());
+                    let extra = &mut out.extra;
+                    let value = match self.record_type.as_str() {
+                        "SSHFP" => {
+                            extra.sshfp_algorithm = data_int(&data, "algorithm")?;
+                            extra.sshfp_fp_type = data_int(&data, "type")?;
+                            str_of("fingerprint")
+                        }
+                        "TLSA" => {
+                            extra.tlsa_usage = data_int(&data, "usage")?;
+                            extra.tlsa_selector = data_int(&data, "selector")?;
+                            extra.tlsa_matching_type = data_int(&data, "matching_type")?;
+                            str_of("certificate")
+                        }
+                        "NAPTR" => {
+                            extra.naptr_order = data_int(&data, "order")?;
+                            extra.naptr_preference = data_int(&data, "preference")?;
+                            extra.naptr_flags = str_of("flags");
+                            extra.naptr_service = str_of("service");
+                            extra.naptr_regexp = str_of("regex");
+                            str_of("replacement")
+                        }
+                        _ => {
+                            extra.ds_key_tag = data_int(&data, "key_tag")?;
+                            extra.ds_algorithm = data_int(&data, "algorithm")?;
+                            extra.ds_digest_type = data_int(&data, "digest_type")?;
+                            str_of("digest")
+                        }
+                    };
+                    if let Some(value) = value {
+                        out.content = value;
+                    }
+                }
+            }
             _ => {}
         }
 
@@ -331,6 +434,14 @@ impl CfDnsRecord {
     }
 }
 
+// data 里的数值超出字段范围时报错，不截断
+fn data_int<T: TryFrom<u64>>(data: &serde_json::Value, key: &str) -> Result<Option<T>, anyhow::Error> {
+    data.get(key)
+        .and_then(|v| v.as_u64())
+        .map(|v| T::try_from(v).map_err(|_| anyhow::anyhow!("{key} out of range: {v}")))
+        .transpose()
+}
+
 #[derive(serde::Serialize)]
 struct CfRecordPayload {
     #[serde(rename = "type")]
@@ -340,6 +451,35 @@ struct CfRecordPayload {
     ttl: u32,
     priority: Option<u16>,
     data: Option<serde_json::Value>,
+    // 修改用 PATCH，不传的备注和标签保持原样
+    #[serde(skip_serializing_if = "Option::is_none")]
+    comment: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    tags: Option<Vec<String>>,
+}
+
+#[derive(serde::Serialize)]
+struct CfBatchPayload {
+    deletes: Vec<CfBatchDelete>,
+    puts: Vec<CfBatchPut>,
+}
+
+#[derive(serde::Serialize)]
+struct CfBatchDelete {
+    id: String,
+}
+
+#[derive(serde::Serialize)]
+struct CfBatchPut {
+    id: String,
+    #[serde(flatten)]
+    record: CfRecordPayload,
+}
+
+#[derive(Debug, Deserialize)]
+struct CfBatchResult {
+    #[serde(default)]
+    puts: Vec<CfDnsRecord>,
 }
 
 fn build_cf_record_payload(zone_name: &str, req: &RecordCreateRequest) -> Result<CfRecordPayload, AppError> {
@@ -355,6 +495,9 @@ fn build_cf_record_payload(zone_name: &str, req: &RecordCreateRequest) -> Result
         req.srv_port,
         req.caa_flags,
         req.caa_tag.as_deref(),
+        &req.extra,
+        req.comment.as_deref(),
+        req.tags.clone(),
     )
 }
 
@@ -371,6 +514,9 @@ fn build_cf_record_payload_update(zone_name: &str, req: &RecordUpdateRequest) ->
         req.srv_port,
         req.caa_flags,
         req.caa_tag.as_deref(),
+        &req.extra,
+        req.comment.as_deref(),
+        req.tags.clone(),
     )
 }
 
@@ -386,8 +532,12 @@ fn build_cf_payload_common(
     srv_port: Option<u16>,
     caa_flags: Option<u8>,
     caa_tag: Option<&str>,
+    extra: &RecordExtra,
+    comment: Option<&str>,
+    tags: Option<Vec<String>>,
 ) -> Result<CfRecordPayload, AppError> {
     let full_name = normalize_full_name(zone_name, host);
+    let comment = comment.map(|c| c.to_string());
     let payload = match record_type {
         "SRV" => {
             let (service, proto) = parse_srv_service_proto(host)?;
@@ -406,6 +556,8 @@ fn build_cf_payload_common(
                     "port": srv_port.unwrap_or(0),
                     "target": content,
                 })),
+                comment,
+                tags,
             }
         }
         "CAA" => CfRecordPayload {
@@ -419,6 +571,32 @@ fn build_cf_payload_common(
                 "tag": caa_tag.unwrap_or("issue"),
                 "value": content,
             })),
+            comment,
+            tags,
+        },
+        "HTTPS" | "SVCB" => CfRecordPayload {
+            record_type: record_type.to_string(),
+            name: full_name,
+            content: None,
+            ttl,
+            priority: None,
+            data: Some(serde_json::json!({
+                "priority": extra.svc_priority.unwrap_or(1),
+                "target": content,
+                "value": extra.svc_params.as_ref().map(svcb::format_params).unwrap_or_default(),
+            })),
+            comment,
+            tags,
+        },
+        "SSHFP" | "TLSA" | "NAPTR" | "DS" => CfRecordPayload {
+            record_type: record_type.to_string(),
+            name: full_name,
+            content: None,
+            ttl,
+            priority: None,
+            data: Some(cf_structured_data(record_type, content, extra)),
+            comment,
+            tags,
         },
         "MX" => CfRecordPayload {
             record_type: record_type.to_string(),
@@ -427,6 +605,19 @@ fn build_cf_payload_common(
             ttl,
             priority: mx_priority,
             data: None,
+            comment,
+            tags,
+        },
+        // 超过 255 字节的值需要分段，统一编码成带引号的形式
+        "TXT" => CfRecordPayload {
+            record_type: record_type.to_string(),
+            name: full_name,
+            content: Some(txt::encode(content)),
+            ttl,
+            priority: None,
+            data: None,
+            comment,
+            tags,
         },
         _ => CfRecordPayload {
             record_type: record_type.to_string(),
@@ -435,11 +626,44 @@ fn build_cf_payload_common(
             ttl,
             priority: None,
             data: None,
+            comment,
+            tags,
         },
     };
     Ok(payload)
 }
 
+// 记录值 content 对应 data 里的指纹、证书、替换域名或摘要字段
+fn cf_structured_data(record_type: &str, content: &str, extra: &RecordExtra) -> serde_json::Value {
+    match record_type {
+        "SSHFP" => serde_json::json!({
+            "algorithm": extra.sshfp_algorithm.unwrap_or(0),
+            "type": extra.sshfp_fp_type.unwrap_or(0),
+            "fingerprint": content,
+        }),
+        "TLSA" => serde_json::json!({
+            "usage": extra.tlsa_usage.unwrap_or(0),
+            "selector": extra.tlsa_selector.unwrap_or(0),
+            "matching_type": extra.tlsa_matching_type.unwrap_or(0),
+            "certificate": content,
+        }),
+        "NAPTR" => serde_json::json!({
+            "order": extra.naptr_order.unwrap_or(0),
+            "preference": extra.naptr_preference.unwrap_or(0),
+            "flags": extra.naptr_flags.as_deref().unwrap_or(""),
+            "service": extra.naptr_service.as_deref().unwrap_or(""),
+            "regex": extra.naptr_regexp.as_deref().unwrap_or(""),
+            "replacement": content,
+        }),
+        _ => serde_json::json!({
+            "key_tag": extra.ds_key_tag.unwrap_or(0),
+            "algorithm": extra.ds_algorithm.unwrap_or(0),
+            "digest_type": extra.ds_digest_type.unwrap_or(0),
+            "digest": content,
+        }),
+    }
+}
+
 fn parse_srv_service_proto(host: &str) -> Result<(String, String), AppError> {
     let parts: Vec<&str> = host.split('.').collect();
     if parts.len() >= 2 && parts[0].starts_with('_') && parts[1].starts_with('_') {
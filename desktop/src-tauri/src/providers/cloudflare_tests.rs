@@ -1,4 +1,6 @@
 use super::*;
+use crate::providers::mock::{create_request, update_request, MockServer};
+use serde_json::json;
 
 #[test]
 fn cloudflare_client_uses_bearer_token_header() {
@@ -10,3 +12,223 @@ fn cloudflare_client_uses_bearer_token_header() {
     assert!(headers.get("X-Auth-Email").is_none());
     assert!(headers.get("X-Auth-Key").is_none());
 }
+
+fn client(server: &MockServer) -> CloudflareClient {
+    server.client(|endpoint| CloudflareClient::new("cfut_test_token".to_string())?.with_endpoint(endpoint))
+}
+
+fn envelope(result: serde_json::Value, info: serde_json::Value) -> (u16, serde_json::Value) {
+    (200, json!({ "success": true, "errors": [], "result": result, "result_info": info }))
+}
+
+#[tokio::test]
+async fn lists_records_across_pages() {
+    let server = MockServer::start(|req| match req.param("page").as_deref() {
+        Some("1") => envelope(
+            json!([
+                { "id": "r1", "type": "A", "name": "example.com", "content": "192.0.2.1", "ttl": 1 },
+                { "id": "r2", "type": "MX", "name": "example.com", "content": "mx.example.com", "ttl": 300, "priority": 10 }
+            ]),
+            json!({ "page": 1, "total_pages": 2, "total_count": 4 }),
+        ),
+        _ => envelope(
+            json!([
+                {
+                    "id": "r3", "type": "SRV", "name": "_sip._tcp.example.com", "ttl": 300,
+                    "data": { "priority": 10, "weight": 20, "port": 5060, "target": "sip.example.com" }
+                },
+                {
+                    "id": "r4", "type": "CAA", "name": "example.com", "ttl": 300,
+                    "data": { "flags": 0, "tag": "issue", "value": "letsencrypt.org" }
+                }
+            ]),
+            json!({ "page": 2, "total_pages": 2, "total_count": 4 }),
+        ),
+    })
+    .await;
+
+    let records = client(&server).list_records("zone1", "example.com").await.unwrap();
+
+    assert_eq!(server.count(|r| r.path == "/zones/zone1/dns_records"), 2);
+    assert_eq!(records.len(), 4);
+    assert_eq!(records[0].name, "@");
+    assert_eq!(records[1].mx_priority, Some(10));
+    assert_eq!(records[2].name, "_sip._tcp");
+    assert_eq!(records[2].content, "sip.example.com");
+    assert_eq!((records[2].srv_priority, records[2].srv_weight, records[2].srv_port), (Some(10), Some(20), Some(5060)));
+    assert_eq!(records[3].content, "letsencrypt.org");
+    assert_eq!((records[3].caa_flags, records[3].caa_tag.as_deref()), (Some(0), Some("issue")));
+}
+
+#[tokio::test]
+async fn creates_updates_and_deletes_records() {
+    let server = MockServer::start(|req| {
+        if req.method == "DELETE" {
+            return envelope(json!({ "id": "r9" }), json!(null));
+        }
+        let mut record = req.json();
+        record["id"] = json!("r9");
+        envelope(record, json!(null))
+    })
+    .await;
+    let client = client(&server);
+
+    let mut req = create_request("SRV", "_sip._tcp", "sip.example.com");
+    req.srv_priority = Some(10);
+    req.srv_weight = Some(5);
+    req.srv_port = Some(5060);
+    req.comment = Some("voip".to_string());
+    req.tags = Some(vec!["owner:ops".to_string()]);
+    let created = client.create_record("zone1", "example.com", &req).await.unwrap();
+    assert_eq!(created.id, "r9");
+    assert_eq!(created.srv_port, Some(5060));
+    assert_eq!(created.tags, ["owner:ops"]);
+
+    let mut update = update_request("r9", "CAA", "@", "pki.goog");
+    update.caa_flags = Some(128);
+    update.caa_tag = Some("issuewild".to_string());
+    let updated = client.update_record("zone1", "example.com", &update).await.unwrap();
+    assert_eq!(updated.caa_tag.as_deref(), Some("issuewild"));
+    client.delete_record("zone1", "r9").await.unwrap();
+
+    let requests = server.requests();
+    let create = &requests[0];
+    assert!(create.is("POST", "/zones/zone1/dns_records"));
+    assert_eq!(create.header("authorization"), Some("Bearer cfut_test_token"));
+    let payload = create.json();
+    assert_eq!(payload["name"], "_sip._tcp.example.com");
+    assert_eq!(payload["data"]["service"], "_sip");
+    assert_eq!(payload["data"]["proto"], "_tcp");
+    assert_eq!(payload["data"]["port"], 5060);
+    assert_eq!(payload["comment"], "voip");
+    assert_eq!(payload["tags"], json!(["owner:ops"]));
+    assert!(requests[1].is("PATCH", "/zones/zone1/dns_records/r9"));
+    let patch = requests[1].json();
+    assert_eq!(patch["data"]["flags"], 128);
+    // 没有传备注和标签时不覆盖原有的值
+    assert!(patch.get("comment").is_none() && patch.get("tags").is_none());
+    assert!(requests[2].is("DELETE", "/zones/zone1/dns_records/r9"));
+}
+
+#[tokio::test]
+async fn structured_records_round_trip_through_data() {
+    let server = MockServer::start(|req| {
+        let mut record = req.json();
+        record["id"] = json!("r1");
+        envelope(record, json!(null))
+    })
+    .await;
+    let client = client(&server);
+
+    let mut req = create_request("TLSA", "_443._tcp", "0f0f");
+    req.extra.tlsa_usage = Some(3);
+    req.extra.tlsa_selector = Some(1);
+    req.extra.tlsa_matching_type = Some(1);
+    let created = client.create_record("zone1", "example.com", &req).await.unwrap();
+    assert_eq!(created.content, "0f0f");
+    assert_eq!(created.extra, req.extra);
+
+    let mut req = create_request("NAPTR", "@", ".");
+    req.extra.naptr_order = Some(100);
+    req.extra.naptr_preference = Some(10);
+    req.extra.naptr_flags = Some("U".to_string());
+    req.extra.naptr_service = Some("E2U+sip".to_string());
+    req.extra.naptr_regexp = Some("!^.*$!sip:info@example.com!".to_string());
+    let created = client.create_record("zone1", "example.com", &req).await.unwrap();
+    assert_eq!(created.extra, req.extra);
+
+    let mut req = create_request("HTTPS", "www", ".");
+    req.extra.svc_priority = Some(1);
+    req.extra.svc_params = Some(crate::svcb::parse_params("alpn=h3 mandatory=alpn ipv6hint=2001:db8::1").unwrap());
+    let created = client.create_record("zone1", "example.com", &req).await.unwrap();
+    assert_eq!(created.extra, req.extra);
+
+    let requests = server.requests();
+    assert_eq!(
+        requests[2].json()["data"],
+        json!({ "priority": 1, "target": ".", "value": "mandatory=alpn alpn=\"h3\" ipv6hint=2001:db8::1" })
+    );
+    let tlsa = requests[0].json();
+    assert!(tlsa["content"].is_null());
+    assert_eq!(tlsa["data"], json!({ "usage": 3, "selector": 1, "matching_type": 1, "certificate": "0f0f" }));
+    assert_eq!(requests[1].json()["data"]["regex"], "!^.*$!sip:info@example.com!");
+    assert_eq!(requests[1].json()["data"]["replacement"], ".");
+}
+
+#[tokio::test]
+async fn out_of_range_data_is_an_error() {
+    let server = MockServer::start(|req| {
+        let mut record = req.json();
+        record["id"] = json!("r1");
+        record["data"]["algorithm"] = json!(300);
+        envelope(record, json!(null))
+    })
+    .await;
+
+    let mut req = create_request("SSHFP", "host", &"ab".repeat(32));
+    req.extra.sshfp_algorithm = Some(4);
+    req.extra.sshfp_fp_type = Some(2);
+    let err = client(&server).create_record("zone1", "example.com", &req).await.unwrap_err();
+
+    assert!(err.message.contains("algorithm out of range"), "{}", err.message);
+}
+
+#[tokio::test]
+async fn out_of_range_data_is_still_listed() {
+    let server = MockServer::start(|_| {
+        envelope(
+            json!([{
+                "id": "r1", "type": "SRV", "name": "_sip._tcp.example.com", "ttl": 300,
+                "content": "10 70000 5060 sip.example.com",
+                "data": { "priority": 10, "weight": 70000, "port": 5060, "target": "sip.example.com" }
+            }]),
+            json!({ "page": 1, "total_pages": 1 }),
+        )
+    })
+    .await;
+    let client = client(&server);
+
+    let records = client.list_records("zone1", "example.com").await.unwrap();
+    let conflicts = client.find_conflicts("zone1", "example.com", "SRV", "_sip._tcp").await.unwrap();
+
+    assert_eq!(records.len(), 1);
+    assert_eq!(records[0].srv_weight, None);
+    assert_eq!(records[0].content, "10 70000 5060 sip.example.com");
+    assert_eq!(conflicts.len(), 1);
+}
+
+#[tokio::test]
+async fn txt_values_are_chunked_and_read_back_whole() {
+    let server = MockServer::start(|req| {
+        let mut record = req.json();
+        record["id"] = json!("r1");
+        envelope(record, json!(null))
+    })
+    .await;
+    let dkim = format!("v=DKIM1; k=rsa; p={}", "M".repeat(300));
+
+    let req = create_request("TXT", "s1._domainkey", &dkim);
+    let created = client(&server).create_record("zone1", "example.com", &req).await.unwrap();
+
+    let content = server.requests()[0].json()["content"].as_str().unwrap().to_string();
+    assert!(content.starts_with("\"v=DKIM1; k=rsa; p=MMM"));
+    assert_eq!(content.matches("\" \"").count(), 1);
+    assert_eq!(created.content, dkim);
+}
+
+#[tokio::test]
+async fn rejected_token_is_an_auth_failure() {
+    let server = MockServer::start(|_| {
+        (
+            403,
+            json!({ "success": false, "errors": [{ "code": 10000, "message": "Authentication error" }], "result": null }),
+        )
+    })
+    .await;
+
+    let err = client(&server).list_domains().await.unwrap_err();
+
+    assert_eq!(err.code, "auth_failed");
+    assert_eq!(err.provider_code(), Some("10000"));
+    assert!(err.is_auth_failure());
+}
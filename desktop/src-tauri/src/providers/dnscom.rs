@@ -1,35 +1,44 @@
 use crate::error::AppError;
-use crate::types::{DnsRecord, DomainItem, DomainStatus, Provider, RecordCreateRequest, RecordUpdateRequest};
+use crate::providers::http::{self, Call};
+use crate::txt;
+use crate::types::{
+    DnsRecord, DomainItem, DomainStatus, Provider, RecordCreateRequest, RecordExtra, RecordStatus,
+    RecordUpdateRequest,
+};
 use anyhow::Context;
 use chrono::Utc;
 use hmac::{Hmac, Mac};
+use serde::de::DeserializeOwned;
 use serde::Deserialize;
 use sha2::Sha256;
 use std::collections::BTreeMap;
-use std::time::Duration;
 
-const API_BASE: &str = "https://openapi.dns.com/api";
+pub const API_BASE: &str = "https://openapi.dns.com/api";
+const RECORDS_PER_PAGE: u32 = 500;
 
 pub struct DnscomClient {
     client: reqwest::Client,
+    base_url: String,
     api_key: String,
     api_secret: String,
 }
 
 impl DnscomClient {
     pub fn new(api_key: String, api_secret: String) -> Result<Self, AppError> {
-        let client = reqwest::Client::builder()
-            .user_agent("LaoChenDNS/0.1.0")
-            .timeout(Duration::from_secs(30))
-            .build()
-            .map_err(AppError::from)?;
+        let client = http::client()?;
         Ok(Self {
             client,
+            base_url: API_BASE.to_string(),
             api_key,
             api_secret,
         })
     }
 
+    pub fn with_endpoint(mut self, endpoint: Option<&str>) -> Result<Self, AppError> {
+        self.base_url = http::endpoint_or(endpoint, API_BASE)?;
+        Ok(self)
+    }
+
     pub async fn test(&self) -> Result<(), AppError> {
         let _ = self.list_domains().await?;
         Ok(())
@@ -41,16 +50,12 @@ impl DnscomClient {
         params.insert("page".to_string(), "1".to_string());
         params.insert("paginate".to_string(), "500".to_string());
         self.sign_params("GET", path, &mut params);
-        let url = format!("{API_BASE}{path}");
-        let res = self
-            .client
-            .get(url)
-            .query(&params)
-            .send()
-            .await
-            .map_err(AppError::from)?;
-        let parsed: DnscomResponse<DnscomDomainListData> = res.json().await.map_err(AppError::from)?;
-        parsed.ensure_ok()?;
+        let url = format!("{}{path}", self.base_url);
+        let res = http::send(Provider::Dnscom, Call::Read, || {
+            Ok(self.client.get(&url).query(&params))
+        })
+        .await?;
+        let parsed: DnscomResponse<DnscomDomainListData> = parse_response(res).await?;
         let mut items = Vec::new();
         if let Some(data) = parsed.data {
             for item in data.data.unwrap_or_default() {
@@ -61,39 +66,34 @@ impl DnscomClient {
                     status: DomainStatus::Ok,
                     records_count: item.record_count,
                     last_changed_at: item.updated_at.clone(),
+                    display_name: None,
                 });
             }
         }
         Ok(items)
     }
 
-    pub async fn list_records(&self, domain_id: &str, domain_name: &str) -> Result<Vec<DnsRecord>, AppError> {
+    pub async fn list_records(&self, _domain_id: &str, domain_name: &str) -> Result<Vec<DnsRecord>, AppError> {
+        let records = http::paginate(|page| self.records_page(domain_name, page)).await?;
+        Ok(records.into_iter().map(|r| r.to_dns_record(domain_name)).collect())
+    }
+
+    async fn records_page(&self, domain_name: &str, page: u32) -> Result<(Vec<DnscomRecord>, bool), AppError> {
         let path = "/record/lists/";
         let mut params = BTreeMap::new();
         params.insert("domain".to_string(), domain_name.to_string());
-        params.insert("page".to_string(), "1".to_string());
-        params.insert("paginate".to_string(), "500".to_string());
+        params.insert("page".to_string(), page.to_string());
+        params.insert("paginate".to_string(), RECORDS_PER_PAGE.to_string());
         self.sign_params("GET", path, &mut params);
-        let url = format!("{API_BASE}{path}");
-        let res = self
-            .client
-            .get(url)
-            .query(&params)
-            .send()
-            .await
-            .map_err(AppError::from)?;
-        let parsed: DnscomResponse<DnscomRecordListData> = res.json().await.map_err(AppError::from)?;
-        parsed.ensure_ok()?;
-        let mut items = Vec::new();
-        if let Some(data) = parsed.data {
-            for record in data.data.unwrap_or_default() {
-                items.push(record.to_dns_record(domain_name));
-            }
-        }
-        if items.is_empty() {
-            let _ = domain_id;
-        }
-        Ok(items)
+        let url = format!("{}{path}", self.base_url);
+        let res = http::send(Provider::Dnscom, Call::Read, || {
+            Ok(self.client.get(&url).query(&params))
+        })
+        .await?;
+        let parsed: DnscomResponse<DnscomRecordListData> = parse_response(res).await?;
+        let records = parsed.data.and_then(|d| d.data).unwrap_or_default();
+        let more = http::more_pages(page, RECORDS_PER_PAGE, records.len(), None);
+        Ok((records, more))
     }
 
     pub async fn create_record(&self, _domain_id: &str, domain_name: &str, req: &RecordCreateRequest) -> Result<DnsRecord, AppError> {
@@ -120,16 +120,12 @@ impl DnscomClient {
         }
         params.insert("view_id".to_string(), "1".to_string());
         self.sign_params("POST", path, &mut params);
-        let url = format!("{API_BASE}{path}");
-        let res = self
-            .client
-            .post(url)
-            .form(&params)
-            .send()
-            .await
-            .map_err(AppError::from)?;
-        let parsed: DnscomResponse<DnscomRecord> = res.json().await.map_err(AppError::from)?;
-        parsed.ensure_ok()?;
+        let url = format!("{}{path}", self.base_url);
+        let res = http::send(Provider::Dnscom, Call::Write, || {
+            Ok(self.client.post(&url).form(&params))
+        })
+        .await?;
+        let parsed: DnscomResponse<DnscomRecord> = parse_response(res).await?;
         let record = parsed.data.context("record missing").map_err(AppError::from)?;
         Ok(record.to_dns_record(domain_name))
     }
@@ -159,16 +155,12 @@ impl DnscomClient {
         }
         params.insert("view_id".to_string(), "1".to_string());
         self.sign_params("POST", path, &mut params);
-        let url = format!("{API_BASE}{path}");
-        let res = self
-            .client
-            .post(url)
-            .form(&params)
-            .send()
-            .await
-            .map_err(AppError::from)?;
-        let parsed: DnscomResponse<DnscomRecord> = res.json().await.map_err(AppError::from)?;
-        parsed.ensure_ok()?;
+        let url = format!("{}{path}", self.base_url);
+        let res = http::send(Provider::Dnscom, Call::Write, || {
+            Ok(self.client.post(&url).form(&params))
+        })
+        .await?;
+        let parsed: DnscomResponse<DnscomRecord> = parse_response(res).await?;
         let record = parsed.data.context("record missing").map_err(AppError::from)?;
         Ok(record.to_dns_record(domain_name))
     }
@@ -179,34 +171,66 @@ impl DnscomClient {
         params.insert("domain".to_string(), domain_name.to_string());
         params.insert("record_id".to_string(), record_id.to_string());
         self.sign_params("POST", path, &mut params);
-        let url = format!("{API_BASE}{path}");
-        let res = self
-            .client
-            .post(url)
-            .form(&params)
-            .send()
-            .await
-            .map_err(AppError::from)?;
-        let parsed: DnscomResponse<serde_json::Value> = res.json().await.map_err(AppError::from)?;
-        parsed.ensure_ok()?;
+        let url = format!("{}{path}", self.base_url);
+        let res = http::send(Provider::Dnscom, Call::Write, || {
+            Ok(self.client.post(&url).form(&params))
+        })
+        .await?;
+        let _: DnscomResponse<serde_json::Value> = parse_response(res).await?;
+        Ok(())
+    }
+
+    pub async fn set_record_status(
+        &self,
+        _domain_id: &str,
+        domain_name: &str,
+        record_id: &str,
+        status: RecordStatus,
+    ) -> Result<(), AppError> {
+        let path = match status {
+            RecordStatus::Enabled => "/record/start/",
+            RecordStatus::Disabled => "/record/pause/",
+        };
+        let mut params = BTreeMap::new();
+        params.insert("domain".to_string(), domain_name.to_string());
+        params.insert("record_id".to_string(), record_id.to_string());
+        self.sign_params("POST", path, &mut params);
+        let url = format!("{}{path}", self.base_url);
+        let res = http::send(Provider::Dnscom, Call::Write, || {
+            Ok(self.client.post(&url).form(&params))
+        })
+        .await?;
+        let _: DnscomResponse<serde_json::Value> = parse_response(res).await?;
         Ok(())
     }
 
     fn sign_params(&self, method: &str, path: &str, params: &mut BTreeMap<String, String>) {
-        params.insert("api_key".to_string(), self.api_key.clone());
-        let timestamp = Utc::now().timestamp().to_string();
-        params.insert("timestamp".to_string(), timestamp.clone());
-        let canonical = params
-            .iter()
-            .map(|(k, v)| format!("{}={}", k, v))
-            .collect::<Vec<String>>()
-            .join("&");
-        let string_to_sign = format!("{}\n{}\n{}", method.to_uppercase(), path, canonical);
-        let signature = hmac_sha256_hex(self.api_secret.as_bytes(), string_to_sign.as_bytes());
-        params.insert("signature".to_string(), signature);
+        sign_request(&self.api_key, &self.api_secret, method, path, params, Utc::now().timestamp());
     }
 }
 
+/// 补上 api_key、timestamp 后对全部参数签名，结果写回 signature 参数。
+fn sign_request(
+    api_key: &str,
+    api_secret: &str,
+    method: &str,
+    path: &str,
+    params: &mut BTreeMap<String, String>,
+    timestamp: i64,
+) {
+    params.insert("api_key".to_string(), api_key.to_string());
+    params.insert("timestamp".to_string(), timestamp.to_string());
+    params.remove("signature");
+    let canonical = params
+        .iter()
+        .map(|(k, v)| format!("{}={}", k, v))
+        .collect::<Vec<String>>()
+        .join("&");
+    let string_to_sign = format!("{}\n{}\n{}", method.to_uppercase(), path, canonical);
+    let signature = hmac_sha256_hex(api_secret.as_bytes(), string_to_sign.as_bytes());
+    params.insert("signature".to_string(), signature);
+}
+
 fn hmac_sha256_hex(key: &[u8], data: &[u8]) -> String {
     let mut mac = Hmac::<Sha256>::new_from_slice(key).expect("hmac can take key of any size");
     mac.update(data);
@@ -268,6 +292,7 @@ fn parse_dnscom_record_value(
                 (value.to_string(), None, None, None, None, None)
             }
         }
+        "TXT" => (txt::decode(value), None, None, None, None, None),
         _ => (value.to_string(), None, None, None, None, None),
     }
 }
@@ -281,15 +306,38 @@ struct DnscomResponse<T> {
 
 impl<T> DnscomResponse<T> {
     fn ensure_ok(&self) -> Result<(), AppError> {
-        if self.code == 0 {
-            Ok(())
+        // 1 api_key 无效，2 签名错误，3 时间戳过期，6 无权操作该域名；4 调用过于频繁；5 记录已存在
+        let code = match self.code {
+            0 => return Ok(()),
+            1 | 2 | 3 | 6 => "auth_failed",
+            4 => "rate_limited",
+            5 => "conflict",
+            _ => "provider_error",
+        };
+        Err(AppError::new(
+            code,
+            self.message.clone().unwrap_or_else(|| "DNS.COM API failed".to_string()),
+        )
+        .with_provider_code(self.code.to_string())
+        .with_retryable(self.code == 4))
+    }
+}
+
+// 先看 HTTP 状态，网关返回的 5xx 页面不是 JSON，按可重试的 HTTP 错误报出
+async fn parse_response<T: DeserializeOwned>(res: reqwest::Response) -> Result<DnscomResponse<T>, AppError> {
+    let status = res.status();
+    if !status.is_success() {
+        let text = res.text().await.unwrap_or_default();
+        let code = if status.as_u16() == 401 || status.as_u16() == 403 {
+            "auth_failed"
         } else {
-            Err(AppError::new(
-                "auth_failed",
-                self.message.clone().unwrap_or_else(|| "DNS.COM API failed".to_string()),
-            ))
-        }
+            "http_error"
+        };
+        return Err(AppError::http(code, status.as_u16(), format!("HTTP {}: {}", status.as_u16(), text)));
     }
+    let parsed: DnscomResponse<T> = res.json().await.map_err(AppError::from)?;
+    parsed.ensure_ok()?;
+    Ok(parsed)
 }
 
 #[derive(Debug, Deserialize)]
@@ -319,6 +367,8 @@ struct DnscomRecord {
     value: String,
     ttl: u32,
     mx: Option<u16>,
+    #[serde(default)]
+    status: Option<serde_json::Value>,
 }
 
 impl DnscomRecord {
@@ -339,6 +389,154 @@ impl DnscomRecord {
             srv_port,
             caa_flags,
             caa_tag,
+            status: dnscom_status(self.status.as_ref()),
+            comment: None,
+            tags: Vec::new(),
+            weight: None,
+            display_name: None,
+            extra: RecordExtra::default(),
         }
     }
 }
+
+fn dnscom_status(value: Option<&serde_json::Value>) -> RecordStatus {
+    let paused = match value {
+        Some(serde_json::Value::Number(n)) => n.as_i64() == Some(0),
+        Some(serde_json::Value::String(s)) => s == "0" || s.eq_ignore_ascii_case("pause"),
+        Some(serde_json::Value::Bool(b)) => !b,
+        _ => false,
+    };
+    if paused {
+        RecordStatus::Disabled
+    } else {
+        RecordStatus::Enabled
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::providers::mock::{self, create_request, update_request, MockServer};
+    use serde_json::json;
+
+    // 只检查待签名串的拼法：方法大写、路径、按键排序的参数（含 api_key 和 timestamp，不含 signature）
+    #[test]
+    fn signs_sorted_params_with_fixed_timestamp() {
+        let mut params = BTreeMap::new();
+        params.insert("record_id".to_string(), "123".to_string());
+        params.insert("domain".to_string(), "example.com".to_string());
+        params.insert("signature".to_string(), "stale".to_string());
+
+        sign_request("KEYEXAMPLE", "SECRETEXAMPLE", "post", "/record/delete/", &mut params, 1700000000);
+
+        let string_to_sign = "POST\n/record/delete/\napi_key=KEYEXAMPLE&domain=example.com&record_id=123&timestamp=1700000000";
+        assert_eq!(params["api_key"], "KEYEXAMPLE");
+        assert_eq!(params["timestamp"], "1700000000");
+        assert_eq!(params["signature"], hmac_sha256_hex(b"SECRETEXAMPLE", string_to_sign.as_bytes()));
+    }
+
+    // RFC 4231 第 2 组测试向量
+    #[test]
+    fn hmac_matches_rfc_4231() {
+        assert_eq!(
+            hmac_sha256_hex(b"Jefe", b"what do ya want for nothing?"),
+            "5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843"
+        );
+    }
+
+    fn client(server: &MockServer) -> DnscomClient {
+        server.client(|endpoint| DnscomClient::new("testkey".to_string(), "testsecret".to_string())?.with_endpoint(endpoint))
+    }
+
+    fn record(id: u32, name: &str, record_type: &str, value: &str) -> serde_json::Value {
+        json!({ "id": id, "record": name, "type": record_type, "value": value, "ttl": 600, "status": 1 })
+    }
+
+    #[tokio::test]
+    async fn lists_records_until_short_page() {
+        let server = MockServer::start(|req| {
+            let records = mock::two_pages(
+                req.param("page").as_deref() == Some("1"),
+                RECORDS_PER_PAGE,
+                |i| record(i, "www", "A", "192.0.2.1"),
+                vec![
+                    record(9001, "_sip._tcp", "SRV", "10 20 5060 sip.example.com"),
+                    json!({ "id": 9002, "record": "@", "type": "MX", "value": "mx.example.com", "ttl": 600, "mx": 10, "status": "pause" }),
+                ],
+            );
+            (200, json!({ "code": 0, "message": "success", "data": { "data": records } }))
+        })
+        .await;
+
+        let records = client(&server).list_records("", "example.com").await.unwrap();
+
+        assert_eq!(server.count(|r| r.path == "/record/lists/"), 2);
+        assert_eq!(records.len(), 502);
+        assert_eq!((records[500].srv_priority, records[500].srv_port), (Some(10), Some(5060)));
+        assert_eq!(records[501].mx_priority, Some(10));
+        assert_eq!(records[501].status, RecordStatus::Disabled);
+    }
+
+    #[tokio::test]
+    async fn writes_are_signed_form_posts() {
+        let server = MockServer::start(|req| match req.path.as_str() {
+            "/record/create/" | "/record/update/" => (
+                200,
+                json!({ "code": 0, "data": record(9001, &req.param("record").unwrap_or_default(), &req.param("type").unwrap_or_default(), &req.param("value").unwrap_or_default()) }),
+            ),
+            _ => (200, json!({ "code": 0, "data": {} })),
+        })
+        .await;
+        let client = client(&server);
+
+        let mut req = create_request("CAA", "@", "letsencrypt.org");
+        req.caa_flags = Some(0);
+        req.caa_tag = Some("issue".to_string());
+        let created = client.create_record("", "example.com", &req).await.unwrap();
+        assert_eq!((created.caa_tag.as_deref(), created.content.as_str()), (Some("issue"), "letsencrypt.org"));
+
+        let mut update = update_request("9001", "MX", "@", "mx.example.com");
+        update.mx_priority = Some(5);
+        client.update_record("", "example.com", &update).await.unwrap();
+        client.set_record_status("", "example.com", "9001", RecordStatus::Disabled).await.unwrap();
+        client.delete_record("", "example.com", "9001").await.unwrap();
+
+        let requests = server.requests();
+        let paths: Vec<&str> = requests.iter().map(|r| r.path.as_str()).collect();
+        assert_eq!(paths, ["/record/create/", "/record/update/", "/record/pause/", "/record/delete/"]);
+        assert_eq!(requests[0].param("value").as_deref(), Some("0 issue letsencrypt.org"));
+        assert_eq!(requests[1].param("mx").as_deref(), Some("5"));
+        assert!(requests.iter().all(|r| r.param("api_key").as_deref() == Some("testkey") && r.param("signature").is_some()));
+    }
+
+    #[tokio::test]
+    async fn rejected_key_is_an_auth_failure() {
+        let server = MockServer::start(|_| (200, json!({ "code": 1, "message": "api_key 无效" }))).await;
+
+        let err = client(&server).list_domains().await.unwrap_err();
+
+        assert_eq!(err.code, "auth_failed");
+        assert_eq!(err.provider_code(), Some("1"));
+    }
+
+    #[tokio::test]
+    async fn error_codes_are_mapped_individually() {
+        let server = MockServer::start(|req| match req.path.as_str() {
+            "/record/create/" => (200, json!({ "code": 5, "message": "记录已存在" })),
+            "/record/delete/" => (200, json!({ "code": 4, "message": "请求过于频繁" })),
+            "/record/pause/" => (200, json!({ "code": 99, "message": "系统错误" })),
+            _ => (502, json!({ "message": "bad gateway" })),
+        })
+        .await;
+        let client = client(&server);
+
+        let err = client.create_record("", "example.com", &create_request("A", "www", "192.0.2.1")).await.unwrap_err();
+        assert_eq!((err.code.as_str(), err.retryable), ("conflict", false));
+        let err = client.delete_record("", "example.com", "9001").await.unwrap_err();
+        assert_eq!((err.code.as_str(), err.retryable), ("rate_limited", true));
+        let err = client.set_record_status("", "example.com", "9001", RecordStatus::Disabled).await.unwrap_err();
+        assert_eq!((err.code.as_str(), err.provider_code()), ("provider_error", Some("99")));
+        let err = client.list_domains().await.unwrap_err();
+        assert_eq!((err.http_status(), err.retryable), (Some(502), true));
+    }
+}
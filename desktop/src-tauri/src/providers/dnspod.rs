@@ -1,35 +1,58 @@
 use crate::error::AppError;
-use crate::types::{DnsRecord, DomainItem, DomainStatus, Provider, RecordCreateRequest, RecordUpdateRequest};
+use crate::providers::http::{self, Call};
+use crate::providers::tencentcloud::TencentCloudClient;
+use crate::svcb;
+use crate::txt;
+use crate::types::{
+    DnsRecord, DomainItem, DomainStatus, Provider, RecordCreateRequest, RecordExtra, RecordStatus,
+    RecordUpdateRequest,
+};
 use anyhow::Context;
 use chrono::{DateTime, Utc};
 use reqwest::header::{HeaderMap, HeaderValue};
 use serde::de::DeserializeOwned;
 use serde::Deserialize;
 use std::collections::HashMap;
-use std::time::Duration;
 
-const API_BASE: &str = "https://dnsapi.cn";
+pub const API_BASE: &str = "https://dnsapi.cn";
+const RECORDS_PER_PAGE: u32 = 3000;
 
 pub struct DnspodClient {
     client: reqwest::Client,
+    base_url: String,
     token_id: String,
     token: String,
+    // DNSPod 已并入腾讯云，配置了 API 3.0 密钥时所有请求都走腾讯云接口
+    api3: Option<TencentCloudClient>,
 }
 
 impl DnspodClient {
     pub fn new(token_id: String, token: String) -> Result<Self, AppError> {
-        let client = reqwest::Client::builder()
-            .user_agent("LaoChenDNS/0.1.0")
-            .timeout(Duration::from_secs(30))
-            .build()
-            .map_err(AppError::from)?;
+        let client = http::client()?;
         Ok(Self {
             client,
+            base_url: API_BASE.to_string(),
             token_id,
             token,
+            api3: None,
         })
     }
 
+    pub fn with_api3(secret_id: String, secret_key: String) -> Result<Self, AppError> {
+        let mut client = Self::new(String::new(), String::new())?;
+        client.api3 = Some(TencentCloudClient::new(secret_id, secret_key)?);
+        Ok(client)
+    }
+
+    // 接口地址覆盖当前使用的那套接口：旧版 dnsapi.cn 或腾讯云 API 3.0
+    pub fn with_endpoint(mut self, endpoint: Option<&str>) -> Result<Self, AppError> {
+        match self.api3.take() {
+            Some(api3) => self.api3 = Some(api3.with_endpoint(endpoint)?),
+            None => self.base_url = http::endpoint_or(endpoint, API_BASE)?,
+        }
+        Ok(self)
+    }
+
     fn headers(&self) -> Result<HeaderMap, AppError> {
         let mut headers = HeaderMap::new();
         headers.insert(
@@ -49,11 +72,21 @@ impl DnspodClient {
     }
 
     pub async fn test(&self) -> Result<(), AppError> {
+        if let Some(api3) = &self.api3 {
+            return api3.test().await;
+        }
         let _ = self.domain_list().await?;
         Ok(())
     }
 
     pub async fn list_domains(&self) -> Result<Vec<DomainItem>, AppError> {
+        if let Some(api3) = &self.api3 {
+            let mut items = api3.list_domains().await?;
+            for item in &mut items {
+                item.provider = Provider::Dnspod;
+            }
+            return Ok(items);
+        }
         let domains = self.domain_list().await?;
         let mut items = Vec::new();
         for d in domains {
@@ -64,136 +97,190 @@ impl DnspodClient {
                 status: DomainStatus::Ok,
                 records_count: d.records.parse::<u32>().ok(),
                 last_changed_at: parse_ymd_hms(&d.updated_on).ok().map(|d| d.to_rfc3339()),
+                display_name: None,
             });
         }
         Ok(items)
     }
 
     pub async fn list_records(&self, domain_id: &str, domain_name: &str) -> Result<Vec<DnsRecord>, AppError> {
-        let url = format!("{API_BASE}/Record.List");
-        let mut params = common_params(&self.login_token());
-        params.insert("domain_id".to_string(), domain_id.to_string());
-        let res = self
-            .client
-            .post(url)
-            .headers(self.headers()?)
-            .form(&params)
-            .send()
-            .await
-            .map_err(AppError::from)?;
-        let parsed: DnspodRecordListResponse = parse_response(res).await?;
-        ensure_ok(&parsed.status)?;
-
-        Ok(parsed
-            .records
-            .unwrap_or_default()
+        if let Some(api3) = &self.api3 {
+            let records = api3.list_records(domain_id, domain_name).await?;
+            return Ok(records.into_iter().map(as_dnspod).collect());
+        }
+        let records = http::paginate(|page| self.records_page(domain_id, page)).await?;
+        Ok(records
             .into_iter()
             .map(|r| r.to_dns_record(domain_name, None, None, None, None))
             .collect())
     }
 
+    async fn records_page(&self, domain_id: &str, page: u32) -> Result<(Vec<DnspodRecord>, bool), AppError> {
+        let mut params = common_params(&self.login_token());
+        params.insert("domain_id".to_string(), domain_id.to_string());
+        params.insert("offset".to_string(), ((page - 1) * RECORDS_PER_PAGE).to_string());
+        params.insert("length".to_string(), RECORDS_PER_PAGE.to_string());
+        let parsed: DnspodRecordListResponse = self.post("Record.List", Call::Read, &params).await?;
+        if parsed.status.code == "10" {
+            return Ok((Vec::new(), false));
+        }
+        let records = parsed.records.unwrap_or_default();
+        let total = parsed
+            .info
+            .and_then(|i| i.record_total)
+            .and_then(|t| t.parse::<u64>().ok());
+        let more = http::more_pages(page, RECORDS_PER_PAGE, records.len(), total);
+        Ok((records, more))
+    }
+
     pub async fn create_record(&self, domain_id: &str, domain_name: &str, req: &RecordCreateRequest) -> Result<DnsRecord, AppError> {
-        let url = format!("{API_BASE}/Record.Create");
+        if let Some(api3) = &self.api3 {
+            return api3.create_record(domain_id, domain_name, req).await.map(as_dnspod);
+        }
         let mut params = common_params(&self.login_token());
         params.insert("domain_id".to_string(), domain_id.to_string());
         params.insert("sub_domain".to_string(), req.name.clone());
         params.insert("record_type".to_string(), req.record_type.clone());
         params.insert("record_line".to_string(), "默认".to_string());
-        let value = dnspod_value(&req.record_type, &req.content, req.srv_priority, req.srv_weight, req.srv_port, req.caa_flags, req.caa_tag.as_deref());
+        let value = dnspod_value(&req.record_type, &req.content, req.srv_priority, req.srv_weight, req.srv_port, req.caa_flags, req.caa_tag.as_deref(), &req.extra);
         params.insert("value".to_string(), value.clone());
         params.insert("ttl".to_string(), req.ttl.to_string());
+        if let Some(weight) = req.weight {
+            params.insert("weight".to_string(), weight.to_string());
+        }
         if req.record_type == "MX" {
             if let Some(mx) = req.mx_priority {
                 params.insert("mx".to_string(), mx.to_string());
             }
         }
 
-        let res = self
-            .client
-            .post(url)
-            .headers(self.headers()?)
-            .form(&params)
-            .send()
-            .await
-            .map_err(AppError::from)?;
-        let parsed: DnspodRecordCreateResponse = parse_response(res).await?;
-        ensure_ok(&parsed.status)?;
+        let parsed: DnspodRecordCreateResponse = self.post("Record.Create", Call::Write, &params).await?;
 
         let record = parsed
             .record
             .context("missing record")
             .map_err(AppError::from)?;
-        Ok(record.to_dns_record(domain_name, Some(&req.record_type), Some(value), Some(req.ttl), req.mx_priority))
+        let mut out = record.to_dns_record(domain_name, Some(&req.record_type), Some(value), Some(req.ttl), req.mx_priority);
+        // 旧版 Record.Create 不接受备注，只能创建后再设置
+        if let Some(comment) = req.comment.as_deref().filter(|c| !c.is_empty()) {
+            self.set_remark(domain_id, &out.id, comment)
+                .await
+                .map_err(|e| e.partially_created(&out.id, "remark"))?;
+            out.comment = Some(comment.to_string());
+        }
+        Ok(out)
     }
 
     pub async fn update_record(&self, domain_id: &str, domain_name: &str, req: &RecordUpdateRequest) -> Result<DnsRecord, AppError> {
-        let url = format!("{API_BASE}/Record.Modify");
+        if let Some(api3) = &self.api3 {
+            return api3.update_record(domain_id, domain_name, req).await.map(as_dnspod);
+        }
         let mut params = common_params(&self.login_token());
         params.insert("domain_id".to_string(), domain_id.to_string());
         params.insert("record_id".to_string(), req.id.clone());
         params.insert("sub_domain".to_string(), req.name.clone());
         params.insert("record_type".to_string(), req.record_type.clone());
         params.insert("record_line".to_string(), "默认".to_string());
-        let value = dnspod_value(&req.record_type, &req.content, req.srv_priority, req.srv_weight, req.srv_port, req.caa_flags, req.caa_tag.as_deref());
+        let value = dnspod_value(&req.record_type, &req.content, req.srv_priority, req.srv_weight, req.srv_port, req.caa_flags, req.caa_tag.as_deref(), &req.extra);
         params.insert("value".to_string(), value.clone());
         params.insert("ttl".to_string(), req.ttl.to_string());
+        if let Some(weight) = req.weight {
+            params.insert("weight".to_string(), weight.to_string());
+        }
         if req.record_type == "MX" {
             if let Some(mx) = req.mx_priority {
                 params.insert("mx".to_string(), mx.to_string());
             }
         }
 
-        let res = self
-            .client
-            .post(url)
-            .headers(self.headers()?)
-            .form(&params)
-            .send()
-            .await
-            .map_err(AppError::from)?;
-        let parsed: DnspodRecordModifyResponse = parse_response(res).await?;
-        ensure_ok(&parsed.status)?;
+        let parsed: DnspodRecordModifyResponse = self.post("Record.Modify", Call::Write, &params).await?;
 
         let record = parsed
             .record
             .context("missing record")
             .map_err(AppError::from)?;
-        Ok(record.to_dns_record(domain_name, Some(&req.record_type), Some(value), Some(req.ttl), req.mx_priority))
+        let mut out = record.to_dns_record(domain_name, Some(&req.record_type), Some(value), Some(req.ttl), req.mx_priority);
+        if let Some(comment) = req.comment.as_deref() {
+            self.set_remark(domain_id, &out.id, comment)
+                .await
+                .map_err(|e| e.partially_updated(&out.id, "remark"))?;
+            out.comment = Some(comment.to_string()).filter(|c| !c.is_empty());
+        }
+        Ok(out)
     }
 
-    pub async fn delete_record(&self, domain_id: &str, record_id: &str) -> Result<(), AppError> {
-        let url = format!("{API_BASE}/Record.Remove");
+    pub async fn delete_record(&self, domain_id: &str, domain_name: &str, record_id: &str) -> Result<(), AppError> {
+        if let Some(api3) = &self.api3 {
+            return api3.delete_record(domain_id, domain_name, record_id).await;
+        }
         let mut params = common_params(&self.login_token());
         params.insert("domain_id".to_string(), domain_id.to_string());
         params.insert("record_id".to_string(), record_id.to_string());
-        let res = self
-            .client
-            .post(url)
-            .headers(self.headers()?)
-            .form(&params)
-            .send()
-            .await
-            .map_err(AppError::from)?;
-        let parsed: DnspodStatusResponse = parse_response(res).await?;
-        ensure_ok(&parsed.status)?;
+        let _: DnspodStatusResponse = self.post("Record.Remove", Call::Write, &params).await?;
+        Ok(())
+    }
+
+    pub async fn set_record_status(
+        &self,
+        domain_id: &str,
+        domain_name: &str,
+        record_id: &str,
+        status: RecordStatus,
+    ) -> Result<(), AppError> {
+        if let Some(api3) = &self.api3 {
+            return api3.set_record_status(domain_id, domain_name, record_id, status).await;
+        }
+        let mut params = common_params(&self.login_token());
+        params.insert("domain_id".to_string(), domain_id.to_string());
+        params.insert("record_id".to_string(), record_id.to_string());
+        let status = match status {
+            RecordStatus::Enabled => "enable",
+            RecordStatus::Disabled => "disable",
+        };
+        params.insert("status".to_string(), status.to_string());
+        let _: DnspodStatusResponse = self.post("Record.Status", Call::Write, &params).await?;
+        Ok(())
+    }
+
+    async fn set_remark(&self, domain_id: &str, record_id: &str, remark: &str) -> Result<(), AppError> {
+        let mut params = common_params(&self.login_token());
+        params.insert("domain_id".to_string(), domain_id.to_string());
+        params.insert("record_id".to_string(), record_id.to_string());
+        params.insert("remark".to_string(), remark.to_string());
+        let _: DnspodStatusResponse = self.post("Record.Remark", Call::Write, &params).await?;
         Ok(())
     }
 
     async fn domain_list(&self) -> Result<Vec<DnspodDomain>, AppError> {
-        let url = format!("{API_BASE}/Domain.List");
         let params = common_params(&self.login_token());
-        let res = self
-            .client
-            .post(url)
-            .headers(self.headers()?)
-            .form(&params)
-            .send()
-            .await
-            .map_err(AppError::from)?;
-        let parsed: DnspodDomainListResponse = parse_response(res).await?;
-        ensure_ok(&parsed.status)?;
+        let parsed: DnspodDomainListResponse = self.post("Domain.List", Call::Read, &params).await?;
         Ok(parsed.domains.unwrap_or_default())
     }
+
+    // 接口调用超限（-2）写在响应体里，整个请求交给 http::retry 重试
+    async fn post<T: DeserializeOwned + DnspodReply>(
+        &self,
+        action: &str,
+        call: Call,
+        params: &HashMap<String, String>,
+    ) -> Result<T, AppError> {
+        let url = format!("{}/{action}", self.base_url);
+        http::retry(call, || async {
+            let res = http::send(Provider::Dnspod, call, || {
+                Ok(self.client.post(&url).headers(self.headers()?).form(params))
+            })
+            .await?;
+            let parsed: T = parse_response(res).await?;
+            parsed.check()?;
+            Ok(parsed)
+        })
+        .await
+    }
+}
+
+fn as_dnspod(mut record: DnsRecord) -> DnsRecord {
+    record.provider = Provider::Dnspod;
+    record
 }
 
 fn common_params(login_token: &str) -> HashMap<String, String> {
@@ -204,19 +291,24 @@ fn common_params(login_token: &str) -> HashMap<String, String> {
 }
 
 fn ensure_ok(status: &DnspodStatus) -> Result<(), AppError> {
-    if status.code == "1" {
-        Ok(())
-    } else {
-        Err(AppError::new("auth_failed", status.message.clone()))
-    }
+    // -1 登录失败，-7 无权使用接口，-8 登录失败次数过多，83 账户被锁定，85 异地登录被拒绝；-2 为接口调用超限
+    let code = match status.code.as_str() {
+        "1" => return Ok(()),
+        "-1" | "-7" | "-8" | "83" | "85" => "auth_failed",
+        _ => "fetch_failed",
+    };
+    Err(AppError::new(code, status.message.clone())
+        .with_provider_code(status.code.clone())
+        .with_retryable(status.code == "-2"))
 }
 
 async fn parse_response<T: DeserializeOwned>(res: reqwest::Response) -> Result<T, AppError> {
     let status = res.status();
     let text = res.text().await.map_err(AppError::from)?;
     if !status.is_success() {
-        return Err(AppError::new(
+        return Err(AppError::http(
             "http_error",
+            status.as_u16(),
             format!(
                 "HTTP {}: {} (response text: {})",
                 status.as_u16(),
@@ -238,6 +330,7 @@ fn parse_ymd_hms(value: &str) -> Result<DateTime<Utc>, anyhow::Error> {
     Ok(DateTime::<Utc>::from_naive_utc_and_offset(dt, Utc))
 }
 
+#[allow(clippy::too_many_arguments)]
 fn dnspod_value(
     record_type: &str,
     content: &str,
@@ -246,8 +339,10 @@ fn dnspod_value(
     srv_port: Option<u16>,
     caa_flags: Option<u8>,
     caa_tag: Option<&str>,
+    extra: &RecordExtra,
 ) -> String {
     match record_type {
+        "HTTPS" | "SVCB" => svcb::presentation(content, extra),
         "SRV" => format!(
             "{} {} {} {}",
             srv_priority.unwrap_or(0),
@@ -279,9 +374,15 @@ struct DnspodDomainListResponse {
 #[derive(Debug, Deserialize)]
 struct DnspodRecordListResponse {
     status: DnspodStatus,
+    info: Option<DnspodRecordListInfo>,
     records: Option<Vec<DnspodRecord>>,
 }
 
+#[derive(Debug, Deserialize)]
+struct DnspodRecordListInfo {
+    record_total: Option<String>,
+}
+
 #[derive(Debug, Deserialize)]
 struct DnspodRecordCreateResponse {
     status: DnspodStatus,
@@ -294,6 +395,52 @@ struct DnspodRecordModifyResponse {
     record: Option<DnspodRecord>,
 }
 
+trait DnspodReply {
+    fn status(&self) -> &DnspodStatus;
+
+    fn check(&self) -> Result<(), AppError> {
+        ensure_ok(self.status())
+    }
+}
+
+impl DnspodReply for DnspodStatusResponse {
+    fn status(&self) -> &DnspodStatus {
+        &self.status
+    }
+}
+
+impl DnspodReply for DnspodDomainListResponse {
+    fn status(&self) -> &DnspodStatus {
+        &self.status
+    }
+}
+
+impl DnspodReply for DnspodRecordListResponse {
+    fn status(&self) -> &DnspodStatus {
+        &self.status
+    }
+
+    // 10 表示这一页没有记录，不算错误
+    fn check(&self) -> Result<(), AppError> {
+        match self.status.code.as_str() {
+            "10" => Ok(()),
+            _ => ensure_ok(&self.status),
+        }
+    }
+}
+
+impl DnspodReply for DnspodRecordCreateResponse {
+    fn status(&self) -> &DnspodStatus {
+        &self.status
+    }
+}
+
+impl DnspodReply for DnspodRecordModifyResponse {
+    fn status(&self) -> &DnspodStatus {
+        &self.status
+    }
+}
+
 #[derive(Debug, Deserialize)]
 struct DnspodStatus {
     code: String,
@@ -317,6 +464,10 @@ struct DnspodRecord {
     value: Option<String>,
     ttl: Option<String>,
     mx: Option<String>,
+    enabled: Option<String>,
+    remark: Option<String>,
+    #[serde(default)]
+    weight: Option<serde_json::Value>,
 }
 
 impl DnspodRecord {
@@ -345,6 +496,8 @@ impl DnspodRecord {
             } else {
                 (value.clone(), None, None, None)
             }
+        } else if record_type == "TXT" {
+            (txt::decode(&value), None, None, None)
         } else {
             (value.clone(), None, None, None)
         };
@@ -364,7 +517,7 @@ impl DnspodRecord {
             (None, None, content.clone())
         };
 
-        DnsRecord {
+        let record = DnsRecord {
             id: self.id,
             provider: Provider::Dnspod,
             domain: domain_name.to_string(),
@@ -382,6 +535,144 @@ impl DnspodRecord {
             srv_port,
             caa_flags,
             caa_tag,
+            status: match self.enabled.as_deref() {
+                Some("0") => RecordStatus::Disabled,
+                _ => RecordStatus::Enabled,
+            },
+            comment: self.remark.filter(|r| !r.is_empty()),
+            tags: Vec::new(),
+            weight: self.weight.as_ref().and_then(|v| match v {
+                serde_json::Value::Number(n) => n.as_u64().and_then(|n| u16::try_from(n).ok()),
+                serde_json::Value::String(s) => s.parse::<u16>().ok(),
+                _ => None,
+            }),
+            display_name: None,
+            extra: RecordExtra::default(),
+        };
+        svcb::split_record(record)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::providers::mock::{create_request, update_request, MockServer};
+    use serde_json::json;
+
+    fn client(server: &MockServer) -> DnspodClient {
+        server.client(|endpoint| DnspodClient::new("13490".to_string(), "6b5976c68aba5b14a0558b77c17c3932".to_string())?.with_endpoint(endpoint))
+    }
+
+    fn ok(extra: serde_json::Value) -> (u16, serde_json::Value) {
+        let mut body = json!({ "status": { "code": "1", "message": "Action completed successful" } });
+        if let (Some(body), Some(extra)) = (body.as_object_mut(), extra.as_object()) {
+            body.extend(extra.clone());
         }
+        (200, body)
+    }
+
+    #[tokio::test]
+    async fn lists_records_across_pages() {
+        let server = MockServer::start(|req| match req.param("offset").as_deref() {
+            Some("0") => {
+                let records: Vec<_> = (0..RECORDS_PER_PAGE)
+                    .map(|i| json!({ "id": i.to_string(), "name": "www", "type": "A", "value": "192.0.2.1", "ttl": "600", "enabled": "1" }))
+                    .collect();
+                ok(json!({ "info": { "record_total": "3002" }, "records": records }))
+            }
+            Some("3000") => ok(json!({
+                "info": { "record_total": "3002" },
+                "records": [
+                    { "id": "9001", "name": "_sip._tcp", "type": "SRV", "value": "10 20 5060 sip.example.com.", "ttl": "600", "enabled": "0" },
+                    { "id": "9002", "name": "@", "type": "CAA", "value": "0 issue \"letsencrypt.org\"", "ttl": "600", "remark": "证书" }
+                ]
+            })),
+            _ => (200, json!({ "status": { "code": "10", "message": "No records" } })),
+        })
+        .await;
+
+        let records = client(&server).list_records("42", "example.com").await.unwrap();
+
+        assert_eq!(server.count(|r| r.is("POST", "/Record.List")), 2);
+        assert_eq!(records.len(), 3002);
+        let srv = &records[3000];
+        assert_eq!(srv.content, "sip.example.com.");
+        assert_eq!((srv.srv_priority, srv.srv_weight, srv.srv_port), (Some(10), Some(20), Some(5060)));
+        assert_eq!(srv.status, RecordStatus::Disabled);
+        let caa = &records[3001];
+        assert_eq!((caa.caa_flags, caa.caa_tag.as_deref()), (Some(0), Some("issue")));
+        assert_eq!(caa.content, "\"letsencrypt.org\"");
+        assert_eq!(caa.comment.as_deref(), Some("证书"));
+    }
+
+    #[tokio::test]
+    async fn empty_record_list_is_not_an_error() {
+        let server = MockServer::start(|_| (200, json!({ "status": { "code": "10", "message": "No records" } }))).await;
+
+        let records = client(&server).list_records("42", "example.com").await.unwrap();
+
+        assert!(records.is_empty());
+    }
+
+    #[tokio::test]
+    async fn writes_send_encoded_values_and_remarks() {
+        let server = MockServer::start(|req| match req.path.as_str() {
+            "/Record.Create" | "/Record.Modify" => ok(json!({ "record": { "id": "77", "name": req.param("sub_domain"), "status": "enable" } })),
+            _ => ok(json!({})),
+        })
+        .await;
+        let client = client(&server);
+
+        let mut req = create_request("SRV", "_sip._tcp", "sip.example.com");
+        req.srv_priority = Some(10);
+        req.srv_weight = Some(20);
+        req.srv_port = Some(5060);
+        req.comment = Some("voip".to_string());
+        let created = client.create_record("42", "example.com", &req).await.unwrap();
+        assert_eq!(created.id, "77");
+        assert_eq!(created.srv_port, Some(5060));
+        assert_eq!(created.content, "sip.example.com");
+
+        let mut update = update_request("77", "MX", "@", "mx.example.com");
+        update.mx_priority = Some(5);
+        let updated = client.update_record("42", "example.com", &update).await.unwrap();
+        assert_eq!(updated.mx_priority, Some(5));
+        client.set_record_status("42", "example.com", "77", RecordStatus::Disabled).await.unwrap();
+        client.delete_record("42", "example.com", "77").await.unwrap();
+
+        let requests = server.requests();
+        let paths: Vec<&str> = requests.iter().map(|r| r.path.as_str()).collect();
+        assert_eq!(paths, ["/Record.Create", "/Record.Remark", "/Record.Modify", "/Record.Status", "/Record.Remove"]);
+        assert_eq!(requests[0].param("value").as_deref(), Some("10 20 5060 sip.example.com"));
+        assert_eq!(requests[0].param("login_token").as_deref(), Some("13490,6b5976c68aba5b14a0558b77c17c3932"));
+        assert_eq!(requests[1].param("remark").as_deref(), Some("voip"));
+        assert_eq!(requests[2].param("mx").as_deref(), Some("5"));
+        assert_eq!(requests[3].param("status").as_deref(), Some("disable"));
+    }
+
+    #[tokio::test]
+    async fn rate_limited_writes_are_retried() {
+        let calls = std::sync::atomic::AtomicUsize::new(0);
+        let server = MockServer::start(move |_| {
+            match calls.fetch_add(1, std::sync::atomic::Ordering::SeqCst) {
+                0 => (200, json!({ "status": { "code": "-2", "message": "API usage is limited" } })),
+                _ => ok(json!({})),
+            }
+        })
+        .await;
+
+        client(&server).delete_record("42", "example.com", "77").await.unwrap();
+
+        assert_eq!(server.count(|r| r.is("POST", "/Record.Remove")), 2);
+    }
+
+    #[tokio::test]
+    async fn login_failure_is_an_auth_failure() {
+        let server = MockServer::start(|_| (200, json!({ "status": { "code": "-1", "message": "Login failed" } }))).await;
+
+        let err = client(&server).list_domains().await.unwrap_err();
+
+        assert_eq!(err.code, "auth_failed");
+        assert_eq!(err.provider_code(), Some("-1"));
     }
 }
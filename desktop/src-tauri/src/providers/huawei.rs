@@ -1,34 +1,186 @@
 use crate::error::AppError;
-use crate::types::{DnsRecord, DomainItem, DomainStatus, Provider, RecordCreateRequest, RecordUpdateRequest};
+use crate::providers::http::{self, Call};
+use crate::svcb;
+use crate::txt;
+use crate::types::{
+    DnsRecord, DomainItem, DomainStatus, Provider, RecordCreateRequest, RecordExtra, RecordStatus,
+    RecordUpdateRequest, RecordWeight,
+};
 use chrono::{DateTime, Utc};
+use hmac::{Hmac, Mac};
 use reqwest::header::{HeaderMap, HeaderValue};
+use reqwest::Method;
 use serde::Deserialize;
-use std::time::Duration;
+use sha2::{Digest, Sha256};
+use std::sync::Mutex;
 
-const API_BASE: &str = "https://dns.cn-north-4.myhuaweicloud.com";
+pub const DEFAULT_REGION: &str = "cn-north-4";
+const SIGN_ALGORITHM: &str = "SDK-HMAC-SHA256";
+const RECORDSETS_PER_PAGE: u32 = 500;
+// 权重属于 v2.1 的线路记录集接口，v2 接口不处理 weight
+const RECORDSET_API: &str = "/v2.1";
+
+type HmacSha256 = Hmac<Sha256>;
+
+pub enum HuaweiAuth {
+    Token(String),
+    AkSk {
+        access_key_id: String,
+        secret_access_key: String,
+        project_id: Option<String>,
+    },
+}
 
 pub struct HuaweiClient {
     client: reqwest::Client,
-    token: String,
+    auth: HuaweiAuth,
+    region: String,
+    base_url: String,
+    project_id: Mutex<Option<String>>,
 }
 
 impl HuaweiClient {
-    pub fn new(token: String) -> Result<Self, AppError> {
-        let client = reqwest::Client::builder()
-            .user_agent("LaoChenDNS/0.1.0")
-            .timeout(Duration::from_secs(30))
-            .build()
-            .map_err(AppError::from)?;
-        Ok(Self { client, token })
+    pub fn new(auth: HuaweiAuth, region: Option<String>) -> Result<Self, AppError> {
+        let client = http::client()?;
+        let region = region
+            .map(|r| r.trim().to_string())
+            .filter(|r| !r.is_empty())
+            .unwrap_or_else(|| DEFAULT_REGION.to_string());
+        let project_id = match &auth {
+            HuaweiAuth::AkSk { project_id, .. } => project_id.clone().filter(|p| !p.is_empty()),
+            HuaweiAuth::Token(_) => None,
+        };
+        Ok(Self {
+            client,
+            auth,
+            base_url: format!("https://dns.{region}.myhuaweicloud.com"),
+            region,
+            project_id: Mutex::new(project_id),
+        })
+    }
+
+    // 只替换 DNS 接口地址，AK/SK 模式查询项目 ID 仍按区域访问 IAM
+    pub fn with_endpoint(mut self, endpoint: Option<&str>) -> Result<Self, AppError> {
+        self.base_url = http::endpoint_or(endpoint, &self.base_url)?;
+        Ok(self)
     }
 
-    fn headers(&self) -> Result<HeaderMap, AppError> {
+    async fn send(
+        &self,
+        method: Method,
+        path: &str,
+        query: &[(&str, &str)],
+        body: Option<String>,
+    ) -> Result<reqwest::Response, AppError> {
         let mut headers = HeaderMap::new();
-        headers.insert(
-            "X-Auth-Token",
-            HeaderValue::from_str(&self.token).map_err(|e| AppError::new("invalid_input", e.to_string()))?,
-        );
-        Ok(headers)
+        match &self.auth {
+            HuaweiAuth::Token(token) => {
+                headers.insert(
+                    "X-Auth-Token",
+                    HeaderValue::from_str(token).map_err(|e| AppError::new("invalid_input", e.to_string()))?,
+                );
+            }
+            HuaweiAuth::AkSk { .. } => {
+                let project_id = self.project_id().await?;
+                headers.insert(
+                    "X-Project-Id",
+                    HeaderValue::from_str(&project_id).map_err(|e| AppError::new("invalid_input", e.to_string()))?,
+                );
+            }
+        }
+        let code = match method {
+            Method::POST => "create_failed",
+            Method::PUT => "update_failed",
+            Method::DELETE => "delete_failed",
+            _ => "fetch_failed",
+        };
+        let res = self.dispatch(method, &self.base_url, path, query, headers, body).await?;
+        ensure_success(res, code).await
+    }
+
+    // AK/SK 模式下 DNS 接口需要项目 ID，未填写时按区域名从 IAM 查询一次并缓存
+    async fn project_id(&self) -> Result<String, AppError> {
+        if let Some(id) = self.project_id.lock().unwrap_or_else(|e| e.into_inner()).clone() {
+            return Ok(id);
+        }
+        let base_url = format!("https://iam.{}.myhuaweicloud.com", self.region);
+        let query = [("name", self.region.as_str())];
+        let res = self
+            .dispatch(Method::GET, &base_url, "/v3/projects", &query, HeaderMap::new(), None)
+            .await?;
+        let res = ensure_success(res, "fetch_failed").await?;
+        let parsed: HuaweiProjectListResponse = res.json().await.map_err(AppError::from)?;
+        let id = parsed
+            .projects
+            .unwrap_or_default()
+            .into_iter()
+            .next()
+            .map(|p| p.id)
+            .ok_or_else(|| AppError::new("not_found", format!("No Huawei Cloud project found for region {}", self.region)))?;
+        *self.project_id.lock().unwrap_or_else(|e| e.into_inner()) = Some(id.clone());
+        Ok(id)
+    }
+
+    async fn dispatch(
+        &self,
+        method: Method,
+        base_url: &str,
+        path: &str,
+        query: &[(&str, &str)],
+        mut headers: HeaderMap,
+        body: Option<String>,
+    ) -> Result<reqwest::Response, AppError> {
+        let query = canonical_query(query);
+        let host = http::authority(base_url)?;
+        let signed_path = format!("{}{path}", http::base_path(base_url)?);
+        if body.is_some() {
+            headers.insert("Content-Type", HeaderValue::from_static("application/json"));
+        }
+        if let HuaweiAuth::AkSk {
+            access_key_id,
+            secret_access_key,
+            ..
+        } = &self.auth
+        {
+            let sdk_date = Utc::now().format("%Y%m%dT%H%M%SZ").to_string();
+            headers.insert(
+                "X-Sdk-Date",
+                HeaderValue::from_str(&sdk_date).map_err(|e| AppError::new("invalid_input", e.to_string()))?,
+            );
+            let mut signed: Vec<(String, String)> = vec![("host".to_string(), host)];
+            for (name, value) in headers.iter() {
+                let value = value.to_str().map_err(|e| AppError::new("invalid_input", e.to_string()))?;
+                signed.push((name.as_str().to_string(), value.to_string()));
+            }
+            let authorization = sign_request(
+                access_key_id,
+                secret_access_key,
+                method.as_str(),
+                &signed_path,
+                &query,
+                &signed,
+                body.as_deref().unwrap_or(""),
+                &sdk_date,
+            );
+            headers.insert(
+                "Authorization",
+                HeaderValue::from_str(&authorization).map_err(|e| AppError::new("invalid_input", e.to_string()))?,
+            );
+        }
+        let url = if query.is_empty() {
+            format!("{base_url}{path}")
+        } else {
+            format!("{base_url}{path}?{query}")
+        };
+        let call = if method == Method::GET { Call::Read } else { Call::Write };
+        http::send(Provider::Huawei, call, || {
+            let builder = self.client.request(method.clone(), &url).headers(headers.clone());
+            Ok(match &body {
+                Some(body) => builder.body(body.clone()),
+                None => builder,
+            })
+        })
+        .await
     }
 
     pub async fn test(&self) -> Result<(), AppError> {
@@ -37,14 +189,9 @@ impl HuaweiClient {
     }
 
     pub async fn list_domains(&self) -> Result<Vec<DomainItem>, AppError> {
-        let url = format!("{API_BASE}/v2/zones?type=public&limit=500");
         let res = self
-            .client
-            .get(url)
-            .headers(self.headers()?)
-            .send()
-            .await
-            .map_err(AppError::from)?;
+            .send(Method::GET, "/v2/zones", &[("type", "public"), ("limit", "500")], None)
+            .await?;
         let parsed: HuaweiZoneListResponse = res.json().await.map_err(AppError::from)?;
         let mut items = Vec::new();
         for zone in parsed.zones.unwrap_or_default() {
@@ -55,47 +202,35 @@ impl HuaweiClient {
                 status: DomainStatus::Ok,
                 records_count: zone.record_num,
                 last_changed_at: zone.update_at.and_then(|s| parse_huawei_time(&s).ok()).map(|d| d.to_rfc3339()),
+                display_name: None,
             });
         }
         Ok(items)
     }
 
     pub async fn list_records(&self, zone_id: &str, domain_name: &str) -> Result<Vec<DnsRecord>, AppError> {
-        let url = format!("{API_BASE}/v2/zones/{zone_id}/recordsets?limit=500");
-        let res = self
-            .client
-            .get(url)
-            .headers(self.headers()?)
-            .send()
-            .await
-            .map_err(AppError::from)?;
-        let parsed: HuaweiRecordsetListResponse = res.json().await.map_err(AppError::from)?;
+        let recordsets = http::paginate(|page| self.recordsets_page(zone_id, page)).await?;
         let mut items = Vec::new();
-        for recordset in parsed.recordsets.unwrap_or_default() {
-            let raw_value = recordset.records.get(0).cloned().unwrap_or_default();
-            let (content, srv_priority, srv_weight, srv_port, caa_flags, caa_tag) =
-                parse_huawei_record_value(&recordset.record_type, &raw_value);
-            items.push(DnsRecord {
-                id: recordset.id,
-                provider: Provider::Huawei,
-                domain: domain_name.to_string(),
-                record_type: recordset.record_type,
-                name: huawei_rr(&recordset.name, domain_name),
-                content,
-                ttl: recordset.ttl,
-                mx_priority: recordset.priority,
-                srv_priority,
-                srv_weight,
-                srv_port,
-                caa_flags,
-                caa_tag,
-            });
+        for recordset in recordsets {
+            items.extend(expand_recordset(&recordset, domain_name));
         }
         Ok(items)
     }
 
+    async fn recordsets_page(&self, zone_id: &str, page: u32) -> Result<(Vec<HuaweiRecordset>, bool), AppError> {
+        let path = format!("{RECORDSET_API}/zones/{zone_id}/recordsets");
+        let limit = RECORDSETS_PER_PAGE.to_string();
+        let offset = ((page - 1) * RECORDSETS_PER_PAGE).to_string();
+        let query = [("limit", limit.as_str()), ("offset", offset.as_str())];
+        let res = self.send(Method::GET, &path, &query, None).await?;
+        let parsed: HuaweiRecordsetListResponse = res.json().await.map_err(AppError::from)?;
+        let recordsets = parsed.recordsets.unwrap_or_default();
+        let total = parsed.metadata.and_then(|m| m.total_count);
+        let more = http::more_pages(page, RECORDSETS_PER_PAGE, recordsets.len(), total);
+        Ok((recordsets, more))
+    }
+
     pub async fn create_record(&self, zone_id: &str, domain_name: &str, req: &RecordCreateRequest) -> Result<DnsRecord, AppError> {
-        let url = format!("{API_BASE}/v2/zones/{zone_id}/recordsets");
         let value = huawei_value(
             &req.record_type,
             &req.content,
@@ -104,43 +239,23 @@ impl HuaweiClient {
             req.srv_port,
             req.caa_flags,
             req.caa_tag.as_deref(),
+            &req.extra,
         );
         let payload = HuaweiRecordsetCreateRequest {
             name: huawei_full_name(domain_name, &req.name),
             record_type: req.record_type.clone(),
             ttl: req.ttl,
-            records: vec![value],
+            records: vec![value.clone()],
+            description: req.comment.clone(),
+            weight: req.weight,
         };
-        let res = self
-            .client
-            .post(url)
-            .headers(self.headers()?)
-            .json(&payload)
-            .send()
-            .await
-            .map_err(AppError::from)?;
-        let parsed: HuaweiRecordset = res.json().await.map_err(AppError::from)?;
-        let (content, srv_priority, srv_weight, srv_port, caa_flags, caa_tag) =
-            parse_huawei_record_value(&parsed.record_type, &req.content);
-        Ok(DnsRecord {
-            id: parsed.id,
-            provider: Provider::Huawei,
-            domain: domain_name.to_string(),
-            record_type: parsed.record_type,
-            name: huawei_rr(&parsed.name, domain_name),
-            content,
-            ttl: parsed.ttl,
-            mx_priority: parsed.priority,
-            srv_priority,
-            srv_weight,
-            srv_port,
-            caa_flags,
-            caa_tag,
-        })
+        let parsed = self.add_value(zone_id, payload).await?;
+        Ok(huawei_record(&parsed, &value, domain_name))
     }
 
     pub async fn update_record(&self, zone_id: &str, domain_name: &str, req: &RecordUpdateRequest) -> Result<DnsRecord, AppError> {
-        let url = format!("{API_BASE}/v2/zones/{zone_id}/recordsets/{}", req.id);
+        let (recordset_id, key) = split_record_id(&req.id);
+        let recordset = self.get_recordset(zone_id, recordset_id).await?;
         let value = huawei_value(
             &req.record_type,
             &req.content,
@@ -149,83 +264,368 @@ impl HuaweiClient {
             req.srv_port,
             req.caa_flags,
             req.caa_tag.as_deref(),
+            &req.extra,
         );
+        let full_name = huawei_full_name(domain_name, &req.name);
         let payload = HuaweiRecordsetCreateRequest {
-            name: huawei_full_name(domain_name, &req.name),
+            name: full_name.clone(),
             record_type: req.record_type.clone(),
             ttl: req.ttl,
-            records: vec![value],
+            records: vec![value.clone()],
+            description: req.comment.clone(),
+            weight: req.weight,
         };
-        let res = self
-            .client
-            .put(url)
-            .headers(self.headers()?)
-            .json(&payload)
-            .send()
-            .await
-            .map_err(AppError::from)?;
-        let parsed: HuaweiRecordset = res.json().await.map_err(AppError::from)?;
-        let (content, srv_priority, srv_weight, srv_port, caa_flags, caa_tag) =
-            parse_huawei_record_value(&parsed.record_type, &req.content);
-        Ok(DnsRecord {
-            id: parsed.id,
-            provider: Provider::Huawei,
-            domain: domain_name.to_string(),
-            record_type: parsed.record_type,
-            name: huawei_rr(&parsed.name, domain_name),
-            content,
-            ttl: parsed.ttl,
-            mx_priority: parsed.priority,
-            srv_priority,
-            srv_weight,
-            srv_port,
-            caa_flags,
-            caa_tag,
-        })
+        let key = match key {
+            Some(key) => key,
+            None => {
+                // 旧版记录 ID 指向整个记录集，保持原来整体覆盖的行为
+                let parsed = self.put_recordset(zone_id, recordset_id, &payload).await?;
+                return Ok(huawei_record(&parsed, &value, domain_name));
+            }
+        };
+        let index = value_index(&recordset, key)?;
+
+        let same_set = recordset.record_type == req.record_type
+            && recordset.name.trim_end_matches('.').eq_ignore_ascii_case(full_name.trim_end_matches('.'));
+        if !same_set {
+            // 改了主机记录或类型：从原记录集摘掉这个值，再放进目标记录集
+            self.remove_value(zone_id, recordset, index).await?;
+            let parsed = self.add_value(zone_id, payload).await?;
+            return Ok(huawei_record(&parsed, &value, domain_name));
+        }
+
+        let mut records = recordset.records.clone();
+        if records.iter().enumerate().any(|(i, v)| i != index && v == &value) {
+            return Err(AppError::new("conflict", "Record with the same value already exists"));
+        }
+        records[index] = value.clone();
+        let payload = HuaweiRecordsetCreateRequest { records, ..payload };
+        let parsed = self.put_recordset(zone_id, &recordset.id, &payload).await?;
+        Ok(huawei_record(&parsed, &value, domain_name))
     }
 
     pub async fn delete_record(&self, zone_id: &str, record_id: &str) -> Result<(), AppError> {
-        let url = format!("{API_BASE}/v2/zones/{zone_id}/recordsets/{record_id}");
-        let res = self
-            .client
-            .delete(url)
-            .headers(self.headers()?)
-            .send()
-            .await
-            .map_err(AppError::from)?;
-        if !res.status().is_success() {
-            let status = res.status();
-            let text = res.text().await.unwrap_or_default();
+        let (recordset_id, key) = split_record_id(record_id);
+        let key = match key {
+            Some(key) => key,
+            None => return self.delete_recordset(zone_id, recordset_id).await,
+        };
+        let recordset = self.get_recordset(zone_id, recordset_id).await?;
+        let index = value_index(&recordset, key)?;
+        self.remove_value(zone_id, recordset, index).await
+    }
+
+    /// 用单个值整体覆盖记录集，同名同类型的其他值一并移除。
+    pub async fn replace_recordset(&self, zone_id: &str, domain_name: &str, req: &RecordUpdateRequest) -> Result<DnsRecord, AppError> {
+        let (recordset_id, _) = split_record_id(&req.id);
+        let legacy = RecordUpdateRequest {
+            id: recordset_id.to_string(),
+            ..req.clone()
+        };
+        self.update_record(zone_id, domain_name, &legacy).await
+    }
+
+    // 同名同类型的记录集已存在时把值追加进去，否则新建记录集
+    async fn add_value(&self, zone_id: &str, payload: HuaweiRecordsetCreateRequest) -> Result<HuaweiRecordset, AppError> {
+        if let Some(existing) = self.find_recordset(zone_id, &payload.name, &payload.record_type).await? {
+            let mut records = existing.records;
+            for value in payload.records {
+                if records.contains(&value) {
+                    return Err(AppError::new("conflict", "Record with the same value already exists"));
+                }
+                records.push(value);
+            }
+            let merged = HuaweiRecordsetCreateRequest {
+                name: existing.name,
+                record_type: existing.record_type,
+                ttl: payload.ttl,
+                records,
+                description: payload.description.or(existing.description),
+                weight: payload.weight.or(existing.weight),
+            };
+            return self.put_recordset(zone_id, &existing.id, &merged).await;
+        }
+        let path = format!("{RECORDSET_API}/zones/{zone_id}/recordsets");
+        let res = self.send(Method::POST, &path, &[], Some(json_body(&payload)?)).await?;
+        res.json().await.map_err(AppError::from)
+    }
+
+    async fn remove_value(&self, zone_id: &str, recordset: HuaweiRecordset, index: usize) -> Result<(), AppError> {
+        if recordset.records.len() <= 1 {
+            return self.delete_recordset(zone_id, &recordset.id).await;
+        }
+        let mut records = recordset.records;
+        records.remove(index);
+        let payload = HuaweiRecordsetCreateRequest {
+            name: recordset.name,
+            record_type: recordset.record_type,
+            ttl: recordset.ttl,
+            records,
+            description: recordset.description,
+            weight: recordset.weight,
+        };
+        self.put_recordset(zone_id, &recordset.id, &payload).await?;
+        Ok(())
+    }
+
+    async fn find_recordset(&self, zone_id: &str, full_name: &str, record_type: &str) -> Result<Option<HuaweiRecordset>, AppError> {
+        let path = format!("{RECORDSET_API}/zones/{zone_id}/recordsets");
+        let query = [("name", full_name), ("type", record_type), ("search_mode", "equal")];
+        let res = self.send(Method::GET, &path, &query, None).await?;
+        let parsed: HuaweiRecordsetListResponse = res.json().await.map_err(AppError::from)?;
+        let wanted = full_name.trim_end_matches('.');
+        Ok(parsed.recordsets.unwrap_or_default().into_iter().find(|r| {
+            r.record_type == record_type && r.name.trim_end_matches('.').eq_ignore_ascii_case(wanted)
+        }))
+    }
+
+    async fn get_recordset(&self, zone_id: &str, recordset_id: &str) -> Result<HuaweiRecordset, AppError> {
+        let path = format!("{RECORDSET_API}/zones/{zone_id}/recordsets/{recordset_id}");
+        let res = self.send(Method::GET, &path, &[], None).await?;
+        res.json().await.map_err(AppError::from)
+    }
+
+    async fn put_recordset(
+        &self,
+        zone_id: &str,
+        recordset_id: &str,
+        payload: &HuaweiRecordsetCreateRequest,
+    ) -> Result<HuaweiRecordset, AppError> {
+        let path = format!("{RECORDSET_API}/zones/{zone_id}/recordsets/{recordset_id}");
+        let res = self.send(Method::PUT, &path, &[], Some(json_body(payload)?)).await?;
+        res.json().await.map_err(AppError::from)
+    }
+
+    async fn delete_recordset(&self, zone_id: &str, recordset_id: &str) -> Result<(), AppError> {
+        let path = format!("{RECORDSET_API}/zones/{zone_id}/recordsets/{recordset_id}");
+        self.send(Method::DELETE, &path, &[], None).await?;
+        Ok(())
+    }
+
+    /// 批量设置权重。权重属于整个记录集，同一记录集的值必须给相同的权重，全部检查通过后才写入。
+    pub async fn set_record_weights(&self, zone_id: &str, weights: &[RecordWeight]) -> Result<(), AppError> {
+        let mut grouped: Vec<(&str, u16)> = Vec::new();
+        for item in weights {
+            let (recordset_id, _) = split_record_id(&item.id);
+            match grouped.iter().find(|(id, _)| *id == recordset_id) {
+                Some((_, weight)) if *weight != item.weight => {
+                    return Err(AppError::new(
+                        "invalid_input",
+                        format!("Values of recordset {recordset_id} share one weight and cannot be weighted differently"),
+                    ));
+                }
+                Some(_) => {}
+                None => grouped.push((recordset_id, item.weight)),
+            }
+        }
+        for (recordset_id, weight) in grouped {
+            let recordset = self.get_recordset(zone_id, recordset_id).await?;
+            let payload = HuaweiRecordsetCreateRequest {
+                name: recordset.name,
+                record_type: recordset.record_type,
+                ttl: recordset.ttl,
+                records: recordset.records,
+                description: recordset.description,
+                weight: Some(weight),
+            };
+            self.put_recordset(zone_id, recordset_id, &payload).await?;
+        }
+        Ok(())
+    }
+
+    pub async fn set_record_status(&self, zone_id: &str, record_id: &str, status: RecordStatus) -> Result<(), AppError> {
+        // 状态作用于整个记录集，有多个值时暂停一个会连带其他值，这种情况直接拒绝
+        let (recordset_id, _) = split_record_id(record_id);
+        let recordset = self.get_recordset(zone_id, recordset_id).await?;
+        if recordset.records.len() > 1 {
             return Err(AppError::new(
-                "delete_failed",
+                "unsupported",
                 format!(
-                    "HTTP {}: {} (response text: {})",
-                    status.as_u16(),
-                    status.canonical_reason().unwrap_or("Unknown"),
-                    text
+                    "Huawei Cloud sets status per recordset; recordset {recordset_id} has {} values that would all change",
+                    recordset.records.len()
                 ),
             ));
         }
+        let path = format!("{RECORDSET_API}/recordsets/{recordset_id}/statuses/set");
+        let status = match status {
+            RecordStatus::Enabled => "ENABLE",
+            RecordStatus::Disabled => "DISABLE",
+        };
+        let body = json_body(&serde_json::json!({ "status": status }))?;
+        self.send(Method::PUT, &path, &[], Some(body)).await?;
         Ok(())
     }
 }
 
+async fn ensure_success(res: reqwest::Response, code: &str) -> Result<reqwest::Response, AppError> {
+    let status = res.status();
+    if status.is_success() {
+        return Ok(res);
+    }
+    let request_id = res
+        .headers()
+        .get("X-Request-Id")
+        .and_then(|v| v.to_str().ok())
+        .map(|v| v.to_string());
+    let text = res.text().await.unwrap_or_default();
+    let parsed: HuaweiErrorResponse = serde_json::from_str(&text).unwrap_or_default();
+    let provider_code = parsed.error_code.or(parsed.code).unwrap_or_default();
+    let message = parsed
+        .error_msg
+        .or(parsed.message)
+        .unwrap_or_else(|| format!("HTTP {}: {}", status.as_u16(), text));
+    let code = if status.as_u16() == 401 || status.as_u16() == 403 {
+        "auth_failed"
+    } else {
+        code
+    };
+    Err(AppError::http(code, status.as_u16(), message)
+        .with_provider_code(provider_code)
+        .with_request_id(request_id))
+}
+
+fn json_body<T: serde::Serialize>(payload: &T) -> Result<String, AppError> {
+    serde_json::to_string(payload).map_err(|e| AppError::new("serialize_error", e.to_string()))
+}
+
+fn canonical_query(query: &[(&str, &str)]) -> String {
+    let mut pairs: Vec<(String, String)> = query
+        .iter()
+        .map(|(k, v)| (urlencoding::encode(k).into_owned(), urlencoding::encode(v).into_owned()))
+        .collect();
+    pairs.sort();
+    pairs
+        .iter()
+        .map(|(k, v)| format!("{k}={v}"))
+        .collect::<Vec<_>>()
+        .join("&")
+}
+
+/// 华为云 APIG 的 SDK-HMAC-SHA256 签名，返回 Authorization 头的值。
+/// `query` 需要是已经排序编码好的查询串，`headers` 为参与签名的全部请求头。
+#[allow(clippy::too_many_arguments)]
+fn sign_request(
+    access_key_id: &str,
+    secret_access_key: &str,
+    method: &str,
+    path: &str,
+    query: &str,
+    headers: &[(String, String)],
+    body: &str,
+    sdk_date: &str,
+) -> String {
+    let mut canonical_uri = path
+        .split('/')
+        .map(|segment| urlencoding::encode(segment).into_owned())
+        .collect::<Vec<_>>()
+        .join("/");
+    if !canonical_uri.ends_with('/') {
+        canonical_uri.push('/');
+    }
+    let mut headers: Vec<(String, String)> = headers
+        .iter()
+        .map(|(k, v)| (k.to_ascii_lowercase(), v.trim().to_string()))
+        .collect();
+    headers.sort();
+    let canonical_headers: String = headers.iter().map(|(k, v)| format!("{k}:{v}\n")).collect();
+    let signed_headers = headers.iter().map(|(k, _)| k.as_str()).collect::<Vec<_>>().join(";");
+    let canonical_request = format!(
+        "{method}\n{canonical_uri}\n{query}\n{canonical_headers}\n{signed_headers}\n{}",
+        hex::encode(Sha256::digest(body.as_bytes()))
+    );
+    let string_to_sign = format!(
+        "{SIGN_ALGORITHM}\n{sdk_date}\n{}",
+        hex::encode(Sha256::digest(canonical_request.as_bytes()))
+    );
+    let mut mac = HmacSha256::new_from_slice(secret_access_key.as_bytes()).expect("hmac can take key of any size");
+    mac.update(string_to_sign.as_bytes());
+    let signature = hex::encode(mac.finalize().into_bytes());
+    format!("{SIGN_ALGORITHM} Access={access_key_id}, SignedHeaders={signed_headers}, Signature={signature}")
+}
+
+// 记录 ID 由记录集 ID 和值的摘要组成，值被别人改动或重排后不会误改到其他值
+const VALUE_ID_SEPARATOR: char = ':';
+
+fn record_id(recordset_id: &str, value: &str) -> String {
+    format!("{recordset_id}{VALUE_ID_SEPARATOR}{}", value_key(value))
+}
+
+fn split_record_id(id: &str) -> (&str, Option<&str>) {
+    match id.split_once(VALUE_ID_SEPARATOR) {
+        Some((recordset_id, key)) => (recordset_id, Some(key)),
+        None => (id, None),
+    }
+}
+
+fn value_key(value: &str) -> String {
+    hex::encode(&Sha256::digest(value.as_bytes())[..8])
+}
+
+fn value_index(recordset: &HuaweiRecordset, key: &str) -> Result<usize, AppError> {
+    recordset
+        .records
+        .iter()
+        .position(|v| value_key(v) == key)
+        .ok_or_else(|| AppError::new("not_found", "Record value no longer exists, please refresh"))
+}
+
+fn expand_recordset(recordset: &HuaweiRecordset, domain_name: &str) -> Vec<DnsRecord> {
+    recordset
+        .records
+        .iter()
+        .map(|value| huawei_record(recordset, value, domain_name))
+        .collect()
+}
+
+fn huawei_record(recordset: &HuaweiRecordset, value: &str, domain_name: &str) -> DnsRecord {
+    let (content, srv_priority, srv_weight, srv_port, caa_flags, caa_tag) =
+        parse_huawei_record_value(&recordset.record_type, value);
+    let record = DnsRecord {
+        id: record_id(&recordset.id, value),
+        provider: Provider::Huawei,
+        domain: domain_name.to_string(),
+        record_type: recordset.record_type.clone(),
+        name: huawei_rr(&recordset.name, domain_name),
+        content,
+        ttl: recordset.ttl,
+        mx_priority: recordset.priority,
+        srv_priority,
+        srv_weight,
+        srv_port,
+        caa_flags,
+        caa_tag,
+        status: huawei_status(recordset.status.as_deref()),
+        comment: recordset.description.clone().filter(|d| !d.is_empty()),
+        tags: Vec::new(),
+        weight: recordset.weight,
+        display_name: None,
+        extra: RecordExtra::default(),
+    };
+    svcb::split_record(record)
+}
+
+fn huawei_status(status: Option<&str>) -> RecordStatus {
+    match status {
+        Some("DISABLE") => RecordStatus::Disabled,
+        _ => RecordStatus::Enabled,
+    }
+}
+
 fn parse_huawei_time(value: &str) -> Result<DateTime<Utc>, anyhow::Error> {
     let dt = chrono::DateTime::parse_from_rfc3339(value)?;
     Ok(dt.with_timezone(&Utc))
 }
 
 fn huawei_full_name(domain_name: &str, host: &str) -> String {
+    let domain = domain_name.trim_end_matches('.');
+    let lower = host.trim_end_matches('.').to_ascii_lowercase();
+    let zone = domain.to_ascii_lowercase();
     if host == "@" {
-        format!("{}.", domain_name.trim_end_matches('.'))
-    } else if host.ends_with(domain_name) {
-        if host.ends_with('.') {
-            host.to_string()
-        } else {
-            format!("{}.", host)
-        }
+        format!("{domain}.")
+    } else if lower == zone || lower.ends_with(&format!(".{zone}")) {
+        format!("{}.", host.trim_end_matches('.'))
     } else {
-        format!("{}.{}.", host, domain_name.trim_end_matches('.'))
+        format!("{host}.{domain}.")
     }
 }
 
@@ -254,9 +654,34 @@ struct HuaweiZone {
     update_at: Option<String>,
 }
 
+// DNS 接口用 code/message，IAM 和网关用 error_code/error_msg
+#[derive(Debug, Deserialize, Default)]
+struct HuaweiErrorResponse {
+    code: Option<String>,
+    message: Option<String>,
+    error_code: Option<String>,
+    error_msg: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct HuaweiProjectListResponse {
+    projects: Option<Vec<HuaweiProject>>,
+}
+
+#[derive(Debug, Deserialize)]
+struct HuaweiProject {
+    id: String,
+}
+
 #[derive(Debug, Deserialize)]
 struct HuaweiRecordsetListResponse {
     recordsets: Option<Vec<HuaweiRecordset>>,
+    metadata: Option<HuaweiListMetadata>,
+}
+
+#[derive(Debug, Deserialize)]
+struct HuaweiListMetadata {
+    total_count: Option<u64>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -268,6 +693,9 @@ struct HuaweiRecordset {
     ttl: u32,
     records: Vec<String>,
     priority: Option<u16>,
+    status: Option<String>,
+    description: Option<String>,
+    weight: Option<u16>,
 }
 
 #[derive(Debug, serde::Serialize)]
@@ -277,8 +705,13 @@ struct HuaweiRecordsetCreateRequest {
     record_type: String,
     ttl: u32,
     records: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    description: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    weight: Option<u16>,
 }
 
+#[allow(clippy::too_many_arguments)]
 fn huawei_value(
     record_type: &str,
     content: &str,
@@ -287,8 +720,12 @@ fn huawei_value(
     srv_port: Option<u16>,
     caa_flags: Option<u8>,
     caa_tag: Option<&str>,
+    extra: &RecordExtra,
 ) -> String {
     match record_type {
+        "HTTPS" | "SVCB" => svcb::presentation(content, extra),
+        // 华为云要求 TXT 记录值带引号
+        "TXT" => txt::encode(content),
         "SRV" => format!(
             "{} {} {} {}",
             srv_priority.unwrap_or(0),
@@ -334,6 +771,249 @@ fn parse_huawei_record_value(
                 (value.to_string(), None, None, None, None, None)
             }
         }
+        "TXT" => (txt::decode(value), None, None, None, None, None),
         _ => (value.to_string(), None, None, None, None, None),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::providers::mock::{self, create_request, update_request, MockServer};
+    use serde_json::json;
+
+    fn recordset(records: &[&str]) -> HuaweiRecordset {
+        HuaweiRecordset {
+            id: "ff8080825b8fc86c015b94bc6f8712c3".to_string(),
+            name: "www.example.com.".to_string(),
+            record_type: "A".to_string(),
+            ttl: 300,
+            records: records.iter().map(|s| s.to_string()).collect(),
+            priority: None,
+            status: Some("ACTIVE".to_string()),
+            description: None,
+            weight: None,
+        }
+    }
+
+    #[test]
+    fn expands_every_value_with_its_own_id() {
+        let set = recordset(&["192.0.2.1", "192.0.2.2"]);
+        let records = expand_recordset(&set, "example.com");
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[0].name, "www");
+        assert_eq!(records[1].content, "192.0.2.2");
+        assert_ne!(records[0].id, records[1].id);
+
+        let (recordset_id, key) = split_record_id(&records[1].id);
+        assert_eq!(recordset_id, set.id);
+        assert_eq!(value_index(&set, key.unwrap()).unwrap(), 1);
+    }
+
+    #[test]
+    fn legacy_id_refers_to_whole_recordset() {
+        assert_eq!(split_record_id("ff8080825b8fc86c015b94bc6f8712c3"), ("ff8080825b8fc86c015b94bc6f8712c3", None));
+    }
+
+    // 华为云 API 签名文档中的示例请求
+    #[test]
+    fn signs_request_like_documented_example() {
+        let query = canonical_query(&[("marker", "13551d6b-755d-4757-b956-536f674975c0"), ("limit", "2")]);
+        assert_eq!(query, "limit=2&marker=13551d6b-755d-4757-b956-536f674975c0");
+        let headers = vec![
+            ("Content-Type".to_string(), "application/json".to_string()),
+            ("Host".to_string(), "service.region.example.com".to_string()),
+            ("X-Sdk-Date".to_string(), "20191115T033655Z".to_string()),
+        ];
+        let authorization = sign_request(
+            "QTWAOYTTINDUT2QVKYUC",
+            "MFyfvK41ba2giqM7Uio6PznpdUKGpownRZlmVmHc",
+            "GET",
+            "/v1/77b6a44cba5143ab91d13ab9a8ff44fd/vpcs",
+            &query,
+            &headers,
+            "",
+            "20191115T033655Z",
+        );
+        assert_eq!(
+            authorization,
+            "SDK-HMAC-SHA256 Access=QTWAOYTTINDUT2QVKYUC, SignedHeaders=content-type;host;x-sdk-date, \
+             Signature=7be6668032f70418fcc22abc52071e57aff61b84a1d2381bb430d6870f4f6ebe"
+        );
+    }
+
+    #[test]
+    fn full_name_only_keeps_names_inside_the_zone() {
+        assert_eq!(huawei_full_name("example.com.", "@"), "example.com.");
+        assert_eq!(huawei_full_name("example.com", "www"), "www.example.com.");
+        assert_eq!(huawei_full_name("example.com", "WWW.Example.com."), "WWW.Example.com.");
+        assert_eq!(huawei_full_name("example.com", "notexample.com"), "notexample.com.example.com.");
+        assert_eq!(huawei_full_name("xn--fiq228c.com", "www"), "www.xn--fiq228c.com.");
+    }
+
+    #[test]
+    fn stale_value_is_not_found() {
+        let set = recordset(&["192.0.2.1"]);
+        let err = value_index(&set, &value_key("192.0.2.9")).unwrap_err();
+        assert_eq!(err.code, "not_found");
+    }
+
+    fn client(server: &MockServer) -> HuaweiClient {
+        server.client(|endpoint| HuaweiClient::new(HuaweiAuth::Token("iam-token".to_string()), None)?.with_endpoint(endpoint))
+    }
+
+    fn recordset_json(id: &str, name: &str, record_type: &str, records: &[&str]) -> serde_json::Value {
+        json!({ "id": id, "name": name, "type": record_type, "ttl": 300, "records": records, "status": "ACTIVE" })
+    }
+
+    #[tokio::test]
+    async fn lists_recordsets_across_pages() {
+        let server = MockServer::start(|req| {
+            let recordsets = mock::two_pages(
+                req.param("offset").as_deref() == Some("0"),
+                RECORDSETS_PER_PAGE,
+                |i| recordset_json(&format!("rs{i}"), "www.example.com.", "A", &["192.0.2.1"]),
+                vec![
+                    recordset_json("srv", "_sip._tcp.example.com.", "SRV", &["10 20 5060 a.example.com.", "10 20 5060 b.example.com."]),
+                    json!({ "id": "caa", "name": "example.com.", "type": "CAA", "ttl": 300, "records": ["0 issue \"letsencrypt.org\""], "status": "DISABLE" }),
+                ],
+            );
+            (200, json!({ "recordsets": recordsets, "metadata": { "total_count": 502 } }))
+        })
+        .await;
+
+        let records = client(&server).list_records("zone1", "example.com").await.unwrap();
+
+        assert_eq!(server.count(|r| r.path == "/v2.1/zones/zone1/recordsets"), 2);
+        assert_eq!(records.len(), 503);
+        assert_eq!(records[500].name, "_sip._tcp");
+        assert_eq!((records[501].srv_port, records[501].content.as_str()), (Some(5060), "b.example.com."));
+        let caa = &records[502];
+        assert_eq!((caa.name.as_str(), caa.caa_tag.as_deref()), ("@", Some("issue")));
+        assert_eq!(caa.status, RecordStatus::Disabled);
+        assert_eq!(server.requests()[0].header("x-auth-token"), Some("iam-token"));
+    }
+
+    #[tokio::test]
+    async fn new_value_joins_existing_recordset() {
+        let server = MockServer::start(|req| match req.method.as_str() {
+            "GET" => (200, json!({ "recordsets": [recordset_json("rs1", "www.example.com.", "A", &["192.0.2.1"])] })),
+            _ => {
+                let mut body = req.json();
+                body["id"] = json!("rs1");
+                (202, body)
+            }
+        })
+        .await;
+        let client = client(&server);
+
+        let created = client
+            .create_record("zone1", "example.com", &create_request("A", "www", "192.0.2.2"))
+            .await
+            .unwrap();
+        assert_eq!(created.content, "192.0.2.2");
+        assert_eq!(split_record_id(&created.id).0, "rs1");
+        let put = server.requests().into_iter().find(|r| r.method == "PUT").unwrap();
+        assert!(put.is("PUT", "/v2.1/zones/zone1/recordsets/rs1"));
+        assert_eq!(put.json()["records"], json!(["192.0.2.1", "192.0.2.2"]));
+
+        let err = client
+            .create_record("zone1", "example.com", &create_request("A", "www", "192.0.2.1"))
+            .await
+            .unwrap_err();
+        assert_eq!(err.code, "conflict");
+    }
+
+    #[tokio::test]
+    async fn recordset_errors_keep_provider_details() {
+        let server = MockServer::start(|req| match (req.method.as_str(), req.path.as_str()) {
+            ("GET", "/v2.1/zones/zone1/recordsets/gone") => {
+                (404, json!({ "code": "DNS.0312", "message": "Record set does not exist." }))
+            }
+            ("GET", _) => (200, recordset_json("rs1", "www.example.com.", "A", &["192.0.2.1"])),
+            _ => (400, json!({ "code": "DNS.0303", "message": "Record set is locked." })),
+        })
+        .await;
+        let client = client(&server);
+
+        let err = client
+            .update_record("zone1", "example.com", &update_request("gone", "A", "www", "192.0.2.9"))
+            .await
+            .unwrap_err();
+        assert_eq!((err.code.as_str(), err.http_status()), ("fetch_failed", Some(404)));
+        assert_eq!(err.provider_code(), Some("DNS.0312"));
+
+        let err = client
+            .update_record("zone1", "example.com", &update_request("rs1", "A", "www", "192.0.2.9"))
+            .await
+            .unwrap_err();
+        assert_eq!((err.code.as_str(), err.http_status()), ("update_failed", Some(400)));
+        assert_eq!(err.message, "Record set is locked.");
+    }
+
+    #[tokio::test]
+    async fn weights_are_set_once_per_recordset() {
+        let server = MockServer::start(|req| match req.method.as_str() {
+            "GET" => (200, recordset_json("rs1", "www.example.com.", "A", &["192.0.2.1", "192.0.2.2"])),
+            _ => {
+                let mut body = req.json();
+                body["id"] = json!("rs1");
+                (202, body)
+            }
+        })
+        .await;
+        let client = client(&server);
+        let weight = |id: &str, weight: u16| RecordWeight { id: id.to_string(), weight };
+
+        let mixed = [weight(&record_id("rs1", "192.0.2.1"), 3), weight(&record_id("rs1", "192.0.2.2"), 7)];
+        let err = client.set_record_weights("zone1", &mixed).await.unwrap_err();
+        assert_eq!(err.code, "invalid_input");
+        assert!(server.requests().is_empty());
+
+        let same = [weight(&record_id("rs1", "192.0.2.1"), 5), weight(&record_id("rs1", "192.0.2.2"), 5)];
+        client.set_record_weights("zone1", &same).await.unwrap();
+        let puts: Vec<_> = server.requests().into_iter().filter(|r| r.method == "PUT").collect();
+        assert_eq!(puts.len(), 1);
+        assert!(puts[0].is("PUT", "/v2.1/zones/zone1/recordsets/rs1"));
+        assert_eq!(puts[0].json()["weight"], 5);
+        assert_eq!(puts[0].json()["records"], json!(["192.0.2.1", "192.0.2.2"]));
+    }
+
+    #[tokio::test]
+    async fn status_is_only_set_on_single_value_recordsets() {
+        let server = MockServer::start(|req| match req.path.as_str() {
+            "/v2.1/zones/zone1/recordsets/multi" => (200, recordset_json("multi", "www.example.com.", "A", &["192.0.2.1", "192.0.2.2"])),
+            "/v2.1/zones/zone1/recordsets/single" => (200, recordset_json("single", "api.example.com.", "A", &["192.0.2.3"])),
+            _ => (202, json!({ "id": "single", "status": "DISABLE" })),
+        })
+        .await;
+        let client = client(&server);
+
+        let err = client
+            .set_record_status("zone1", &record_id("multi", "192.0.2.1"), RecordStatus::Disabled)
+            .await
+            .unwrap_err();
+        assert_eq!(err.code, "unsupported");
+
+        client
+            .set_record_status("zone1", &record_id("single", "192.0.2.3"), RecordStatus::Disabled)
+            .await
+            .unwrap();
+        let put = server.requests().into_iter().find(|r| r.method == "PUT").unwrap();
+        assert!(put.is("PUT", "/v2.1/recordsets/single/statuses/set"));
+        assert_eq!(put.json()["status"], "DISABLE");
+    }
+
+    #[tokio::test]
+    async fn expired_token_is_an_auth_failure() {
+        let server = MockServer::start(|_| {
+            (401, json!({ "error_code": "APIGW.0301", "error_msg": "Incorrect IAM authentication information" }))
+        })
+        .await;
+
+        let err = client(&server).list_domains().await.unwrap_err();
+
+        assert_eq!(err.code, "auth_failed");
+        assert_eq!(err.provider_code(), Some("APIGW.0301"));
+    }
+}
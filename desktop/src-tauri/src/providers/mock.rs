@@ -0,0 +1,197 @@
+//! 测试用的本地 HTTP 桩服务，配合各客户端的 `with_endpoint` 模拟服务商接口。
+
+use crate::error::AppError;
+use crate::types::{
+    ConflictStrategy, DnsRecord, Provider, RecordCreateRequest, RecordExtra, RecordStatus, RecordUpdateRequest,
+};
+use axum::body::Bytes;
+use axum::http::{HeaderMap, Method, StatusCode, Uri};
+use axum::response::IntoResponse;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+#[derive(Debug, Clone)]
+pub struct Request {
+    pub method: String,
+    pub path: String,
+    pub query: HashMap<String, String>,
+    pub headers: HashMap<String, String>,
+    pub body: String,
+}
+
+impl Request {
+    /// 依次从查询串、表单和 JSON 请求体里取参数。
+    pub fn param(&self, key: &str) -> Option<String> {
+        if let Some(value) = self.query.get(key) {
+            return Some(value.clone());
+        }
+        if let Some((_, value)) = form_pairs(&self.body).into_iter().find(|(k, _)| k == key) {
+            return Some(value);
+        }
+        match self.json().get(key)? {
+            Value::String(s) => Some(s.clone()),
+            other => Some(other.to_string()),
+        }
+    }
+
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers.get(&name.to_ascii_lowercase()).map(String::as_str)
+    }
+
+    pub fn json(&self) -> Value {
+        serde_json::from_str(&self.body).unwrap_or(Value::Null)
+    }
+
+    pub fn is(&self, method: &str, path: &str) -> bool {
+        self.method == method && self.path.trim_end_matches('/') == path.trim_end_matches('/')
+    }
+}
+
+type Handler = dyn Fn(&Request) -> (u16, Value) + Send + Sync;
+
+pub struct MockServer {
+    pub url: String,
+    requests: Arc<Mutex<Vec<Request>>>,
+}
+
+impl MockServer {
+    /// 在随机端口启动桩服务，每个请求交给 `handler` 决定状态码和 JSON 响应。
+    pub async fn start<F>(handler: F) -> Self
+    where
+        F: Fn(&Request) -> (u16, Value) + Send + Sync + 'static,
+    {
+        let handler: Arc<Handler> = Arc::new(handler);
+        let requests = Arc::new(Mutex::new(Vec::new()));
+        let recorded = requests.clone();
+        let app = axum::Router::new().fallback(move |method: Method, uri: Uri, headers: HeaderMap, body: Bytes| {
+            let handler = handler.clone();
+            let recorded = recorded.clone();
+            async move {
+                let request = Request {
+                    method: method.to_string(),
+                    path: uri.path().to_string(),
+                    query: form_pairs(uri.query().unwrap_or_default()).into_iter().collect(),
+                    headers: headers
+                        .iter()
+                        .map(|(k, v)| (k.as_str().to_string(), v.to_str().unwrap_or_default().to_string()))
+                        .collect(),
+                    body: String::from_utf8_lossy(&body).into_owned(),
+                };
+                let (status, body) = handler(&request);
+                recorded.lock().unwrap().push(request);
+                let status = StatusCode::from_u16(status).unwrap_or(StatusCode::INTERNAL_SERVER_ERROR);
+                (status, axum::Json(body)).into_response()
+            }
+        });
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        tokio::spawn(async move {
+            axum::serve(listener, app).await.unwrap();
+        });
+        Self { url, requests }
+    }
+
+    /// 用 `build` 建一个指向桩服务的客户端，`build` 收到的是传给 `with_endpoint` 的地址。
+    pub fn client<C>(&self, build: impl FnOnce(Option<&str>) -> Result<C, AppError>) -> C {
+        build(Some(&self.url)).unwrap()
+    }
+
+    pub fn requests(&self) -> Vec<Request> {
+        self.requests.lock().unwrap().clone()
+    }
+
+    /// 满足条件的请求个数。
+    pub fn count(&self, matches: impl Fn(&Request) -> bool) -> usize {
+        self.requests().iter().filter(|r| matches(r)).count()
+    }
+}
+
+/// 翻页测试的两页数据：第一页用 `filler` 填满 `per_page` 条，之后一页是 `tail`，
+/// 客户端要继续翻页并把两页拼起来。
+pub fn two_pages(first: bool, per_page: u32, filler: impl Fn(u32) -> Value, tail: Vec<Value>) -> Vec<Value> {
+    if first {
+        (0..per_page).map(filler).collect()
+    } else {
+        tail
+    }
+}
+
+/// DNSPod 上 example.com 的一条已启用记录，其他字段用结构体更新语法按需覆盖。
+pub fn record(id: &str, record_type: &str, name: &str, content: &str) -> DnsRecord {
+    DnsRecord {
+        id: id.to_string(),
+        provider: Provider::Dnspod,
+        domain: "example.com".to_string(),
+        record_type: record_type.to_string(),
+        name: name.to_string(),
+        content: content.to_string(),
+        ttl: 600,
+        mx_priority: None,
+        srv_priority: None,
+        srv_weight: None,
+        srv_port: None,
+        caa_flags: None,
+        caa_tag: None,
+        status: RecordStatus::Enabled,
+        comment: None,
+        tags: Vec::new(),
+        weight: None,
+        display_name: None,
+        extra: RecordExtra::default(),
+    }
+}
+
+pub fn create_request(record_type: &str, name: &str, content: &str) -> RecordCreateRequest {
+    RecordCreateRequest {
+        record_type: record_type.to_string(),
+        name: name.to_string(),
+        content: content.to_string(),
+        ttl: 600,
+        conflict_strategy: ConflictStrategy::Append,
+        mx_priority: None,
+        srv_priority: None,
+        srv_weight: None,
+        srv_port: None,
+        caa_flags: None,
+        caa_tag: None,
+        comment: None,
+        tags: None,
+        weight: None,
+        extra: RecordExtra::default(),
+    }
+}
+
+pub fn update_request(id: &str, record_type: &str, name: &str, content: &str) -> RecordUpdateRequest {
+    RecordUpdateRequest {
+        id: id.to_string(),
+        record_type: record_type.to_string(),
+        name: name.to_string(),
+        content: content.to_string(),
+        ttl: 600,
+        mx_priority: None,
+        srv_priority: None,
+        srv_weight: None,
+        srv_port: None,
+        caa_flags: None,
+        caa_tag: None,
+        comment: None,
+        tags: None,
+        weight: None,
+        original_type: None,
+        original_name: None,
+        extra: RecordExtra::default(),
+    }
+}
+
+fn form_pairs(raw: &str) -> Vec<(String, String)> {
+    raw.split('&')
+        .filter_map(|pair| pair.split_once('='))
+        .map(|(k, v)| (decode(k), decode(v)))
+        .collect()
+}
+
+fn decode(value: &str) -> String {
+    let value = value.replace('+', " ");
+    urlencoding::decode(&value).map(|v| v.into_owned()).unwrap_or(value)
+}
@@ -6,3 +6,7 @@ pub mod baidu;
 pub mod dnscom;
 pub mod rainyun;
 pub mod tencentcloud;
+pub mod sts;
+pub mod http;
+#[cfg(test)]
+pub mod mock;
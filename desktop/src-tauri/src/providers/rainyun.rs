@@ -1,25 +1,36 @@
 use crate::error::AppError;
-use crate::types::{DnsRecord, DomainItem, DomainStatus, Provider, RecordCreateRequest, RecordUpdateRequest};
+use crate::providers::http::{self, Call};
+use crate::txt;
+use crate::types::{
+    DnsRecord, DomainItem, DomainStatus, Provider, RecordCreateRequest, RecordExtra, RecordStatus,
+    RecordUpdateRequest,
+};
 use reqwest::header::{HeaderMap, HeaderValue};
 use serde::Serialize;
 use serde_json::Value;
-use std::time::Duration;
 
-const API_BASE: &str = "https://api.v2.rainyun.com";
+pub const API_BASE: &str = "https://api.v2.rainyun.com";
+const RECORDS_PER_PAGE: u32 = 500;
 
 pub struct RainyunClient {
     client: reqwest::Client,
+    base_url: String,
     api_key: String,
 }
 
 impl RainyunClient {
     pub fn new(api_key: String) -> Result<Self, AppError> {
-        let client = reqwest::Client::builder()
-            .user_agent("LaoChenDNS/0.1.0")
-            .timeout(Duration::from_secs(30))
-            .build()
-            .map_err(AppError::from)?;
-        Ok(Self { client, api_key })
+        let client = http::client()?;
+        Ok(Self {
+            client,
+            base_url: API_BASE.to_string(),
+            api_key,
+        })
+    }
+
+    pub fn with_endpoint(mut self, endpoint: Option<&str>) -> Result<Self, AppError> {
+        self.base_url = http::endpoint_or(endpoint, API_BASE)?;
+        Ok(self)
     }
 
     pub async fn test(&self) -> Result<(), AppError> {
@@ -28,15 +39,11 @@ impl RainyunClient {
     }
 
     pub async fn list_domains(&self) -> Result<Vec<DomainItem>, AppError> {
-        let url = format!("{API_BASE}/product/domain/");
-        let res = self
-            .client
-            .get(url)
-            .headers(self.headers()?)
-            .query(&[("options", "{}")])
-            .send()
-            .await
-            .map_err(AppError::from)?;
+        let url = format!("{}/product/domain/", self.base_url);
+        let res = http::send(Provider::Rainyun, Call::Read, || {
+            Ok(self.client.get(&url).headers(self.headers()?).query(&[("options", "{}")]))
+        })
+        .await?;
         let value = parse_json_or_error(res, "fetch_failed").await?;
         let mut items = Vec::new();
         for item in extract_array(&value) {
@@ -48,18 +55,9 @@ impl RainyunClient {
     }
 
     pub async fn list_records(&self, domain_id: &str, domain_name: &str) -> Result<Vec<DnsRecord>, AppError> {
-        let url = format!("{API_BASE}/product/domain/{domain_id}/dns/");
-        let res = self
-            .client
-            .get(url)
-            .headers(self.headers()?)
-            .query(&[("limit", "500"), ("page_no", "1")])
-            .send()
-            .await
-            .map_err(AppError::from)?;
-        let value = parse_json_or_error(res, "fetch_failed").await?;
+        let list = http::paginate(|page| self.records_page(domain_id, page)).await?;
         let mut items = Vec::new();
-        for item in extract_array(&value) {
+        for item in list {
             if let Some(record) = parse_record_item(&item, domain_name) {
                 items.push(record);
             }
@@ -67,8 +65,25 @@ impl RainyunClient {
         Ok(items)
     }
 
+    async fn records_page(&self, domain_id: &str, page: u32) -> Result<(Vec<Value>, bool), AppError> {
+        let url = format!("{}/product/domain/{domain_id}/dns/", self.base_url);
+        let query = [("limit", RECORDS_PER_PAGE.to_string()), ("page_no", page.to_string())];
+        let res = http::send(Provider::Rainyun, Call::Read, || {
+            Ok(self.client.get(&url).headers(self.headers()?).query(&query))
+        })
+        .await?;
+        let value = parse_json_or_error(res, "fetch_failed").await?;
+        let list = extract_array(&value);
+        let total = value
+            .get("data")
+            .and_then(|v| v.get("TotalRecords"))
+            .and_then(|v| v.as_u64());
+        let more = http::more_pages(page, RECORDS_PER_PAGE, list.len(), total);
+        Ok((list, more))
+    }
+
     pub async fn create_record(&self, domain_id: &str, domain_name: &str, req: &RecordCreateRequest) -> Result<DnsRecord, AppError> {
-        let url = format!("{API_BASE}/product/domain/{domain_id}/dns");
+        let url = format!("{}/product/domain/{domain_id}/dns", self.base_url);
         let value = rainyun_value(
             &req.record_type,
             &req.content,
@@ -89,14 +104,10 @@ impl RainyunClient {
             record_type: req.record_type.clone(),
             value,
         };
-        let res = self
-            .client
-            .post(url)
-            .headers(self.headers()?)
-            .json(&payload)
-            .send()
-            .await
-            .map_err(AppError::from)?;
+        let res = http::send(Provider::Rainyun, Call::Write, || {
+            Ok(self.client.post(&url).headers(self.headers()?).json(&payload))
+        })
+        .await?;
         let value = parse_json_or_error(res, "create_failed").await?;
         parse_record_from_response(
             &value,
@@ -117,7 +128,7 @@ impl RainyunClient {
 
     pub async fn update_record(&self, domain_id: &str, domain_name: &str, req: &RecordUpdateRequest) -> Result<DnsRecord, AppError> {
         let record_id = parse_u64(&req.id).ok_or_else(|| AppError::new("invalid_input", "记录 ID 无效"))?;
-        let url = format!("{API_BASE}/product/domain/{domain_id}/dns");
+        let url = format!("{}/product/domain/{domain_id}/dns", self.base_url);
         let value = rainyun_value(
             &req.record_type,
             &req.content,
@@ -138,14 +149,10 @@ impl RainyunClient {
             record_type: req.record_type.clone(),
             value,
         };
-        let res = self
-            .client
-            .patch(url)
-            .headers(self.headers()?)
-            .json(&payload)
-            .send()
-            .await
-            .map_err(AppError::from)?;
+        let res = http::send(Provider::Rainyun, Call::Write, || {
+            Ok(self.client.patch(&url).headers(self.headers()?).json(&payload))
+        })
+        .await?;
         let value = parse_json_or_error(res, "update_failed").await?;
         parse_record_from_response(
             &value,
@@ -166,15 +173,15 @@ impl RainyunClient {
 
     pub async fn delete_record(&self, domain_id: &str, record_id: &str) -> Result<(), AppError> {
         let record_id = parse_u64(record_id).ok_or_else(|| AppError::new("invalid_input", "记录 ID 无效"))?;
-        let url = format!("{API_BASE}/product/domain/{domain_id}/dns/");
-        let res = self
-            .client
-            .delete(url)
-            .headers(self.headers()?)
-            .json(&serde_json::json!({ "record_id": record_id }))
-            .send()
-            .await
-            .map_err(AppError::from)?;
+        let url = format!("{}/product/domain/{domain_id}/dns/", self.base_url);
+        let res = http::send(Provider::Rainyun, Call::Write, || {
+            Ok(self
+                .client
+                .delete(&url)
+                .headers(self.headers()?)
+                .json(&serde_json::json!({ "record_id": record_id })))
+        })
+        .await?;
         if !res.status().is_success() {
             let status = res.status();
             let text = res.text().await.unwrap_or_default();
@@ -183,7 +190,7 @@ impl RainyunClient {
             } else {
                 "delete_failed"
             };
-            return Err(AppError::new(code, format!("HTTP {}: {}", status.as_u16(), text)));
+            return Err(AppError::http(code, status.as_u16(), format!("HTTP {}: {}", status.as_u16(), text)));
         }
         Ok(())
     }
@@ -221,7 +228,7 @@ async fn parse_json_or_error(res: reqwest::Response, code: &str) -> Result<Value
         } else {
             code
         };
-        return Err(AppError::new(mapped, format!("HTTP {}: {}", status.as_u16(), text)));
+        return Err(AppError::http(mapped, status.as_u16(), format!("HTTP {}: {}", status.as_u16(), text)));
     }
     res.json::<Value>().await.map_err(AppError::from)
 }
@@ -239,6 +246,9 @@ fn extract_array(value: &Value) -> Vec<Value> {
     if let Some(arr) = value.get("data").and_then(|v| v.get("list")).and_then(|v| v.as_array()) {
         return arr.clone();
     }
+    if let Some(arr) = value.get("data").and_then(|v| v.get("Records")).and_then(|v| v.as_array()) {
+        return arr.clone();
+    }
     if let Some(arr) = value.get("list").and_then(|v| v.as_array()) {
         return arr.clone();
     }
@@ -263,6 +273,7 @@ fn parse_domain_item(value: &Value) -> Option<DomainItem> {
         status: DomainStatus::Ok,
         records_count,
         last_changed_at,
+        display_name: None,
     })
 }
 
@@ -288,6 +299,12 @@ fn parse_record_item(value: &Value, domain_name: &str) -> Option<DnsRecord> {
         srv_port,
         caa_flags,
         caa_tag,
+        status: RecordStatus::Enabled,
+        comment: None,
+        tags: Vec::new(),
+        weight: None,
+        display_name: None,
+        extra: RecordExtra::default(),
     })
 }
 
@@ -329,6 +346,12 @@ fn parse_record_from_response(
         srv_port,
         caa_flags,
         caa_tag: caa_tag.cloned(),
+        status: RecordStatus::Enabled,
+        comment: None,
+        tags: Vec::new(),
+        weight: None,
+        display_name: None,
+        extra: RecordExtra::default(),
     })
 }
 
@@ -440,6 +463,81 @@ fn parse_record_value(
                 (value.to_string(), None, None, None, None, None)
             }
         }
+        "TXT" => (txt::decode(value), None, None, None, None, None),
         _ => (value.to_string(), None, None, None, None, None),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::providers::mock::{self, create_request, update_request, MockServer};
+    use serde_json::json;
+
+    fn client(server: &MockServer) -> RainyunClient {
+        server.client(|endpoint| RainyunClient::new("test-api-key".to_string())?.with_endpoint(endpoint))
+    }
+
+    #[tokio::test]
+    async fn lists_records_across_pages() {
+        let server = MockServer::start(|req| {
+            let records = mock::two_pages(
+                req.param("page_no").as_deref() == Some("1"),
+                RECORDS_PER_PAGE,
+                |i| json!({ "record_id": i, "host": "www", "type": "A", "value": "192.0.2.1", "ttl": 600 }),
+                vec![
+                    json!({ "record_id": 9001, "host": "_sip._tcp", "type": "SRV", "value": "10 20 5060 sip.example.com", "ttl": 600 }),
+                    json!({ "record_id": 9002, "host": "@", "type": "CAA", "value": "0 issue letsencrypt.org", "ttl": 600 }),
+                ],
+            );
+            (200, json!({ "code": 200, "data": { "TotalRecords": 502, "Records": records } }))
+        })
+        .await;
+
+        let records = client(&server).list_records("77", "example.com").await.unwrap();
+
+        assert_eq!(server.count(|r| r.path == "/product/domain/77/dns/"), 2);
+        assert_eq!(records.len(), 502);
+        assert_eq!((records[500].srv_weight, records[500].content.as_str()), (Some(20), "sip.example.com"));
+        assert_eq!(records[501].caa_tag.as_deref(), Some("issue"));
+    }
+
+    #[tokio::test]
+    async fn writes_send_encoded_values() {
+        let server = MockServer::start(|_| (200, json!({ "code": 200, "data": {} }))).await;
+        let client = client(&server);
+
+        let mut req = create_request("SRV", "_sip._tcp", "sip.example.com");
+        req.srv_priority = Some(10);
+        req.srv_weight = Some(20);
+        req.srv_port = Some(5060);
+        let created = client.create_record("77", "example.com", &req).await.unwrap();
+        assert_eq!((created.srv_priority, created.srv_port), (Some(10), Some(5060)));
+
+        client
+            .update_record("77", "example.com", &update_request("9001", "A", "www", "192.0.2.9"))
+            .await
+            .unwrap();
+        client.delete_record("77", "9001").await.unwrap();
+
+        let requests = server.requests();
+        assert!(requests[0].is("POST", "/product/domain/77/dns"));
+        assert_eq!(requests[0].json()["value"], "10 20 5060 sip.example.com");
+        assert_eq!(requests[0].json()["rain_product_id"], 77);
+        assert!(requests[1].is("PATCH", "/product/domain/77/dns"));
+        assert_eq!(requests[1].json()["record_id"], 9001);
+        assert!(requests[2].is("DELETE", "/product/domain/77/dns"));
+        assert_eq!(requests[2].json()["record_id"], 9001);
+        assert!(requests.iter().all(|r| r.header("x-api-key") == Some("test-api-key")));
+    }
+
+    #[tokio::test]
+    async fn rejected_key_is_an_auth_failure() {
+        let server = MockServer::start(|_| (401, json!({ "code": 40100, "message": "unauthorized" }))).await;
+
+        let err = client(&server).list_domains().await.unwrap_err();
+
+        assert_eq!(err.code, "auth_failed");
+        assert_eq!(err.http_status(), Some(401));
+    }
+}
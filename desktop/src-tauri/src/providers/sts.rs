@@ -0,0 +1,78 @@
+use crate::error::AppError;
+use chrono::{DateTime, Duration, Utc};
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::{Mutex, OnceLock};
+
+pub const ROLE_SESSION_NAME: &str = "laochendns";
+pub const ROLE_DURATION_SECS: u32 = 3600;
+
+// 距离过期不足这个时间就重新 AssumeRole，避免请求途中凭证失效
+const REFRESH_MARGIN_SECS: i64 = 300;
+
+#[derive(Clone)]
+pub struct TemporaryCredentials {
+    pub access_key_id: String,
+    pub access_key_secret: String,
+    pub security_token: String,
+    pub expires_at: DateTime<Utc>,
+}
+
+impl TemporaryCredentials {
+    fn needs_refresh(&self, now: DateTime<Utc>) -> bool {
+        self.expires_at - now < Duration::seconds(REFRESH_MARGIN_SECS)
+    }
+}
+
+fn cache() -> &'static Mutex<HashMap<String, TemporaryCredentials>> {
+    static CACHE: OnceLock<Mutex<HashMap<String, TemporaryCredentials>>> = OnceLock::new();
+    CACHE.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+/// 返回缓存中仍然有效的临时凭证，否则调用 `assume` 重新获取并缓存。
+pub async fn get_or_assume<F, Fut>(
+    provider: &str,
+    access_key_id: &str,
+    role_arn: &str,
+    assume: F,
+) -> Result<TemporaryCredentials, AppError>
+where
+    F: FnOnce() -> Fut,
+    Fut: Future<Output = Result<TemporaryCredentials, AppError>>,
+{
+    let key = format!("{provider}:{access_key_id}:{role_arn}");
+    let cached = cache()
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .get(&key)
+        .filter(|c| !c.needs_refresh(Utc::now()))
+        .cloned();
+    if let Some(creds) = cached {
+        return Ok(creds);
+    }
+    let creds = assume().await?;
+    cache()
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .insert(key, creds.clone());
+    Ok(creds)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn refreshes_shortly_before_expiry() {
+        let now = Utc::now();
+        let mut creds = TemporaryCredentials {
+            access_key_id: "STS.id".to_string(),
+            access_key_secret: "secret".to_string(),
+            security_token: "token".to_string(),
+            expires_at: now + Duration::seconds(3600),
+        };
+        assert!(!creds.needs_refresh(now));
+        creds.expires_at = now + Duration::seconds(60);
+        assert!(creds.needs_refresh(now));
+    }
+}
@@ -1,39 +1,87 @@
 use crate::error::AppError;
-use crate::types::{DnsRecord, DomainItem, DomainStatus, Provider, RecordCreateRequest, RecordUpdateRequest};
+use crate::providers::http::{self, Call};
+use crate::providers::sts::{self, TemporaryCredentials};
+use crate::svcb;
+use crate::txt;
+use crate::types::{
+    DnsRecord, DomainItem, DomainStatus, Provider, RecordCreateRequest, RecordExtra, RecordStatus,
+    RecordUpdateRequest,
+};
 use chrono::{DateTime, NaiveDateTime, Utc};
 use hmac::{Hmac, Mac};
 use reqwest::header::{HeaderMap, HeaderValue};
 use serde_json::{Map, Value};
 use sha2::{Digest, Sha256};
-use std::time::Duration;
 
-const API_HOST: &str = "dnspod.tencentcloudapi.com";
-const API_ENDPOINT: &str = "https://dnspod.tencentcloudapi.com";
+pub const API_BASE: &str = "https://dnspod.tencentcloudapi.com";
 const API_VERSION: &str = "2021-03-23";
 const SERVICE: &str = "dnspod";
+const STS_BASE: &str = "https://sts.tencentcloudapi.com";
+const STS_VERSION: &str = "2018-08-13";
+const STS_REGION: &str = "ap-guangzhou";
+const RECORDS_PER_PAGE: u32 = 3000;
 
 type HmacSha256 = Hmac<Sha256>;
 
 pub struct TencentCloudClient {
     client: reqwest::Client,
+    base_url: String,
     secret_id: String,
     secret_key: String,
+    token: Option<String>,
 }
 
 impl TencentCloudClient {
     pub fn new(secret_id: String, secret_key: String) -> Result<Self, AppError> {
-        let client = reqwest::Client::builder()
-            .user_agent("LaoChenDNS/0.1.0")
-            .timeout(Duration::from_secs(30))
-            .build()
-            .map_err(AppError::from)?;
+        let client = http::client()?;
         Ok(Self {
             client,
+            base_url: API_BASE.to_string(),
             secret_id,
             secret_key,
+            token: None,
         })
     }
 
+    pub fn with_security_token(mut self, token: Option<String>) -> Self {
+        self.token = token.filter(|t| !t.trim().is_empty());
+        self
+    }
+
+    pub fn with_endpoint(mut self, endpoint: Option<&str>) -> Result<Self, AppError> {
+        self.base_url = http::endpoint_or(endpoint, API_BASE)?;
+        Ok(self)
+    }
+
+    pub async fn assume_role(&self, role_arn: &str) -> Result<TemporaryCredentials, AppError> {
+        let payload = serde_json::json!({
+            "RoleArn": role_arn,
+            "RoleSessionName": sts::ROLE_SESSION_NAME,
+            "DurationSeconds": sts::ROLE_DURATION_SECS,
+        });
+        let value = self
+            .call(STS_BASE, "sts", STS_VERSION, Some(STS_REGION), "AssumeRole", payload)
+            .await?;
+        let response = value.get("Response");
+        let credentials = response.and_then(|v| v.get("Credentials"));
+        let field = |key: &str| credentials.and_then(|c| extract_string(c, key));
+        let expires_at = response
+            .and_then(|v| v.get("ExpiredTime"))
+            .and_then(|v| v.as_i64())
+            .and_then(|ts| DateTime::<Utc>::from_timestamp(ts, 0));
+        match (field("TmpSecretId"), field("TmpSecretKey"), field("Token"), expires_at) {
+            (Some(access_key_id), Some(access_key_secret), Some(security_token), Some(expires_at)) => {
+                Ok(TemporaryCredentials {
+                    access_key_id,
+                    access_key_secret,
+                    security_token,
+                    expires_at,
+                })
+            }
+            _ => Err(AppError::new("json_decode_failed", "AssumeRole response is missing credentials")),
+        }
+    }
+
     pub async fn test(&self) -> Result<(), AppError> {
         let _ = self.list_domains().await?;
         Ok(())
@@ -65,24 +113,14 @@ impl TencentCloudClient {
                 status: DomainStatus::Ok,
                 records_count,
                 last_changed_at,
+                display_name: None,
             });
         }
         Ok(items)
     }
 
     pub async fn list_records(&self, domain_id: &str, domain_name: &str) -> Result<Vec<DnsRecord>, AppError> {
-        let mut payload = domain_selector(domain_id, domain_name)?;
-        payload.insert("Offset".to_string(), serde_json::json!(0));
-        payload.insert("Limit".to_string(), serde_json::json!(500));
-        let value = self
-            .request("DescribeRecordList", Value::Object(payload))
-            .await?;
-        let list = value
-            .get("Response")
-            .and_then(|v| v.get("RecordList"))
-            .and_then(|v| v.as_array())
-            .cloned()
-            .unwrap_or_default();
+        let list = http::paginate(|page| self.records_page(domain_id, domain_name, page)).await?;
         let mut records = Vec::new();
         for item in list {
             if let Some(record) = parse_record_item(&item, domain_name) {
@@ -92,6 +130,32 @@ impl TencentCloudClient {
         Ok(records)
     }
 
+    async fn records_page(&self, domain_id: &str, domain_name: &str, page: u32) -> Result<(Vec<Value>, bool), AppError> {
+        let mut payload = domain_selector(domain_id, domain_name)?;
+        payload.insert("Offset".to_string(), serde_json::json!((page - 1) * RECORDS_PER_PAGE));
+        payload.insert("Limit".to_string(), serde_json::json!(RECORDS_PER_PAGE));
+        let value = match self.request("DescribeRecordList", Value::Object(payload)).await {
+            Ok(value) => value,
+            // 没有记录时接口返回错误而不是空列表
+            Err(e) if e.provider_code() == Some("ResourceNotFound.NoDataOfRecord") => {
+                return Ok((Vec::new(), false));
+            }
+            Err(e) => return Err(e),
+        };
+        let response = value.get("Response");
+        let list = response
+            .and_then(|v| v.get("RecordList"))
+            .and_then(|v| v.as_array())
+            .cloned()
+            .unwrap_or_default();
+        let total = response
+            .and_then(|v| v.get("RecordCountInfo"))
+            .and_then(|v| v.get("TotalCount"))
+            .and_then(|v| v.as_u64());
+        let more = http::more_pages(page, RECORDS_PER_PAGE, list.len(), total);
+        Ok((list, more))
+    }
+
     pub async fn create_record(
         &self,
         domain_id: &str,
@@ -103,38 +167,43 @@ impl TencentCloudClient {
         payload.insert("SubDomain".to_string(), serde_json::json!(sub_domain));
         payload.insert("RecordType".to_string(), serde_json::json!(req.record_type));
         payload.insert("RecordLine".to_string(), serde_json::json!("默认"));
-        payload.insert(
-            "Value".to_string(),
-            serde_json::json!(tencent_value(
-                &req.record_type,
-                &req.content,
-                req.srv_priority,
-                req.srv_weight,
-                req.srv_port,
-                req.caa_flags,
-                req.caa_tag.as_deref(),
-            )),
+        let value = tencent_value(
+            &req.record_type,
+            &req.content,
+            req.srv_priority,
+            req.srv_weight,
+            req.srv_port,
+            req.caa_flags,
+            req.caa_tag.as_deref(),
+            &req.extra,
         );
+        payload.insert("Value".to_string(), serde_json::json!(value));
         payload.insert("TTL".to_string(), serde_json::json!(req.ttl));
+        if let Some(comment) = req.comment.as_deref() {
+            payload.insert("Remark".to_string(), serde_json::json!(comment));
+        }
+        if let Some(weight) = req.weight {
+            payload.insert("Weight".to_string(), serde_json::json!(weight));
+        }
         if req.record_type == "MX" {
             let mx = req
                 .mx_priority
                 .ok_or_else(|| AppError::new("invalid_input", "MX record requires mx priority"))?;
             payload.insert("MX".to_string(), serde_json::json!(mx));
         }
-        let value = self
+        let response = self
             .request("CreateRecord", Value::Object(payload))
             .await?;
-        parse_record_from_response(
-            &value,
-            domain_name,
-            &req.record_type,
-            &req.name,
-            &req.content,
-            req.ttl,
-            req.mx_priority,
-            None,
-        )
+        let written = WrittenRecord {
+            record_type: &req.record_type,
+            name: &req.name,
+            value: &value,
+            ttl: req.ttl,
+            mx_priority: req.mx_priority,
+            comment: req.comment.as_deref(),
+            weight: req.weight,
+        };
+        parse_record_from_response(&response, domain_name, &written, None)
     }
 
     pub async fn update_record(
@@ -144,89 +213,140 @@ impl TencentCloudClient {
         req: &RecordUpdateRequest,
     ) -> Result<DnsRecord, AppError> {
         let mut payload = domain_selector(domain_id, domain_name)?;
-        let record_id = parse_u64(&req.id).unwrap_or(0);
+        let record_id = parse_u64(&req.id).ok_or_else(|| AppError::new("invalid_input", "记录 ID 无效"))?;
         payload.insert("RecordId".to_string(), serde_json::json!(record_id));
         let sub_domain = if req.name.trim().is_empty() { "@" } else { req.name.as_str() };
         payload.insert("SubDomain".to_string(), serde_json::json!(sub_domain));
         payload.insert("RecordType".to_string(), serde_json::json!(req.record_type));
         payload.insert("RecordLine".to_string(), serde_json::json!("默认"));
-        payload.insert(
-            "Value".to_string(),
-            serde_json::json!(tencent_value(
-                &req.record_type,
-                &req.content,
-                req.srv_priority,
-                req.srv_weight,
-                req.srv_port,
-                req.caa_flags,
-                req.caa_tag.as_deref(),
-            )),
+        let value = tencent_value(
+            &req.record_type,
+            &req.content,
+            req.srv_priority,
+            req.srv_weight,
+            req.srv_port,
+            req.caa_flags,
+            req.caa_tag.as_deref(),
+            &req.extra,
         );
+        payload.insert("Value".to_string(), serde_json::json!(value));
         payload.insert("TTL".to_string(), serde_json::json!(req.ttl));
+        if let Some(comment) = req.comment.as_deref() {
+            payload.insert("Remark".to_string(), serde_json::json!(comment));
+        }
+        if let Some(weight) = req.weight {
+            payload.insert("Weight".to_string(), serde_json::json!(weight));
+        }
         if req.record_type == "MX" {
             let mx = req
                 .mx_priority
                 .ok_or_else(|| AppError::new("invalid_input", "MX record requires mx priority"))?;
             payload.insert("MX".to_string(), serde_json::json!(mx));
         }
-        let value = self
+        let response = self
             .request("ModifyRecord", Value::Object(payload))
             .await?;
-        parse_record_from_response(
-            &value,
-            domain_name,
-            &req.record_type,
-            &req.name,
-            &req.content,
-            req.ttl,
-            req.mx_priority,
-            Some(record_id),
-        )
+        let written = WrittenRecord {
+            record_type: &req.record_type,
+            name: &req.name,
+            value: &value,
+            ttl: req.ttl,
+            mx_priority: req.mx_priority,
+            comment: req.comment.as_deref(),
+            weight: req.weight,
+        };
+        parse_record_from_response(&response, domain_name, &written, Some(record_id))
     }
 
     pub async fn delete_record(&self, domain_id: &str, domain_name: &str, record_id: &str) -> Result<(), AppError> {
         let mut payload = domain_selector(domain_id, domain_name)?;
-        let record_id = parse_u64(record_id).unwrap_or(0);
+        let record_id = parse_u64(record_id).ok_or_else(|| AppError::new("invalid_input", "记录 ID 无效"))?;
         payload.insert("RecordId".to_string(), serde_json::json!(record_id));
         let _ = self.request("DeleteRecord", Value::Object(payload)).await?;
         Ok(())
     }
 
+    pub async fn set_record_status(
+        &self,
+        domain_id: &str,
+        domain_name: &str,
+        record_id: &str,
+        status: RecordStatus,
+    ) -> Result<(), AppError> {
+        let mut payload = domain_selector(domain_id, domain_name)?;
+        let record_id = parse_u64(record_id).ok_or_else(|| AppError::new("invalid_input", "记录 ID 无效"))?;
+        payload.insert("RecordId".to_string(), serde_json::json!(record_id));
+        let status = match status {
+            RecordStatus::Enabled => "ENABLE",
+            RecordStatus::Disabled => "DISABLE",
+        };
+        payload.insert("Status".to_string(), serde_json::json!(status));
+        let _ = self.request("ModifyRecordStatus", Value::Object(payload)).await?;
+        Ok(())
+    }
+
     async fn request(&self, action: &str, payload: Value) -> Result<Value, AppError> {
-        let now = Utc::now();
-        let timestamp = now.timestamp();
-        let date = now.format("%Y-%m-%d").to_string();
+        self.call(&self.base_url, SERVICE, API_VERSION, None, action, payload).await
+    }
+
+    async fn call(
+        &self,
+        endpoint: &str,
+        service: &str,
+        version: &str,
+        region: Option<&str>,
+        action: &str,
+        payload: Value,
+    ) -> Result<Value, AppError> {
+        http::retry(call_kind(action), || {
+            self.call_once(endpoint, service, version, region, action, payload.clone())
+        })
+        .await
+    }
+
+    async fn call_once(
+        &self,
+        endpoint: &str,
+        service: &str,
+        version: &str,
+        region: Option<&str>,
+        action: &str,
+        payload: Value,
+    ) -> Result<Value, AppError> {
+        let host = http::authority(endpoint)?;
+        let path = http::base_path(endpoint)?;
+        let path = if path.is_empty() { "/" } else { path.as_str() };
+        let timestamp = Utc::now().timestamp();
         let payload_str =
             serde_json::to_string(&payload).map_err(|e| AppError::new("serialize_error", e.to_string()))?;
-        let canonical_request = format!(
-            "POST\n/\n\ncontent-type:application/json; charset=utf-8\nhost:{API_HOST}\n\ncontent-type;host\n{}",
-            hex::encode(Sha256::digest(payload_str.as_bytes()))
-        );
-        let credential_scope = format!("{date}/{SERVICE}/tc3_request");
-        let string_to_sign = format!(
-            "TC3-HMAC-SHA256\n{timestamp}\n{credential_scope}\n{}",
-            hex::encode(Sha256::digest(canonical_request.as_bytes()))
-        );
-        let secret_date = hmac_sha256(format!("TC3{}", self.secret_key).as_bytes(), &date);
-        let secret_service = hmac_sha256(&secret_date, SERVICE);
-        let secret_signing = hmac_sha256(&secret_service, "tc3_request");
-        let signature = hex::encode(hmac_sha256(&secret_signing, &string_to_sign));
-        let authorization = format!(
-            "TC3-HMAC-SHA256 Credential={}/{}, SignedHeaders=content-type;host, Signature={}",
-            self.secret_id, credential_scope, signature
-        );
+        let authorization = sign_tc3(&self.secret_id, &self.secret_key, service, &host, path, &payload_str, timestamp);
 
         let mut headers = HeaderMap::new();
         headers.insert(
             "Content-Type",
             HeaderValue::from_static("application/json; charset=utf-8"),
         );
-        headers.insert("Host", HeaderValue::from_static(API_HOST));
+        headers.insert(
+            "Host",
+            HeaderValue::from_str(&host).map_err(|e| AppError::new("invalid_input", e.to_string()))?,
+        );
         headers.insert("X-TC-Action", HeaderValue::from_str(action).map_err(|e| AppError::new("invalid_input", e.to_string()))?);
         headers.insert(
             "X-TC-Version",
-            HeaderValue::from_static(API_VERSION),
+            HeaderValue::from_str(version).map_err(|e| AppError::new("invalid_input", e.to_string()))?,
         );
+        if let Some(region) = region {
+            headers.insert(
+                "X-TC-Region",
+                HeaderValue::from_str(region).map_err(|e| AppError::new("invalid_input", e.to_string()))?,
+            );
+        }
+        if let Some(token) = &self.token {
+            headers.insert(
+                "X-TC-Token",
+                HeaderValue::from_str(token).map_err(|e| AppError::new("invalid_input", e.to_string()))?,
+            );
+        }
         headers.insert(
             "X-TC-Timestamp",
             HeaderValue::from_str(&timestamp.to_string()).map_err(|e| AppError::new("invalid_input", e.to_string()))?,
@@ -236,21 +356,26 @@ impl TencentCloudClient {
             HeaderValue::from_str(&authorization).map_err(|e| AppError::new("invalid_input", e.to_string()))?,
         );
 
-        let res = self
-            .client
-            .post(API_ENDPOINT)
-            .headers(headers)
-            .body(payload_str)
-            .send()
-            .await
-            .map_err(AppError::from)?;
+        let res = http::send(Provider::Tencentcloud, call_kind(action), || {
+            Ok(self.client.post(endpoint).headers(headers.clone()).body(payload_str.clone()))
+        })
+        .await?;
         let status = res.status();
         let text = res.text().await.unwrap_or_default();
         if !status.is_success() {
-            return Err(AppError::new("fetch_failed", format!("HTTP {}: {}", status.as_u16(), text)));
+            return Err(AppError::http(
+                "fetch_failed",
+                status.as_u16(),
+                format!("HTTP {}: {}", status.as_u16(), text),
+            ));
         }
         let value: Value =
             serde_json::from_str(&text).map_err(|e| AppError::new("json_decode_failed", e.to_string()))?;
+        let request_id = value
+            .get("Response")
+            .and_then(|v| v.get("RequestId"))
+            .and_then(|v| v.as_str())
+            .map(|v| v.to_string());
         if let Some(error) = value.get("Response").and_then(|v| v.get("Error")) {
             let code = error
                 .get("Code")
@@ -265,12 +390,56 @@ impl TencentCloudClient {
             } else {
                 "fetch_failed"
             };
-            return Err(AppError::new(err_code, message));
+            // 腾讯云业务错误也返回 HTTP 200，限流和内部错误可以重试
+            let retryable = code.starts_with("RequestLimitExceeded") || code.starts_with("InternalError");
+            return Err(AppError::new(err_code, message)
+                .with_provider_code(code)
+                .with_request_id(request_id)
+                .with_retryable(retryable));
         }
         Ok(value)
     }
 }
 
+fn call_kind(action: &str) -> Call {
+    if action.starts_with("Describe") {
+        Call::Read
+    } else {
+        Call::Write
+    }
+}
+
+/// TC3-HMAC-SHA256 签名，返回 Authorization 头的值。只签 content-type 和 host 两个头，
+/// `path` 是实际请求的路径，`payload` 必须和实际发送的请求体逐字节一致。
+fn sign_tc3(
+    secret_id: &str,
+    secret_key: &str,
+    service: &str,
+    host: &str,
+    path: &str,
+    payload: &str,
+    timestamp: i64,
+) -> String {
+    let date = DateTime::<Utc>::from_timestamp(timestamp, 0)
+        .unwrap_or_default()
+        .format("%Y-%m-%d")
+        .to_string();
+    let canonical_request = format!(
+        "POST\n{path}\n\ncontent-type:application/json; charset=utf-8\nhost:{host}\n\ncontent-type;host\n{}",
+        hex::encode(Sha256::digest(payload.as_bytes()))
+    );
+    let credential_scope = format!("{date}/{service}/tc3_request");
+    let string_to_sign = format!(
+        "TC3-HMAC-SHA256\n{timestamp}\n{credential_scope}\n{}",
+        hex::encode(Sha256::digest(canonical_request.as_bytes()))
+    );
+    let secret_date = hmac_sha256(format!("TC3{secret_key}").as_bytes(), &date);
+    let secret_service = hmac_sha256(&secret_date, service);
+    let secret_signing = hmac_sha256(&secret_service, "tc3_request");
+    let signature = hex::encode(hmac_sha256(&secret_signing, &string_to_sign));
+    format!("TC3-HMAC-SHA256 Credential={secret_id}/{credential_scope}, SignedHeaders=content-type;host, Signature={signature}")
+}
+
 fn hmac_sha256(key: &[u8], msg: &str) -> Vec<u8> {
     let mut mac = HmacSha256::new_from_slice(key).expect("hmac can take key of any size");
     mac.update(msg.as_bytes());
@@ -330,9 +499,13 @@ fn parse_record_item(value: &Value, domain_name: &str) -> Option<DnsRecord> {
     let raw_value = extract_string(value, "Value").unwrap_or_default();
     let ttl = extract_u32(value, "TTL").unwrap_or(600);
     let mx_priority = extract_u16(value, "MX");
+    let status = match extract_string(value, "Status").as_deref() {
+        Some("DISABLE") => RecordStatus::Disabled,
+        _ => RecordStatus::Enabled,
+    };
     let (content, srv_priority, srv_weight, srv_port, caa_flags, caa_tag) =
         parse_record_value(&record_type, &raw_value);
-    Some(DnsRecord {
+    let record = DnsRecord {
         id,
         provider: Provider::Tencentcloud,
         domain: domain_name.to_string(),
@@ -346,17 +519,31 @@ fn parse_record_item(value: &Value, domain_name: &str) -> Option<DnsRecord> {
         srv_port,
         caa_flags,
         caa_tag,
-    })
+        status,
+        comment: extract_string(value, "Remark").filter(|r| !r.is_empty()),
+        tags: Vec::new(),
+        weight: extract_u16(value, "Weight"),
+        display_name: None,
+        extra: RecordExtra::default(),
+    };
+    Some(svcb::split_record(record))
+}
+
+/// 新建和修改时写出去的字段，接口只返回 RecordId，其余字段按写入的值还原。
+struct WrittenRecord<'a> {
+    record_type: &'a str,
+    name: &'a str,
+    value: &'a str,
+    ttl: u32,
+    mx_priority: Option<u16>,
+    comment: Option<&'a str>,
+    weight: Option<u16>,
 }
 
 fn parse_record_from_response(
     value: &Value,
     domain_name: &str,
-    record_type: &str,
-    name: &str,
-    content_raw: &str,
-    ttl: u32,
-    mx_priority: Option<u16>,
+    written: &WrittenRecord,
     record_id: Option<u64>,
 ) -> Result<DnsRecord, AppError> {
     let record_id = value
@@ -367,22 +554,29 @@ fn parse_record_from_response(
         .unwrap_or(0)
         .to_string();
     let (content, srv_priority, srv_weight, srv_port, caa_flags, caa_tag) =
-        parse_record_value(record_type, content_raw);
-    Ok(DnsRecord {
+        parse_record_value(written.record_type, written.value);
+    let record = DnsRecord {
         id: record_id,
         provider: Provider::Tencentcloud,
         domain: domain_name.to_string(),
-        record_type: record_type.to_string(),
-        name: name.to_string(),
+        record_type: written.record_type.to_string(),
+        name: written.name.to_string(),
         content,
-        ttl,
-        mx_priority,
+        ttl: written.ttl,
+        mx_priority: written.mx_priority,
         srv_priority,
         srv_weight,
         srv_port,
         caa_flags,
         caa_tag,
-    })
+        status: RecordStatus::Enabled,
+        comment: written.comment.filter(|c| !c.is_empty()).map(|c| c.to_string()),
+        tags: Vec::new(),
+        weight: written.weight,
+        display_name: None,
+        extra: RecordExtra::default(),
+    };
+    Ok(svcb::split_record(record))
 }
 
 fn parse_record_value(
@@ -413,10 +607,12 @@ fn parse_record_value(
                 (value.to_string(), None, None, None, None, None)
             }
         }
+        "TXT" => (txt::decode(value), None, None, None, None, None),
         _ => (value.to_string(), None, None, None, None, None),
     }
 }
 
+#[allow(clippy::too_many_arguments)]
 fn tencent_value(
     record_type: &str,
     content: &str,
@@ -425,8 +621,10 @@ fn tencent_value(
     srv_port: Option<u16>,
     caa_flags: Option<u8>,
     caa_tag: Option<&str>,
+    extra: &RecordExtra,
 ) -> String {
     match record_type {
+        "HTTPS" | "SVCB" => svcb::presentation(content, extra),
         "SRV" => format!(
             "{} {} {} {}",
             srv_priority.unwrap_or(0),
@@ -469,3 +667,162 @@ fn domain_selector(domain_id: &str, domain_name: &str) -> Result<Map<String, Val
     }
     Ok(payload)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::providers::mock::{self, create_request, update_request, MockServer};
+    use serde_json::json;
+
+    // 腾讯云 API 3.0 签名方法 v3 文档中的示例请求
+    #[test]
+    fn signs_request_like_documented_example() {
+        let payload = r#"{"Limit": 1, "Filters": [{"Values": ["\u672a\u547d\u540d"], "Name": "instance-name"}]}"#;
+
+        let authorization = sign_tc3(
+            "AKIDz8krbsJ5yKBZQpn74WFkmLPx3EXAMPLE",
+            "Gu5t9xGARNpq86cd98joQYCN3EXAMPLE",
+            "cvm",
+            "cvm.tencentcloudapi.com",
+            "/",
+            payload,
+            1551113065,
+        );
+
+        assert_eq!(
+            authorization,
+            "TC3-HMAC-SHA256 Credential=AKIDz8krbsJ5yKBZQpn74WFkmLPx3EXAMPLE/2019-02-25/cvm/tc3_request, \
+             SignedHeaders=content-type;host, Signature=72e494ea809ad7a8c8f7a4507b9bddcbaa8e581f516e8da2f66e2c5a96525168"
+        );
+    }
+
+    fn client(server: &MockServer) -> TencentCloudClient {
+        server.client(|endpoint| TencentCloudClient::new("AKIDtest".to_string(), "testkey".to_string())?.with_endpoint(endpoint))
+    }
+
+    fn action(req: &crate::providers::mock::Request) -> String {
+        req.header("x-tc-action").unwrap_or_default().to_string()
+    }
+
+    #[tokio::test]
+    async fn lists_records_across_pages() {
+        let server = MockServer::start(|req| {
+            let records = mock::two_pages(
+                req.param("Offset").as_deref() == Some("0"),
+                RECORDS_PER_PAGE,
+                |i| json!({ "RecordId": i, "Name": "www", "Type": "A", "Value": "192.0.2.1", "TTL": 600, "Status": "ENABLE" }),
+                vec![
+                    json!({ "RecordId": 9001, "Name": "_sip._tcp", "Type": "SRV", "Value": "10 20 5060 sip.example.com.", "TTL": 600, "Status": "DISABLE" }),
+                    json!({ "RecordId": 9002, "Name": "@", "Type": "MX", "Value": "mx.example.com.", "TTL": 600, "MX": 10, "Weight": 5, "Remark": "mail" }),
+                ],
+            );
+            (
+                200,
+                json!({ "Response": { "RecordCountInfo": { "TotalCount": 3002 }, "RecordList": records, "RequestId": "r" } }),
+            )
+        })
+        .await;
+
+        let records = client(&server).list_records("42", "example.com").await.unwrap();
+
+        assert_eq!(server.count(|r| action(r) == "DescribeRecordList"), 2);
+        assert_eq!(records.len(), 3002);
+        let srv = &records[3000];
+        assert_eq!((srv.srv_priority, srv.srv_weight, srv.srv_port), (Some(10), Some(20), Some(5060)));
+        assert_eq!(srv.status, RecordStatus::Disabled);
+        let mx = &records[3001];
+        assert_eq!((mx.mx_priority, mx.weight, mx.comment.as_deref()), (Some(10), Some(5), Some("mail")));
+    }
+
+    #[tokio::test]
+    async fn domain_without_records_lists_nothing() {
+        let server = MockServer::start(|_| {
+            (
+                200,
+                json!({ "Response": { "Error": { "Code": "ResourceNotFound.NoDataOfRecord", "Message": "记录列表为空。" }, "RequestId": "r" } }),
+            )
+        })
+        .await;
+
+        let records = client(&server).list_records("42", "example.com").await.unwrap();
+
+        assert!(records.is_empty());
+    }
+
+    #[tokio::test]
+    async fn writes_keep_structured_fields() {
+        let server = MockServer::start(|_| (200, json!({ "Response": { "RecordId": 9001, "RequestId": "r" } }))).await;
+        let client = client(&server);
+
+        let mut req = create_request("SRV", "_sip._tcp", "sip.example.com");
+        req.srv_priority = Some(10);
+        req.srv_weight = Some(20);
+        req.srv_port = Some(5060);
+        let created = client.create_record("42", "example.com", &req).await.unwrap();
+        assert_eq!(created.id, "9001");
+        assert_eq!((created.srv_priority, created.srv_port), (Some(10), Some(5060)));
+        assert_eq!(created.content, "sip.example.com");
+
+        let mut update = update_request("9001", "CAA", "@", "letsencrypt.org");
+        update.caa_flags = Some(0);
+        update.caa_tag = Some("issue".to_string());
+        let updated = client.update_record("42", "example.com", &update).await.unwrap();
+        assert_eq!(updated.caa_tag.as_deref(), Some("issue"));
+        assert_eq!(updated.content, "letsencrypt.org");
+        client.set_record_status("42", "example.com", "9001", RecordStatus::Enabled).await.unwrap();
+        client.delete_record("42", "example.com", "9001").await.unwrap();
+
+        let requests = server.requests();
+        let actions: Vec<String> = requests.iter().map(action).collect();
+        assert_eq!(actions, ["CreateRecord", "ModifyRecord", "ModifyRecordStatus", "DeleteRecord"]);
+        assert_eq!(requests[0].json()["Value"], "10 20 5060 sip.example.com");
+        assert_eq!(requests[0].json()["DomainId"], 42);
+        assert_eq!(requests[1].json()["Value"], "0 issue letsencrypt.org");
+        assert_eq!(requests[2].json()["Status"], "ENABLE");
+        assert!(requests[0].header("authorization").unwrap().starts_with("TC3-HMAC-SHA256 Credential=AKIDtest/"));
+    }
+
+    #[tokio::test]
+    async fn https_records_use_presentation_values() {
+        let server = MockServer::start(|_| (200, json!({ "Response": { "RecordId": 9003, "RequestId": "r" } }))).await;
+
+        let mut req = create_request("HTTPS", "@", ".");
+        req.extra.svc_priority = Some(1);
+        req.extra.svc_params = Some(crate::svcb::parse_params("alpn=h3,h2 port=443").unwrap());
+        let created = client(&server).create_record("42", "example.com", &req).await.unwrap();
+
+        assert_eq!(server.requests()[0].json()["Value"], "1 . alpn=\"h3,h2\" port=443");
+        assert_eq!(created.content, ".");
+        assert_eq!(created.extra, req.extra);
+    }
+
+    #[tokio::test]
+    async fn signature_failure_is_an_auth_failure() {
+        let server = MockServer::start(|_| {
+            (
+                200,
+                json!({ "Response": { "Error": { "Code": "AuthFailure.SignatureFailure", "Message": "The provided credentials could not be validated." }, "RequestId": "req-3" } }),
+            )
+        })
+        .await;
+
+        let err = client(&server).list_domains().await.unwrap_err();
+
+        assert_eq!(err.code, "auth_failed");
+        assert_eq!(err.provider_code(), Some("AuthFailure.SignatureFailure"));
+        assert_eq!(err.request_id(), Some("req-3"));
+    }
+
+    #[tokio::test]
+    async fn malformed_record_id_is_rejected_before_calling() {
+        let server = MockServer::start(|_| (200, json!({ "Response": { "RequestId": "req-4" } }))).await;
+
+        let err = client(&server)
+            .set_record_status("1", "example.com", "not-a-number", RecordStatus::Disabled)
+            .await
+            .unwrap_err();
+
+        assert_eq!(err.code, "invalid_input");
+        assert!(server.requests().is_empty());
+    }
+}
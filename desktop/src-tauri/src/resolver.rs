@@ -0,0 +1,83 @@
+//! 通过 DNS-over-HTTPS 的 JSON 接口（`application/dns-json`）查询公网解析结果，
+//! 走和服务商接口相同的代理与证书设置。
+
+use crate::error::AppError;
+use crate::providers::http;
+use crate::txt;
+use reqwest::Client;
+use serde::Deserialize;
+
+pub const DEFAULT_RESOLVER: &str = "https://dns.alidns.com/resolve";
+
+pub const TYPE_CNAME: u16 = 5;
+const TYPE_TXT: u16 = 16;
+
+const RCODE_NXDOMAIN: u16 = 3;
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct Answer {
+    #[serde(rename = "type")]
+    pub record_type: u16,
+    pub data: String,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct Lookup {
+    pub nxdomain: bool,
+    pub answers: Vec<Answer>,
+}
+
+#[derive(Deserialize)]
+struct Response {
+    #[serde(rename = "Status")]
+    status: u16,
+    #[serde(rename = "Answer", default)]
+    answer: Vec<Answer>,
+}
+
+pub struct Resolver {
+    client: Client,
+    url: String,
+}
+
+impl Resolver {
+    pub fn new(url: Option<&str>) -> Result<Self, AppError> {
+        Ok(Self {
+            client: http::client()?,
+            url: http::endpoint_or(url, DEFAULT_RESOLVER)?,
+        })
+    }
+
+    pub async fn lookup(&self, name: &str, record_type: &str) -> Result<Lookup, AppError> {
+        let res = self
+            .client
+            .get(&self.url)
+            .query(&[("name", name), ("type", record_type)])
+            .header("accept", "application/dns-json")
+            .send()
+            .await?;
+        let status = res.status();
+        if !status.is_success() {
+            return Err(AppError::http("resolve_failed", status.as_u16(), format!("Resolver returned HTTP {status}")));
+        }
+        let body: Response = res
+            .json()
+            .await
+            .map_err(|e| AppError::new("resolve_failed", format!("Invalid resolver response: {e}")))?;
+        Ok(Lookup {
+            nxdomain: body.status == RCODE_NXDOMAIN,
+            answers: body.answer,
+        })
+    }
+
+    /// 查询名称下的 TXT 记录，返回解码后的逻辑值。
+    pub async fn txt(&self, name: &str) -> Result<Vec<String>, AppError> {
+        let lookup = self.lookup(name, "TXT").await?;
+        Ok(lookup
+            .answers
+            .iter()
+            .filter(|a| a.record_type == TYPE_TXT)
+            .map(|a| txt::decode(&a.data))
+            .collect())
+    }
+}
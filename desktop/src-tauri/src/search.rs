@@ -0,0 +1,185 @@
+//! 跨服务商、跨域名搜索解析记录。各域名的记录列表在内存里缓存一段时间，
+//! 改动记录后按域名清掉对应的缓存。
+
+use crate::error::AppError;
+use crate::types::{DnsRecord, Provider, RecordSearchQuery};
+use crate::vault::PlainVault;
+use regex::{Regex, RegexBuilder};
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
+use std::sync::{Mutex, OnceLock};
+use std::time::{Duration, Instant};
+
+/// 同时拉取记录的域名数，各服务商自身的频率限制仍由 http 模块控制
+pub const SEARCH_CONCURRENCY: usize = 4;
+
+const CACHE_TTL: Duration = Duration::from_secs(300);
+
+// (保险库, 服务商, 域名 ID)
+type Key = (u64, Provider, String);
+type Cache = Mutex<HashMap<Key, (Instant, Vec<DnsRecord>)>>;
+
+fn cache() -> &'static Cache {
+    static CACHE: OnceLock<Cache> = OnceLock::new();
+    CACHE.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+// 按保险库内容区分缓存，换了凭据或接口地址后不会读到之前账号的记录
+fn key(plain: &PlainVault, provider: Provider, domain_id: &str) -> Key {
+    let mut hasher = DefaultHasher::new();
+    serde_json::to_string(plain).unwrap_or_default().hash(&mut hasher);
+    (hasher.finish(), provider, domain_id.to_string())
+}
+
+pub fn cached(plain: &PlainVault, provider: Provider, domain_id: &str) -> Option<Vec<DnsRecord>> {
+    let cache = cache().lock().unwrap_or_else(|e| e.into_inner());
+    let (stored_at, records) = cache.get(&key(plain, provider, domain_id))?;
+    (stored_at.elapsed() < CACHE_TTL).then(|| records.clone())
+}
+
+pub fn store(plain: &PlainVault, provider: Provider, domain_id: &str, records: Vec<DnsRecord>) {
+    let mut cache = cache().lock().unwrap_or_else(|e| e.into_inner());
+    cache.insert(key(plain, provider, domain_id), (Instant::now(), records));
+}
+
+/// 写操作开始时取得，离开作用域时清掉该域名的缓存。放在写之后清，
+/// 避免写的过程中有搜索把旧记录重新放进缓存；写失败或提前返回时同样会清。
+pub struct Invalidation(Key);
+
+pub fn invalidate_on_exit(plain: &PlainVault, provider: Provider, domain_id: &str) -> Invalidation {
+    Invalidation(key(plain, provider, domain_id))
+}
+
+impl Drop for Invalidation {
+    fn drop(&mut self) {
+        let mut cache = cache().lock().unwrap_or_else(|e| e.into_inner());
+        cache.remove(&self.0);
+    }
+}
+
+enum Pattern {
+    Text(String),
+    Regex(Regex),
+}
+
+impl Pattern {
+    fn new(value: Option<&str>, regex: bool, field: &str) -> Result<Option<Self>, AppError> {
+        let Some(value) = value.map(str::trim).filter(|v| !v.is_empty()) else {
+            return Ok(None);
+        };
+        if !regex {
+            return Ok(Some(Pattern::Text(value.to_lowercase())));
+        }
+        RegexBuilder::new(value)
+            .case_insensitive(true)
+            .build()
+            .map(|re| Some(Pattern::Regex(re)))
+            .map_err(|e| AppError::new("invalid_input", format!("{field}正则表达式无效: {e}")))
+    }
+
+    fn matches(&self, value: &str) -> bool {
+        match self {
+            Pattern::Text(text) => value.to_lowercase().contains(text),
+            Pattern::Regex(re) => re.is_match(value),
+        }
+    }
+}
+
+pub struct Matcher {
+    domain: Option<String>,
+    name: Option<Pattern>,
+    content: Option<Pattern>,
+    record_type: Option<String>,
+}
+
+impl Matcher {
+    pub fn new(query: &RecordSearchQuery) -> Result<Self, AppError> {
+        let matcher = Self {
+            domain: query
+                .domain
+                .as_deref()
+                .map(|d| d.trim().to_lowercase())
+                .filter(|d| !d.is_empty()),
+            name: Pattern::new(query.name.as_deref(), query.regex, "主机记录")?,
+            content: Pattern::new(query.content.as_deref(), query.regex, "记录值")?,
+            record_type: query
+                .record_type
+                .as_deref()
+                .map(|t| t.trim().to_ascii_uppercase())
+                .filter(|t| !t.is_empty()),
+        };
+        if matcher.name.is_none() && matcher.content.is_none() && matcher.record_type.is_none() {
+            return Err(AppError::new("invalid_input", "主机记录、记录值和类型至少填写一项"));
+        }
+        Ok(matcher)
+    }
+
+    /// `name` 是 ASCII 形式的域名，`display_name` 是中文域名的 Unicode 形式。
+    pub fn matches_domain(&self, name: &str, display_name: Option<&str>) -> bool {
+        match &self.domain {
+            Some(domain) => {
+                name.to_lowercase().contains(domain)
+                    || display_name.is_some_and(|n| n.to_lowercase().contains(domain))
+            }
+            None => true,
+        }
+    }
+
+    /// 主机记录同时匹配相对名称、中文显示名和完整域名。
+    pub fn matches(&self, domain_name: &str, record: &DnsRecord) -> bool {
+        if self.record_type.as_deref().is_some_and(|t| t != record.record_type) {
+            return false;
+        }
+        if let Some(name) = &self.name {
+            let fqdn = if record.name == "@" {
+                domain_name.to_string()
+            } else {
+                format!("{}.{domain_name}", record.name)
+            };
+            let candidates = [Some(record.name.as_str()), record.display_name.as_deref(), Some(fqdn.as_str())];
+            if !candidates.into_iter().flatten().any(|c| name.matches(c)) {
+                return false;
+            }
+        }
+        self.content.as_ref().is_none_or(|content| content.matches(&record.content))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::providers::mock::record;
+
+    fn query(name: Option<&str>, content: Option<&str>, record_type: Option<&str>, regex: bool) -> RecordSearchQuery {
+        RecordSearchQuery {
+            name: name.map(str::to_string),
+            content: content.map(str::to_string),
+            record_type: record_type.map(str::to_string),
+            regex,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn matches_text_and_regex_queries() {
+        let www = record("1", "A", "www", "1.2.3.4");
+        let apex = record("1", "A", "@", "1.2.3.40");
+
+        let by_content = Matcher::new(&query(None, Some("1.2.3.4"), None, false)).unwrap();
+        assert!(by_content.matches("example.com", &www) && by_content.matches("example.com", &apex));
+
+        let exact = Matcher::new(&query(None, Some(r"^1\.2\.3\.4$"), Some("a"), true)).unwrap();
+        assert!(exact.matches("example.com", &www));
+        assert!(!exact.matches("example.com", &apex));
+
+        let by_fqdn = Matcher::new(&query(Some("WWW.example.com"), None, None, false)).unwrap();
+        assert!(by_fqdn.matches("example.com", &www));
+        let apex_only = Matcher::new(&query(Some("^example\\.com$"), None, Some("A"), true)).unwrap();
+        assert!(apex_only.matches("example.com", &apex) && !apex_only.matches("example.com", &www));
+
+        assert!(!Matcher::new(&query(None, Some("1.2.3.4"), Some("AAAA"), false)).unwrap().matches("example.com", &www));
+        assert_eq!(Matcher::new(&query(None, Some("(unclosed"), None, true)).err().unwrap().code, "invalid_input");
+        assert!(Matcher::new(&query(None, Some("  "), None, false)).is_err());
+    }
+}
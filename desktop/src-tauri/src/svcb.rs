@@ -0,0 +1,229 @@
+//! HTTPS/SVCB 记录（RFC 9460）在表示格式和结构化字段之间的转换。
+//! 多数服务商只收一整串 `优先级 目标 参数...`，Cloudflare 把参数串单独放在 data.value 里。
+
+use crate::error::AppError;
+use crate::types::{DnsRecord, RecordExtra, SvcParams};
+use std::net::{Ipv4Addr, Ipv6Addr};
+
+pub fn is_svcb(record_type: &str) -> bool {
+    matches!(record_type, "HTTPS" | "SVCB")
+}
+
+/// 解析 `alpn="h3,h2" port=443` 这样的参数串。
+pub fn parse_params(text: &str) -> Result<SvcParams, AppError> {
+    let mut params = SvcParams::default();
+    for item in tokens(text) {
+        let (key, value) = item.split_once('=').unwrap_or((item.as_str(), ""));
+        let value = value.trim_matches('"');
+        let list = || {
+            value
+                .split(',')
+                .map(|v| v.trim().to_string())
+                .filter(|v| !v.is_empty())
+                .collect::<Vec<_>>()
+        };
+        match key.to_ascii_lowercase().as_str() {
+            "alpn" => params.alpn = list(),
+            "port" => {
+                let port = value.parse().map_err(|_| invalid("port 必须是 0-65535 之间的整数"))?;
+                params.port = Some(port);
+            }
+            "ipv4hint" => params.ipv4hint = list(),
+            "ipv6hint" => params.ipv6hint = list(),
+            "ech" => params.ech = Some(value.to_string()),
+            other => {
+                if key_number(other).is_none() {
+                    return Err(invalid(format!("无法识别的 SvcParam: {other}")));
+                }
+                params.other.push(item.clone());
+            }
+        }
+    }
+    Ok(params)
+}
+
+/// 按 SvcParamKey 编号顺序输出参数串，没有参数时为空串。
+pub fn format_params(params: &SvcParams) -> String {
+    let mut out = Vec::new();
+    if !params.alpn.is_empty() {
+        out.push((1, format!("alpn=\"{}\"", params.alpn.join(","))));
+    }
+    if let Some(port) = params.port {
+        out.push((3, format!("port={port}")));
+    }
+    if !params.ipv4hint.is_empty() {
+        out.push((4, format!("ipv4hint={}", params.ipv4hint.join(","))));
+    }
+    if let Some(ech) = params.ech.as_deref() {
+        out.push((5, format!("ech={ech}")));
+    }
+    if !params.ipv6hint.is_empty() {
+        out.push((6, format!("ipv6hint={}", params.ipv6hint.join(","))));
+    }
+    for item in &params.other {
+        let key = item.split_once('=').map_or(item.as_str(), |(key, _)| key);
+        out.push((key_number(&key.to_ascii_lowercase()).unwrap_or(u32::MAX), item.clone()));
+    }
+    out.sort_by_key(|(number, _)| *number);
+    out.into_iter().map(|(_, item)| item).collect::<Vec<_>>().join(" ")
+}
+
+// SvcParamKey 编号，认不出的名字返回 None
+fn key_number(key: &str) -> Option<u32> {
+    match key {
+        "mandatory" => Some(0),
+        "alpn" => Some(1),
+        "no-default-alpn" => Some(2),
+        "port" => Some(3),
+        "ipv4hint" => Some(4),
+        "ech" => Some(5),
+        "ipv6hint" => Some(6),
+        "dohpath" => Some(7),
+        "ohttp" => Some(8),
+        other => other.strip_prefix("key")?.parse().ok(),
+    }
+}
+
+/// 解析完整的记录值，返回优先级、TargetName 和参数。
+pub fn parse(value: &str) -> Result<(u16, String, SvcParams), AppError> {
+    let value = value.trim();
+    let (priority, rest) = value.split_once(char::is_whitespace).unwrap_or((value, ""));
+    let priority = priority
+        .parse::<u16>()
+        .map_err(|_| invalid("HTTPS/SVCB 记录值需为 \"优先级 目标 参数\" 形式"))?;
+    let rest = rest.trim_start();
+    let (target, params) = rest.split_once(char::is_whitespace).unwrap_or((rest, ""));
+    if target.is_empty() {
+        return Err(invalid("HTTPS/SVCB 记录缺少目标域名"));
+    }
+    Ok((priority, target.to_string(), parse_params(params)?))
+}
+
+/// 由 `content`（TargetName）和附加字段拼出服务商要的完整记录值。
+pub fn presentation(content: &str, extra: &RecordExtra) -> String {
+    let params = extra.svc_params.as_ref().map(format_params).unwrap_or_default();
+    let head = format!("{} {}", extra.svc_priority.unwrap_or(1), content.trim());
+    if params.is_empty() {
+        head
+    } else {
+        format!("{head} {params}")
+    }
+}
+
+/// 把服务商返回的完整记录值拆进结构化字段，解析不了的保持原样。
+pub fn split_record(mut record: DnsRecord) -> DnsRecord {
+    if is_svcb(&record.record_type) {
+        if let Ok((priority, target, params)) = parse(&record.content) {
+            record.content = target;
+            record.extra.svc_priority = Some(priority);
+            record.extra.svc_params = Some(params);
+        }
+    }
+    record
+}
+
+pub fn validate_params(params: &SvcParams) -> Result<(), AppError> {
+    if params
+        .alpn
+        .iter()
+        .any(|id| id.chars().any(|c| c.is_whitespace() || c == '"' || c == '\\'))
+    {
+        return Err(invalid("alpn 不能包含空白、引号或反斜杠"));
+    }
+    for ip in &params.ipv4hint {
+        if ip.parse::<Ipv4Addr>().is_err() {
+            return Err(invalid(format!("ipv4hint 不是有效的 IPv4 地址: {ip}")));
+        }
+    }
+    for ip in &params.ipv6hint {
+        if ip.parse::<Ipv6Addr>().is_err() {
+            return Err(invalid(format!("ipv6hint 不是有效的 IPv6 地址: {ip}")));
+        }
+    }
+    if let Some(ech) = params.ech.as_deref() {
+        if ech.is_empty() || !ech.chars().all(|c| c.is_ascii_alphanumeric() || matches!(c, '+' | '/' | '=')) {
+            return Err(invalid("ech 必须是 Base64 编码的 ECHConfigList"));
+        }
+    }
+    Ok(())
+}
+
+// 按空白切分，引号里的空白不算
+fn tokens(text: &str) -> Vec<String> {
+    let mut out = Vec::new();
+    let mut current = String::new();
+    let mut quoted = false;
+    for c in text.chars() {
+        match c {
+            '"' => {
+                quoted = !quoted;
+                current.push(c);
+            }
+            c if c.is_whitespace() && !quoted => {
+                if !current.is_empty() {
+                    out.push(std::mem::take(&mut current));
+                }
+            }
+            c => current.push(c),
+        }
+    }
+    if !current.is_empty() {
+        out.push(current);
+    }
+    out
+}
+
+fn invalid(message: impl Into<String>) -> AppError {
+    AppError::new("invalid_content", message)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_and_formats_presentation_values() {
+        let (priority, target, params) =
+            parse("1 . alpn=\"h3, h2\" port=8443 ipv4hint=192.0.2.1,192.0.2.2 ech=AEX+/w==").unwrap();
+        assert_eq!((priority, target.as_str()), (1, "."));
+        assert_eq!(params.alpn, vec!["h3", "h2"]);
+        assert_eq!(params.port, Some(8443));
+        assert_eq!(params.ipv4hint.len(), 2);
+        assert_eq!(params.ech.as_deref(), Some("AEX+/w=="));
+
+        let extra = RecordExtra {
+            svc_priority: Some(priority),
+            svc_params: Some(params),
+            ..Default::default()
+        };
+        let value = presentation(&target, &extra);
+        assert_eq!(value, "1 . alpn=\"h3,h2\" port=8443 ipv4hint=192.0.2.1,192.0.2.2 ech=AEX+/w==");
+        assert_eq!(parse(&value).unwrap().2, extra.svc_params.unwrap());
+
+        assert_eq!(parse("0 cdn.example.net.").unwrap().1, "cdn.example.net.");
+    }
+
+    #[test]
+    fn keeps_unknown_params_verbatim() {
+        let params = parse_params("key65000=abc port=443 mandatory=alpn,port no-default-alpn alpn=h2").unwrap();
+        assert_eq!(params.other, vec!["key65000=abc", "mandatory=alpn,port", "no-default-alpn"]);
+        assert_eq!(
+            format_params(&params),
+            "mandatory=alpn,port alpn=\"h2\" no-default-alpn port=443 key65000=abc"
+        );
+        let reparsed = parse_params(&format_params(&params)).unwrap();
+        assert_eq!(format_params(&reparsed), format_params(&params));
+    }
+
+    #[test]
+    fn rejects_bad_values() {
+        assert!(parse("high . alpn=h2").is_err());
+        assert!(parse("1").is_err());
+        assert!(parse("1 . foo=bar").is_err());
+        assert!(parse("1 . port=99999").is_err());
+
+        let params = parse_params("ipv6hint=2001:db8::1 ipv4hint=2001:db8::1").unwrap();
+        assert!(validate_params(&params).is_err());
+        assert!(validate_params(&parse_params("ech=not-base64!").unwrap()).is_err());
+    }
+}
@@ -0,0 +1,108 @@
+//! TXT 记录值在逻辑值和 `"..." "..."` 字符串序列之间的转换。
+//! 界面和命令里一律用逻辑值；服务商返回带引号的形式时读回来先解码，
+//! 要求带引号的服务商写入前再按 255 字节分段编码。
+
+const MAX_STRING_LEN: usize = 255;
+
+/// 解码 `"part1" "part2"` 形式的记录值并拼接各段，不是这种形式的原样返回。
+pub fn decode(raw: &str) -> String {
+    let trimmed = raw.trim();
+    if !trimmed.starts_with('"') {
+        return raw.to_string();
+    }
+    let mut out = Vec::new();
+    let mut chars = trimmed.chars().peekable();
+    loop {
+        while chars.next_if(|c| c.is_whitespace()).is_some() {}
+        match chars.next() {
+            None => break,
+            Some('"') => {}
+            Some(_) => return raw.to_string(),
+        }
+        loop {
+            match chars.next() {
+                None => return raw.to_string(),
+                Some('"') => break,
+                Some('\\') => match chars.next() {
+                    // \DDD 是十进制字节值
+                    Some(d) if d.is_ascii_digit() => {
+                        let mut digits = String::from(d);
+                        while digits.len() < 3 {
+                            match chars.next_if(|c| c.is_ascii_digit()) {
+                                Some(c) => digits.push(c),
+                                None => return raw.to_string(),
+                            }
+                        }
+                        match digits.parse::<u8>() {
+                            Ok(byte) => out.push(byte),
+                            Err(_) => return raw.to_string(),
+                        }
+                    }
+                    Some(c) => push_char(&mut out, c),
+                    None => return raw.to_string(),
+                },
+                Some(c) => push_char(&mut out, c),
+            }
+        }
+    }
+    String::from_utf8_lossy(&out).into_owned()
+}
+
+/// 编码成带引号的字符串序列，每段不超过 255 字节，不会从字符中间切开。
+pub fn encode(value: &str) -> String {
+    let mut parts = Vec::new();
+    let mut current = String::new();
+    for c in value.chars() {
+        if current.len() + c.len_utf8() > MAX_STRING_LEN {
+            parts.push(quote(&current));
+            current.clear();
+        }
+        current.push(c);
+    }
+    if !current.is_empty() || parts.is_empty() {
+        parts.push(quote(&current));
+    }
+    parts.join(" ")
+}
+
+fn quote(part: &str) -> String {
+    format!("\"{}\"", part.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+fn push_char(out: &mut Vec<u8>, c: char) {
+    let mut buf = [0; 4];
+    out.extend_from_slice(c.encode_utf8(&mut buf).as_bytes());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decodes_quoted_strings() {
+        assert_eq!(decode("\"v=spf1 -all\""), "v=spf1 -all");
+        assert_eq!(decode("\"v=DKIM1; k=rsa; \" \"p=MIGf\""), "v=DKIM1; k=rsa; p=MIGf");
+        assert_eq!(decode(r#""say \"hi\" \\ \059""#), "say \"hi\" \\ ;");
+        assert_eq!(decode("\"中文\""), "中文");
+        // 不是完整的字符串序列时保持原样
+        assert_eq!(decode("v=spf1 include:\"x\""), "v=spf1 include:\"x\"");
+        assert_eq!(decode("\"unterminated"), "\"unterminated");
+        assert_eq!(decode("\"a\" b"), "\"a\" b");
+    }
+
+    #[test]
+    fn long_values_round_trip_in_chunks() {
+        let key = format!("v=DKIM1; k=rsa; p={}", "A".repeat(400));
+        let encoded = encode(&key);
+        let parts: Vec<&str> = encoded.split("\" \"").collect();
+        assert_eq!(parts.len(), 2);
+        assert_eq!(parts[0].len(), MAX_STRING_LEN + 1);
+        assert_eq!(decode(&encoded), key);
+        assert_eq!(encode(&decode(&encoded)), encoded);
+
+        let wide = "中".repeat(100);
+        assert_eq!(decode(&encode(&wide)), wide);
+        assert_eq!(encode(""), "\"\"");
+        assert_eq!(decode(&encode("a \"quoted\" \\ value")), "a \"quoted\" \\ value");
+    }
+}
@@ -1,6 +1,6 @@
 use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
 #[serde(rename_all = "lowercase")]
 pub enum Provider {
     Cloudflare,
@@ -58,6 +58,9 @@ pub struct DomainItem {
     pub status: DomainStatus,
     pub records_count: Option<u32>,
     pub last_changed_at: Option<String>,
+    /// 中文域名的 Unicode 形式，`name` 始终是 ASCII（xn--）形式
+    #[serde(default)]
+    pub display_name: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -85,6 +88,72 @@ pub struct DnsRecord {
     pub srv_port: Option<u16>,
     pub caa_flags: Option<u8>,
     pub caa_tag: Option<String>,
+    pub status: RecordStatus,
+    pub comment: Option<String>,
+    /// Cloudflare 的记录标签，其他服务商始终为空
+    #[serde(default)]
+    pub tags: Vec<String>,
+    pub weight: Option<u16>,
+    /// 主机记录的 Unicode 形式，`name` 始终是 ASCII（xn--）形式
+    #[serde(default)]
+    pub display_name: Option<String>,
+    #[serde(flatten)]
+    pub extra: RecordExtra,
+}
+
+/// 结构化记录类型的附加字段，序列化时和其他字段平铺在一起。
+/// 记录值 `content` 分别是 SSHFP 指纹、TLSA 证书数据、NAPTR 替换域名、DS 摘要、HTTPS/SVCB 的 TargetName 和 URL 转发的目标地址。
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq, Eq)]
+pub struct RecordExtra {
+    pub sshfp_algorithm: Option<u8>,
+    pub sshfp_fp_type: Option<u8>,
+    pub tlsa_usage: Option<u8>,
+    pub tlsa_selector: Option<u8>,
+    pub tlsa_matching_type: Option<u8>,
+    pub naptr_order: Option<u16>,
+    pub naptr_preference: Option<u16>,
+    pub naptr_flags: Option<String>,
+    pub naptr_service: Option<String>,
+    pub naptr_regexp: Option<String>,
+    pub ds_key_tag: Option<u16>,
+    pub ds_algorithm: Option<u8>,
+    pub ds_digest_type: Option<u8>,
+    pub svc_priority: Option<u16>,
+    pub svc_params: Option<SvcParams>,
+    pub url_redirect: Option<UrlRedirect>,
+}
+
+/// URL 转发记录的跳转方式。
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum UrlRedirect {
+    /// 显性转发，301 跳转
+    Permanent,
+    /// 显性转发，302 跳转
+    Temporary,
+    /// 隐性转发，地址栏保持原域名
+    Implicit,
+}
+
+/// HTTPS/SVCB 记录支持的 SvcParams。
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq, Eq)]
+#[serde(default)]
+pub struct SvcParams {
+    pub alpn: Vec<String>,
+    pub port: Option<u16>,
+    pub ipv4hint: Vec<String>,
+    pub ipv6hint: Vec<String>,
+    pub ech: Option<String>,
+    /// 没有结构化字段的参数（mandatory、no-default-alpn、keyNNNN 等），按原文 `key=value` 保留
+    pub other: Vec<String>,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum RecordStatus {
+    #[default]
+    Enabled,
+    Disabled,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -92,6 +161,8 @@ pub struct DnsRecord {
 pub enum ConflictStrategy {
     DoNotCreate,
     Overwrite,
+    Append,
+    ReplaceAll,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -107,6 +178,15 @@ pub struct RecordCreateRequest {
     pub srv_port: Option<u16>,
     pub caa_flags: Option<u8>,
     pub caa_tag: Option<String>,
+    #[serde(default)]
+    pub comment: Option<String>,
+    /// 仅 Cloudflare 支持；修改时为空表示保持原有标签不变
+    #[serde(default)]
+    pub tags: Option<Vec<String>>,
+    #[serde(default)]
+    pub weight: Option<u16>,
+    #[serde(flatten)]
+    pub extra: RecordExtra,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -122,4 +202,195 @@ pub struct RecordUpdateRequest {
     pub srv_port: Option<u16>,
     pub caa_flags: Option<u8>,
     pub caa_tag: Option<String>,
+    #[serde(default)]
+    pub comment: Option<String>,
+    /// 仅 Cloudflare 支持；修改时为空表示保持原有标签不变
+    #[serde(default)]
+    pub tags: Option<Vec<String>>,
+    #[serde(default)]
+    pub weight: Option<u16>,
+    /// 修改前的类型和主机记录，都没变时不再拉取整个 zone 检查冲突
+    #[serde(default)]
+    pub original_type: Option<String>,
+    #[serde(default)]
+    pub original_name: Option<String>,
+    #[serde(flatten)]
+    pub extra: RecordExtra,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RecordWeight {
+    pub id: String,
+    pub weight: u16,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(default)]
+pub struct NetworkSettings {
+    pub proxy_url: Option<String>,
+    pub no_proxy: Option<String>,
+    pub ca_certs_pem: Vec<String>,
+    pub timeout_secs: Option<u64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NetworkProbeResult {
+    pub provider: Provider,
+    pub url: String,
+    pub ok: bool,
+    pub http_status: Option<u16>,
+    pub elapsed_ms: u64,
+    pub message: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MailPresetInfo {
+    pub id: String,
+    pub label: String,
+    pub params: Vec<MailPresetParam>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MailPresetParam {
+    pub key: String,
+    pub label: String,
+    pub required: bool,
+    pub default_value: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum PresetAction {
+    Create,
+    /// 已有完全相同的记录
+    Skip,
+    /// 同名同类型下有模板之外的记录
+    Conflict,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PresetPreviewItem {
+    pub request: RecordCreateRequest,
+    pub action: PresetAction,
+    pub conflicts: Vec<DnsRecord>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PresetApplyResult {
+    pub request: RecordCreateRequest,
+    pub action: PresetAction,
+    pub record: Option<DnsRecord>,
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(default)]
+pub struct ZoneLintOptions {
+    /// DNS-over-HTTPS JSON 接口地址，不填用默认的公共解析器
+    pub resolver: Option<String>,
+    /// 实际在用的证书签发机构，如 `letsencrypt.org`
+    pub issuers: Vec<String>,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(rename_all = "snake_case")]
+pub enum LintSeverity {
+    Error,
+    Warning,
+    Info,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LintFinding {
+    pub check: String,
+    pub severity: LintSeverity,
+    pub name: String,
+    pub record_ids: Vec<String>,
+    pub message: String,
+    pub suggestion: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(default)]
+pub struct RecordSearchQuery {
+    pub provider: Option<Provider>,
+    /// 只搜索名称包含该文本的域名
+    pub domain: Option<String>,
+    pub name: Option<String>,
+    pub content: Option<String>,
+    pub record_type: Option<String>,
+    /// `name` 和 `content` 按正则表达式匹配
+    pub regex: bool,
+    /// 忽略缓存，重新拉取所有域名的记录
+    pub refresh: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RecordSearchHit {
+    pub domain_id: String,
+    pub domain_name: String,
+    pub domain_display_name: Option<String>,
+    pub record: DnsRecord,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RecordSearchFailure {
+    pub provider: Provider,
+    pub domain_name: String,
+    pub message: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RecordSearchResult {
+    pub hits: Vec<RecordSearchHit>,
+    pub failures: Vec<RecordSearchFailure>,
+    pub domains_searched: u32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(default)]
+pub struct BulkReplaceRequest {
+    pub provider: Option<Provider>,
+    /// 只处理名称包含该文本的域名
+    pub domain: Option<String>,
+    pub record_type: Option<String>,
+    /// 要替换的记录值，按整值匹配
+    pub old_content: String,
+    pub new_content: Option<String>,
+    pub new_ttl: Option<u32>,
+    /// 只生成计划，不修改记录
+    pub dry_run: bool,
+    /// 执行时按预览返回的计划修改，不再重新搜索
+    pub plan: Vec<BulkChange>,
+    /// 有记录更新失败时停止，并把已改过的记录恢复原样
+    pub rollback_on_failure: bool,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum BulkChangeStatus {
+    Planned,
+    Updated,
+    Failed,
+    /// 没有处理：前面的记录失败，或者预览之后记录已经变了
+    Skipped,
+    RolledBack,
+    RollbackFailed,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BulkChange {
+    pub domain_id: String,
+    pub domain_name: String,
+    pub before: DnsRecord,
+    pub after: DnsRecord,
+    pub status: BulkChangeStatus,
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BulkReplaceResult {
+    pub changes: Vec<BulkChange>,
+    /// 没能列出记录的服务商或域名，这些地方的记录没有被替换
+    pub failures: Vec<RecordSearchFailure>,
 }
@@ -0,0 +1,747 @@
+//! 记录写入服务商之前的本地校验，新建和修改共用。
+
+use crate::alias;
+use crate::error::AppError;
+use crate::forwarding;
+use crate::idn;
+use crate::svcb;
+use crate::types::{DnsRecord, Provider, RecordCreateRequest, RecordExtra, RecordUpdateRequest, SvcParams};
+use std::net::{Ipv4Addr, Ipv6Addr};
+
+pub const RECORD_TYPES: &[&str] = &[
+    "A", "AAAA", "CNAME", "TXT", "MX", "NS", "SRV", "CAA", "PTR", "NAPTR", "SSHFP", "TLSA", "DS", "HTTPS", "SVCB", "URL", "ALIAS",
+];
+
+// 所有服务商都支持的类型
+const BASE_TYPES: &[&str] = &["A", "AAAA", "CNAME", "TXT", "MX", "NS", "SRV", "CAA"];
+const HUAWEI_TYPES: &[&str] = &["A", "AAAA", "CNAME", "TXT", "MX", "NS", "SRV", "CAA", "PTR", "HTTPS", "SVCB"];
+// DNSPod 和腾讯云是同一套解析
+const TENCENT_TYPES: &[&str] = &["A", "AAAA", "CNAME", "TXT", "MX", "NS", "SRV", "CAA", "HTTPS", "SVCB"];
+
+const MAX_TTL: u32 = 86400;
+const MAX_LABEL_LEN: usize = 63;
+const MAX_NAME_LEN: usize = 253;
+
+/// 新建和修改请求里参与校验的字段。
+pub struct RecordDraft<'a> {
+    pub record_type: &'a str,
+    pub name: &'a str,
+    pub content: &'a str,
+    pub ttl: u32,
+    pub mx_priority: Option<u16>,
+    pub srv_priority: Option<u16>,
+    pub srv_weight: Option<u16>,
+    pub srv_port: Option<u16>,
+    pub caa_tag: Option<&'a str>,
+    pub weight: Option<u16>,
+    pub comment: Option<&'a str>,
+    pub tags: &'a [String],
+    pub extra: &'a RecordExtra,
+}
+
+impl<'a> From<&'a RecordCreateRequest> for RecordDraft<'a> {
+    fn from(req: &'a RecordCreateRequest) -> Self {
+        Self {
+            record_type: &req.record_type,
+            name: &req.name,
+            content: &req.content,
+            ttl: req.ttl,
+            mx_priority: req.mx_priority,
+            srv_priority: req.srv_priority,
+            srv_weight: req.srv_weight,
+            srv_port: req.srv_port,
+            caa_tag: req.caa_tag.as_deref(),
+            weight: req.weight,
+            comment: req.comment.as_deref(),
+            tags: req.tags.as_deref().unwrap_or_default(),
+            extra: &req.extra,
+        }
+    }
+}
+
+impl<'a> From<&'a RecordUpdateRequest> for RecordDraft<'a> {
+    fn from(req: &'a RecordUpdateRequest) -> Self {
+        Self {
+            record_type: &req.record_type,
+            name: &req.name,
+            content: &req.content,
+            ttl: req.ttl,
+            mx_priority: req.mx_priority,
+            srv_priority: req.srv_priority,
+            srv_weight: req.srv_weight,
+            srv_port: req.srv_port,
+            caa_tag: req.caa_tag.as_deref(),
+            weight: req.weight,
+            comment: req.comment.as_deref(),
+            tags: req.tags.as_deref().unwrap_or_default(),
+            extra: &req.extra,
+        }
+    }
+}
+
+struct ProviderRules {
+    label: &'static str,
+    min_ttl: u32,
+    // Cloudflare 用 TTL=1 表示自动
+    auto_ttl: bool,
+    // 根域上已有 NS/SOA，只有会拉平 CNAME 的服务商才允许根域 CNAME
+    apex_cname: bool,
+    // DNS.COM 和雨云的接口没有备注字段
+    comment: bool,
+    // 记录标签目前只有 Cloudflare 支持
+    tags: bool,
+    // 权重的取值范围，不支持权重的为 None
+    weight: Option<(u16, u16)>,
+    types: &'static [&'static str],
+}
+
+fn rules(provider: Provider) -> ProviderRules {
+    match provider {
+        Provider::Cloudflare => ProviderRules {
+            label: "Cloudflare",
+            min_ttl: 60,
+            auto_ttl: true,
+            apex_cname: true,
+            comment: true,
+            tags: true,
+            weight: None,
+            types: RECORD_TYPES,
+        },
+        // DNSPod、腾讯云和阿里云按套餐限制 TTL（免费套餐最低 600），本地只拦付费套餐也不接受的值，
+        // 套餐限制交给服务商返回错误
+        Provider::Dnspod => ProviderRules {
+            label: "DNSPod",
+            min_ttl: 1,
+            auto_ttl: false,
+            apex_cname: false,
+            comment: true,
+            tags: false,
+            weight: Some((0, 100)),
+            types: TENCENT_TYPES,
+        },
+        Provider::Tencentcloud => ProviderRules {
+            label: "腾讯云DNS",
+            min_ttl: 1,
+            auto_ttl: false,
+            apex_cname: false,
+            comment: true,
+            tags: false,
+            weight: Some((0, 100)),
+            types: TENCENT_TYPES,
+        },
+        Provider::Aliyun => ProviderRules {
+            label: "阿里云DNS",
+            min_ttl: 1,
+            auto_ttl: false,
+            apex_cname: false,
+            comment: true,
+            tags: false,
+            weight: Some((1, 100)),
+            types: BASE_TYPES,
+        },
+        Provider::Huawei => ProviderRules {
+            label: "华为云DNS",
+            min_ttl: 1,
+            auto_ttl: false,
+            apex_cname: false,
+            comment: true,
+            tags: false,
+            weight: Some((0, 1000)),
+            types: HUAWEI_TYPES,
+        },
+        Provider::Baidu => ProviderRules {
+            label: "百度智能云DNS",
+            min_ttl: 60,
+            auto_ttl: false,
+            apex_cname: false,
+            comment: true,
+            tags: false,
+            weight: None,
+            types: BASE_TYPES,
+        },
+        Provider::Dnscom => ProviderRules {
+            label: "DNS.COM",
+            min_ttl: 60,
+            auto_ttl: false,
+            apex_cname: false,
+            comment: false,
+            tags: false,
+            weight: None,
+            types: BASE_TYPES,
+        },
+        Provider::Rainyun => ProviderRules {
+            label: "雨云DNS",
+            min_ttl: 60,
+            auto_ttl: false,
+            apex_cname: false,
+            comment: false,
+            tags: false,
+            weight: None,
+            types: BASE_TYPES,
+        },
+    }
+}
+
+pub fn validate_record(provider: Provider, domain_name: &str, draft: &RecordDraft) -> Result<(), AppError> {
+    let rules = rules(provider);
+    if !RECORD_TYPES.contains(&draft.record_type) {
+        return Err(AppError::new("invalid_type", format!("不支持的记录类型: {}", draft.record_type)));
+    }
+    if draft.record_type == forwarding::URL_TYPE {
+        check_url_forward(provider, rules.label, draft)?;
+    } else if draft.record_type == alias::ALIAS_TYPE {
+        if alias::native_type(provider).is_none() {
+            return Err(AppError::new("unsupported", format!("{} 不支持 ALIAS 记录", rules.label)));
+        }
+        if alias::apex_only(provider) && draft.name.trim() != "@" {
+            return Err(AppError::new(
+                "invalid_name",
+                format!("{} 的 ALIAS 记录只能用于根域（@），子域名请使用 CNAME", rules.label),
+            ));
+        }
+    } else if !rules.types.contains(&draft.record_type) {
+        return Err(AppError::new(
+            "invalid_type",
+            format!("{} 不支持 {} 记录", rules.label, draft.record_type),
+        ));
+    }
+
+    if !rules.comment && draft.comment.is_some_and(|c| !c.trim().is_empty()) {
+        return Err(AppError::new("invalid_input", format!("{} 不支持记录备注", rules.label)));
+    }
+
+    if !rules.tags && !draft.tags.is_empty() {
+        return Err(AppError::new("unsupported", format!("{} 不支持记录标签", rules.label)));
+    }
+
+    if let Some(weight) = draft.weight {
+        check_weight(provider, weight)?;
+    }
+
+    let name = draft.name.trim();
+    if name.is_empty() {
+        return Err(AppError::new("invalid_name", "主机记录不能为空"));
+    }
+    validate_host(name, domain_name)?;
+
+    let content = draft.content.trim();
+    if content.is_empty() {
+        return Err(AppError::new("invalid_content", "记录值不能为空"));
+    }
+
+    let auto = rules.auto_ttl && draft.ttl == 1;
+    if !auto && (draft.ttl < rules.min_ttl || draft.ttl > MAX_TTL) {
+        let message = if rules.auto_ttl {
+            format!("{} 的 TTL 必须为 1（自动）或 {}-{MAX_TTL} 秒", rules.label, rules.min_ttl)
+        } else {
+            format!("{} 的 TTL 必须在 {}-{MAX_TTL} 秒之间", rules.label, rules.min_ttl)
+        };
+        return Err(AppError::new("invalid_ttl", message));
+    }
+
+    match draft.record_type {
+        "A" => {
+            if content.parse::<Ipv4Addr>().is_err() {
+                return Err(AppError::new("invalid_content", "A 记录必须是有效的 IPv4 地址"));
+            }
+        }
+        "AAAA" => {
+            if content.parse::<Ipv6Addr>().is_err() {
+                return Err(AppError::new("invalid_content", "AAAA 记录必须是有效的 IPv6 地址"));
+            }
+        }
+        "CNAME" | "NS" | "PTR" | "ALIAS" => {
+            validate_target(content)?;
+            if draft.record_type == "CNAME" && name == "@" && !rules.apex_cname {
+                let hint = if alias::native_type(provider).is_some() { "，可以改用 ALIAS 记录" } else { "" };
+                return Err(AppError::new(
+                    "cname_conflict",
+                    format!("{} 不支持在根域名上添加 CNAME 记录{hint}", rules.label),
+                ));
+            }
+        }
+        "MX" => {
+            if draft.mx_priority.is_none() {
+                return Err(AppError::new("missing_field", "MX 记录必须设置优先级"));
+            }
+            validate_target(content)?;
+        }
+        "SRV" => {
+            if draft.srv_priority.is_none() || draft.srv_weight.is_none() || draft.srv_port.is_none() {
+                return Err(AppError::new("missing_field", "SRV 记录必须设置优先级、权重和端口"));
+            }
+            let parts: Vec<&str> = name.split('.').collect();
+            if parts.len() < 2 || !parts[0].starts_with('_') || !parts[1].starts_with('_') {
+                return Err(AppError::new("invalid_name", "SRV 主机记录需为 _service._proto 形式"));
+            }
+            // 目标为 . 表示该服务不可用
+            if content != "." {
+                validate_target(content)?;
+            }
+        }
+        "CAA" => {
+            if let Some(tag) = draft.caa_tag {
+                if tag.trim().is_empty() {
+                    return Err(AppError::new("invalid_caa_tag", "CAA Tag 不能为空"));
+                }
+            }
+        }
+        "HTTPS" | "SVCB" => {
+            let Some(priority) = draft.extra.svc_priority else {
+                return Err(AppError::new("missing_field", format!("{} 记录必须设置优先级", draft.record_type)));
+            };
+            let params = draft.extra.svc_params.clone().unwrap_or_default();
+            if priority == 0 && params != SvcParams::default() {
+                return Err(AppError::new("invalid_content", "别名模式（优先级 0）不能设置 SvcParams"));
+            }
+            // 目标为 . 表示使用记录所在的域名
+            if content != "." {
+                validate_target(content)?;
+            }
+            svcb::validate_params(&params)?;
+        }
+        "SSHFP" => {
+            let extra = draft.extra;
+            if extra.sshfp_algorithm.is_none() {
+                return Err(AppError::new("missing_field", "SSHFP 记录必须设置算法"));
+            }
+            let len = match extra.sshfp_fp_type {
+                Some(1) => 40,
+                Some(2) => 64,
+                _ => return Err(AppError::new("invalid_content", "SSHFP 指纹类型必须为 1（SHA-1）或 2（SHA-256）")),
+            };
+            check_hex(content, Some(len), "SSHFP 指纹")?;
+        }
+        "TLSA" => {
+            let extra = draft.extra;
+            let (Some(usage), Some(selector), Some(matching)) =
+                (extra.tlsa_usage, extra.tlsa_selector, extra.tlsa_matching_type)
+            else {
+                return Err(AppError::new("missing_field", "TLSA 记录必须设置用途、选择器和匹配类型"));
+            };
+            if usage > 3 || selector > 1 || matching > 2 {
+                return Err(AppError::new(
+                    "invalid_content",
+                    "TLSA 用途必须为 0-3，选择器为 0-1，匹配类型为 0-2",
+                ));
+            }
+            let len = match matching {
+                1 => Some(64),
+                2 => Some(128),
+                _ => None,
+            };
+            check_hex(content, len, "TLSA 证书数据")?;
+            let parts: Vec<&str> = name.split('.').collect();
+            if parts.len() < 2 || !parts[0].starts_with('_') || !parts[1].starts_with('_') {
+                return Err(AppError::new("invalid_name", "TLSA 主机记录需为 _port._proto 形式"));
+            }
+            if parts[0][1..].parse::<u16>().is_err() {
+                return Err(AppError::new("invalid_name", "TLSA 主机记录的端口必须是数字"));
+            }
+        }
+        "NAPTR" => {
+            let extra = draft.extra;
+            if extra.naptr_order.is_none() || extra.naptr_preference.is_none() {
+                return Err(AppError::new("missing_field", "NAPTR 记录必须设置顺序和优先级"));
+            }
+            let flags = extra.naptr_flags.as_deref().unwrap_or("");
+            if !flags.chars().all(|c| c.is_ascii_alphanumeric()) {
+                return Err(AppError::new("invalid_content", "NAPTR 标志只能包含字母和数字"));
+            }
+            // 正则和替换域名只能二选一，不用替换域名时填 .
+            let regexp = extra.naptr_regexp.as_deref().unwrap_or("").trim();
+            if !regexp.is_empty() {
+                if content != "." {
+                    return Err(AppError::new("invalid_content", "NAPTR 设置了正则时替换域名必须为 ."));
+                }
+            } else if content != "." {
+                validate_target(content)?;
+            }
+        }
+        "DS" => {
+            let extra = draft.extra;
+            if extra.ds_key_tag.is_none() || extra.ds_algorithm.is_none() {
+                return Err(AppError::new("missing_field", "DS 记录必须设置密钥标签和算法"));
+            }
+            let len = match extra.ds_digest_type {
+                Some(1) => 40,
+                Some(2) => 64,
+                Some(4) => 96,
+                _ => return Err(AppError::new("invalid_content", "DS 摘要类型必须为 1、2 或 4")),
+            };
+            check_hex(content, Some(len), "DS 摘要")?;
+        }
+        _ => {}
+    }
+
+    Ok(())
+}
+
+/// 检查权重是否在服务商允许的范围内，批量设置权重时在写入前逐个检查。
+pub fn check_weight(provider: Provider, weight: u16) -> Result<(), AppError> {
+    let rules = rules(provider);
+    let Some((min, max)) = rules.weight else {
+        return Err(AppError::new("unsupported", format!("{} 不支持权重", rules.label)));
+    };
+    if !(min..=max).contains(&weight) {
+        return Err(AppError::new(
+            "invalid_weight",
+            format!("{} 的权重必须在 {min}-{max} 之间", rules.label),
+        ));
+    }
+    Ok(())
+}
+
+/// CNAME 不能和同名的其他类型记录共存。`skip_id` 为正在修改的那条记录。
+pub fn ensure_exclusive(
+    existing: &[DnsRecord],
+    record_type: &str,
+    name: &str,
+    skip_id: Option<&str>,
+) -> Result<(), AppError> {
+    let name = name.trim();
+    for record in existing {
+        if Some(record.id.as_str()) == skip_id || !idn::same_name(&record.name, name) {
+            continue;
+        }
+        if record_type == "CNAME" && record.record_type != "CNAME" {
+            return Err(AppError::new(
+                "cname_conflict",
+                format!("{name} 已有 {} 记录，不能再添加 CNAME 记录", record.record_type),
+            ));
+        }
+        if record_type != "CNAME" && record.record_type == "CNAME" {
+            return Err(AppError::new(
+                "cname_conflict",
+                format!("{name} 已有 CNAME 记录，不能再添加 {record_type} 记录"),
+            ));
+        }
+    }
+    Ok(())
+}
+
+fn validate_host(name: &str, domain_name: &str) -> Result<(), AppError> {
+    if name == "@" {
+        return Ok(());
+    }
+    for (i, label) in name.split('.').enumerate() {
+        if label == "*" && i == 0 {
+            continue;
+        }
+        if label.contains('*') {
+            return Err(AppError::new("invalid_name", "通配符 * 只能作为主机记录最左边的一段"));
+        }
+        check_label(label, "主机记录")?;
+    }
+    if name.len() + 1 + domain_name.trim_end_matches('.').len() > MAX_NAME_LEN {
+        return Err(AppError::new("invalid_name", format!("完整域名不能超过 {MAX_NAME_LEN} 个字符")));
+    }
+    Ok(())
+}
+
+fn validate_target(content: &str) -> Result<(), AppError> {
+    let host = content.strip_suffix('.').unwrap_or(content);
+    if !host.contains('.') || host.len() > MAX_NAME_LEN {
+        return Err(AppError::new("invalid_content", "记录值必须是有效域名"));
+    }
+    for label in host.split('.') {
+        check_label(label, "记录值").map_err(|e| AppError::new("invalid_content", e.message))?;
+    }
+    Ok(())
+}
+
+// URL 转发不是 DNS 记录类型，按服务商是否提供对应的原生类型判断
+fn check_url_forward(provider: Provider, label: &str, draft: &RecordDraft) -> Result<(), AppError> {
+    if !forwarding::supported(provider) {
+        return Err(AppError::new("unsupported", format!("{label} 不支持 URL 转发记录")));
+    }
+    let Some(redirect) = draft.extra.url_redirect else {
+        return Err(AppError::new("missing_field", "URL 转发记录必须设置跳转方式"));
+    };
+    if forwarding::native_type(provider, redirect).is_none() {
+        return Err(AppError::new(
+            "unsupported",
+            format!(
+                "{label} 不支持{}，可用的跳转方式: {}",
+                forwarding::redirect_label(redirect),
+                forwarding::supported_labels(provider)
+            ),
+        ));
+    }
+    let target = draft.content.trim();
+    let authority = target
+        .strip_prefix("http://")
+        .or_else(|| target.strip_prefix("https://"))
+        .and_then(|rest| rest.split(['/', '?', '#']).next())
+        .unwrap_or("");
+    let host = match authority.rsplit_once(':') {
+        Some((host, port)) if port.parse::<u16>().is_ok() => host,
+        _ => authority,
+    };
+    if validate_target(host).is_err() {
+        return Err(AppError::new("invalid_content", "转发地址必须是 http:// 或 https:// 开头的有效网址"));
+    }
+    Ok(())
+}
+
+fn check_hex(content: &str, len: Option<usize>, field: &str) -> Result<(), AppError> {
+    if !content.chars().all(|c| c.is_ascii_hexdigit()) || !content.len().is_multiple_of(2) {
+        return Err(AppError::new("invalid_content", format!("{field}必须是十六进制字符串")));
+    }
+    if let Some(len) = len {
+        if content.len() != len {
+            return Err(AppError::new("invalid_content", format!("{field}长度必须为 {len} 个字符")));
+        }
+    }
+    Ok(())
+}
+
+// RFC 1123 的标签规则，另外放行 _dmarc、_sip 这类下划线前缀
+fn check_label(label: &str, field: &str) -> Result<(), AppError> {
+    if label.is_empty() || label.len() > MAX_LABEL_LEN {
+        return Err(AppError::new(
+            "invalid_name",
+            format!("{field}的每一段长度必须在 1-{MAX_LABEL_LEN} 个字符之间"),
+        ));
+    }
+    if !label.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_') {
+        return Err(AppError::new(
+            "invalid_name",
+            format!("{field}只能包含字母、数字、连字符和下划线"),
+        ));
+    }
+    if label.starts_with('-') || label.ends_with('-') {
+        return Err(AppError::new("invalid_name", format!("{field}的每一段不能以连字符开头或结尾")));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::providers::mock::{create_request, record, update_request};
+    use crate::types::UrlRedirect;
+
+    fn validate(provider: Provider, req: &RecordCreateRequest) -> Result<(), AppError> {
+        validate_record(provider, "example.com", &RecordDraft::from(req))
+    }
+
+    #[test]
+    fn ttl_follows_provider_rules() {
+        let mut req = create_request("A", "www", "192.0.2.1");
+        req.ttl = 30;
+        assert_eq!(validate(Provider::Baidu, &req).unwrap_err().code, "invalid_ttl");
+        assert_eq!(validate(Provider::Cloudflare, &req).unwrap_err().code, "invalid_ttl");
+        // 付费套餐允许低于 600 的 TTL
+        assert!(validate(Provider::Dnspod, &req).is_ok());
+
+        req.ttl = 1;
+        assert!(validate(Provider::Cloudflare, &req).is_ok());
+        assert!(validate(Provider::Huawei, &req).is_ok());
+        assert!(validate(Provider::Aliyun, &req).is_ok());
+        assert!(validate(Provider::Rainyun, &req).is_err());
+
+        req.ttl = MAX_TTL + 1;
+        assert!(validate(Provider::Cloudflare, &req).is_err());
+    }
+
+    #[test]
+    fn host_labels_follow_rfc1123() {
+        for name in ["@", "www", "*", "*.dev", "_dmarc", "_sip._tcp", "a-b.c1"] {
+            let req = create_request("TXT", name, "v=1");
+            assert!(validate(Provider::Cloudflare, &req).is_ok(), "{name}");
+        }
+        let long_label = "a".repeat(64);
+        let long_name = ["a".repeat(63), "a".repeat(63), "a".repeat(63), "a".repeat(50)].join(".");
+        for name in ["www.*", "a*b", "-www", "www-", "w w", "a..b", long_label.as_str(), long_name.as_str()] {
+            let req = create_request("TXT", name, "v=1");
+            assert_eq!(validate(Provider::Cloudflare, &req).unwrap_err().code, "invalid_name", "{name}");
+        }
+    }
+
+    #[test]
+    fn targets_must_be_hostnames() {
+        let req = create_request("CNAME", "www", "target.example.net.");
+        assert!(validate(Provider::Dnspod, &req).is_ok());
+        for target in ["localhost", "bad host.example.com", "-a.example.com"] {
+            let req = create_request("CNAME", "www", target);
+            assert_eq!(validate(Provider::Dnspod, &req).unwrap_err().code, "invalid_content", "{target}");
+        }
+
+        let mut req = create_request("MX", "@", "not a host");
+        req.mx_priority = Some(10);
+        assert!(validate(Provider::Dnspod, &req).is_err());
+
+        let mut req = create_request("SRV", "_sip._tcp", ".");
+        req.srv_priority = Some(0);
+        req.srv_weight = Some(0);
+        req.srv_port = Some(0);
+        assert!(validate(Provider::Dnspod, &req).is_ok());
+    }
+
+    #[test]
+    fn apex_cname_only_where_flattened() {
+        let req = create_request("CNAME", "@", "target.example.net");
+        assert!(validate(Provider::Cloudflare, &req).is_ok());
+        let err = validate(Provider::Aliyun, &req).unwrap_err();
+        assert_eq!(err.code, "cname_conflict");
+        assert!(err.message.ends_with("可以改用 ALIAS 记录"));
+
+        let req = create_request("ALIAS", "@", "target.example.net");
+        assert!(validate(Provider::Aliyun, &req).is_ok());
+        assert!(validate(Provider::Cloudflare, &req).is_ok());
+        assert_eq!(validate(Provider::Dnspod, &req).unwrap_err().code, "unsupported");
+        let req = create_request("ALIAS", "www", "target.example.net");
+        assert_eq!(validate(Provider::Cloudflare, &req).unwrap_err().code, "invalid_name");
+        assert!(validate(Provider::Huawei, &req).is_ok());
+        let req = create_request("ALIAS", "@", "not a host");
+        assert_eq!(validate(Provider::Huawei, &req).unwrap_err().code, "invalid_content");
+    }
+
+    #[test]
+    fn update_requests_are_validated_too() {
+        let update = update_request("1", "A", "www", "not-an-ip");
+        let err = validate_record(Provider::Dnspod, "example.com", &RecordDraft::from(&update)).unwrap_err();
+        assert_eq!(err.code, "invalid_content");
+    }
+
+    #[test]
+    fn weights_follow_provider_ranges() {
+        assert!(check_weight(Provider::Dnspod, 0).is_ok());
+        assert_eq!(check_weight(Provider::Tencentcloud, 101).unwrap_err().code, "invalid_weight");
+        assert_eq!(check_weight(Provider::Aliyun, 0).unwrap_err().code, "invalid_weight");
+        assert!(check_weight(Provider::Aliyun, 100).is_ok());
+        assert_eq!(check_weight(Provider::Cloudflare, 1).unwrap_err().code, "unsupported");
+
+        let mut req = create_request("A", "www", "192.0.2.1");
+        req.weight = Some(200);
+        assert_eq!(validate(Provider::Dnspod, &req).unwrap_err().code, "invalid_weight");
+    }
+
+    #[test]
+    fn tags_only_where_supported() {
+        let mut req = create_request("A", "www", "192.0.2.1");
+        req.tags = Some(vec!["owner:ops".to_string()]);
+        assert!(validate(Provider::Cloudflare, &req).is_ok());
+        assert_eq!(validate(Provider::Aliyun, &req).unwrap_err().code, "unsupported");
+        req.tags = Some(Vec::new());
+        assert!(validate(Provider::Aliyun, &req).is_ok());
+    }
+
+    #[test]
+    fn comments_only_where_supported() {
+        let mut req = create_request("A", "www", "192.0.2.1");
+        req.comment = Some("办公网出口".to_string());
+        assert!(validate(Provider::Baidu, &req).is_ok());
+        assert_eq!(validate(Provider::Dnscom, &req).unwrap_err().code, "invalid_input");
+        assert_eq!(validate(Provider::Rainyun, &req).unwrap_err().code, "invalid_input");
+        req.comment = Some(String::new());
+        assert!(validate(Provider::Rainyun, &req).is_ok());
+    }
+
+    #[test]
+    fn structured_types_are_cloudflare_only() {
+        let others = [
+            Provider::Dnspod,
+            Provider::Tencentcloud,
+            Provider::Aliyun,
+            Provider::Huawei,
+            Provider::Baidu,
+            Provider::Dnscom,
+            Provider::Rainyun,
+        ];
+        for provider in others {
+            for record_type in ["SSHFP", "TLSA", "NAPTR", "DS"] {
+                let req = create_request(record_type, "host", "ab");
+                let err = validate(provider, &req).unwrap_err();
+                assert_eq!(err.code, "invalid_type", "{provider:?} {record_type}");
+            }
+        }
+    }
+
+    #[test]
+    fn structured_types_check_their_fields() {
+        let mut req = create_request("SSHFP", "host", &"ab".repeat(32));
+        req.extra.sshfp_algorithm = Some(4);
+        req.extra.sshfp_fp_type = Some(2);
+        assert!(validate(Provider::Cloudflare, &req).is_ok());
+        req.extra.sshfp_fp_type = Some(1);
+        assert_eq!(validate(Provider::Cloudflare, &req).unwrap_err().code, "invalid_content");
+        assert_eq!(validate(Provider::Dnspod, &req).unwrap_err().code, "invalid_type");
+
+        let mut req = create_request("TLSA", "_443._tcp", &"0f".repeat(32));
+        req.extra.tlsa_usage = Some(3);
+        req.extra.tlsa_selector = Some(1);
+        req.extra.tlsa_matching_type = Some(1);
+        assert!(validate(Provider::Cloudflare, &req).is_ok());
+        req.name = "_https._tcp".to_string();
+        assert_eq!(validate(Provider::Cloudflare, &req).unwrap_err().code, "invalid_name");
+
+        let mut req = create_request("NAPTR", "@", ".");
+        req.extra.naptr_order = Some(100);
+        req.extra.naptr_preference = Some(10);
+        req.extra.naptr_flags = Some("U".to_string());
+        req.extra.naptr_service = Some("E2U+sip".to_string());
+        req.extra.naptr_regexp = Some("!^.*$!sip:info@example.com!".to_string());
+        assert!(validate(Provider::Cloudflare, &req).is_ok());
+        req.content = "sip.example.com".to_string();
+        assert_eq!(validate(Provider::Cloudflare, &req).unwrap_err().code, "invalid_content");
+
+        let mut req = create_request("DS", "child", &"AB".repeat(32));
+        req.extra.ds_key_tag = Some(2371);
+        req.extra.ds_algorithm = Some(13);
+        req.extra.ds_digest_type = Some(2);
+        assert!(validate(Provider::Cloudflare, &req).is_ok());
+        req.extra.ds_key_tag = None;
+        assert_eq!(validate(Provider::Cloudflare, &req).unwrap_err().code, "missing_field");
+
+        let req = create_request("PTR", "1", "host.example.com.");
+        assert!(validate(Provider::Huawei, &req).is_ok());
+        assert_eq!(validate(Provider::Aliyun, &req).unwrap_err().code, "invalid_type");
+    }
+
+    #[test]
+    fn service_bindings_check_mode_and_params() {
+        let mut req = create_request("HTTPS", "@", ".");
+        assert_eq!(validate(Provider::Cloudflare, &req).unwrap_err().code, "missing_field");
+        req.extra.svc_priority = Some(1);
+        req.extra.svc_params = Some(svcb::parse_params("alpn=h3,h2 ipv4hint=192.0.2.1").unwrap());
+        assert!(validate(Provider::Tencentcloud, &req).is_ok());
+        assert_eq!(validate(Provider::Aliyun, &req).unwrap_err().code, "invalid_type");
+
+        req.extra.svc_priority = Some(0);
+        assert_eq!(validate(Provider::Huawei, &req).unwrap_err().code, "invalid_content");
+        req.extra.svc_params = None;
+        req.content = "cdn.example.net".to_string();
+        assert!(validate(Provider::Huawei, &req).is_ok());
+    }
+
+    #[test]
+    fn url_forwards_need_a_supported_redirect() {
+        let mut req = create_request("URL", "go", "https://example.net:8443/landing?from=dns");
+        assert_eq!(validate(Provider::Dnspod, &req).unwrap_err().code, "missing_field");
+        req.extra.url_redirect = Some(UrlRedirect::Permanent);
+        assert!(validate(Provider::Dnspod, &req).is_ok());
+        assert_eq!(validate(Provider::Aliyun, &req).unwrap_err().code, "unsupported");
+        let err = validate(Provider::Cloudflare, &req).unwrap_err();
+        assert_eq!((err.code.as_str(), err.message.as_str()), ("unsupported", "Cloudflare 不支持 URL 转发记录"));
+
+        req.content = "example.net".to_string();
+        assert_eq!(validate(Provider::Dnspod, &req).unwrap_err().code, "invalid_content");
+    }
+
+    #[test]
+    fn cname_cannot_share_a_name() {
+        let records = vec![
+            record("1", "A", "www", "target.example.net"),
+            record("2", "CNAME", "cdn", "target.example.net"),
+        ];
+
+        assert_eq!(ensure_exclusive(&records, "CNAME", "www", None).unwrap_err().code, "cname_conflict");
+        assert_eq!(ensure_exclusive(&records, "TXT", "CDN", None).unwrap_err().code, "cname_conflict");
+        assert!(ensure_exclusive(&records, "CNAME", "cdn", None).is_ok());
+        assert!(ensure_exclusive(&records, "CNAME", "api", None).is_ok());
+        // 把唯一的 A 记录改成 CNAME 不算冲突
+        assert!(ensure_exclusive(&records, "CNAME", "www", Some("1")).is_ok());
+    }
+}
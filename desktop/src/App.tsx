@@ -6,6 +6,7 @@ import { Layout } from "./app/Layout";
 import { DomainsPage } from "./pages/DomainsPage";
 import { IntegrationsPage } from "./pages/IntegrationsPage";
 import { RecordsPage } from "./pages/RecordsPage";
+import { SearchPage } from "./pages/SearchPage";
 import { SettingsPage } from "./pages/SettingsPage";
 import { SmartResolvePage } from "./pages/SmartResolvePage";
 import { SetupPage } from "./pages/SetupPage";
@@ -97,6 +98,7 @@ function App() {
           <Route path="/domains" element={<DomainsPage />} />
           <Route path="/smart-resolve" element={<SmartResolvePage />} />
           <Route path="/records/:provider/:domainId" element={<RecordsPage />} />
+          <Route path="/search" element={<SearchPage />} />
           <Route path="/integrations" element={<IntegrationsPage />} />
           <Route path="/settings" element={<SettingsPage />} />
           <Route path="*" element={<Navigate to="/domains" replace />} />
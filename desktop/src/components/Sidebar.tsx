@@ -1,5 +1,5 @@
 import { NavLink, useLocation } from "react-router-dom";
-import { Globe, Zap, Plug, Settings, Lock } from "lucide-react";
+import { Globe, Search, Zap, Plug, Settings, Lock } from "lucide-react";
 import { cn } from "./Button";
 import { useApp } from "../app/AppContext";
 import logo from "../assets/logo.png";
@@ -10,6 +10,7 @@ export function Sidebar() {
 
   const navItems = [
     { to: "/domains", icon: Globe, label: "域名资产" },
+    { to: "/search", icon: Search, label: "记录搜索" },
     { to: "/smart-resolve", icon: Zap, label: "智能解析" },
     { to: "/integrations", icon: Plug, label: "服务接入" },
     { to: "/settings", icon: Settings, label: "系统设置" },
@@ -43,6 +43,22 @@ export type IntegrationTestResult = {
   message: string;
 };
 
+export type NetworkSettings = {
+  proxy_url?: string | null;
+  no_proxy?: string | null;
+  ca_certs_pem?: string[];
+  timeout_secs?: number | null;
+};
+
+export type NetworkProbeResult = {
+  provider: Provider;
+  url: string;
+  ok: boolean;
+  http_status: number | null;
+  elapsed_ms: number;
+  message: string;
+};
+
 export type DomainStatus =
   | "ok"
   | "auth_failed"
@@ -57,6 +73,7 @@ export type DomainItem = {
   status: DomainStatus;
   records_count: number | null;
   last_changed_at: string | null;
+  display_name?: string | null;
 };
 
 export type DnsRecord = {
@@ -73,9 +90,47 @@ export type DnsRecord = {
   srv_port: number | null;
   caa_flags: number | null;
   caa_tag: string | null;
+  status: RecordStatus;
+  comment: string | null;
+  tags: string[];
+  weight: number | null;
+  display_name?: string | null;
+} & RecordExtra;
+
+// SSHFP/TLSA/NAPTR/DS/HTTPS/SVCB 的附加字段，和记录其他字段平铺在一起
+export type RecordExtra = {
+  sshfp_algorithm?: number | null;
+  sshfp_fp_type?: number | null;
+  tlsa_usage?: number | null;
+  tlsa_selector?: number | null;
+  tlsa_matching_type?: number | null;
+  naptr_order?: number | null;
+  naptr_preference?: number | null;
+  naptr_flags?: string | null;
+  naptr_service?: string | null;
+  naptr_regexp?: string | null;
+  ds_key_tag?: number | null;
+  ds_algorithm?: number | null;
+  ds_digest_type?: number | null;
+  svc_priority?: number | null;
+  svc_params?: SvcParams | null;
+  url_redirect?: UrlRedirect | null;
+};
+
+export type UrlRedirect = "permanent" | "temporary" | "implicit";
+
+export type SvcParams = {
+  alpn: string[];
+  port: number | null;
+  ipv4hint: string[];
+  ipv6hint: string[];
+  ech: string | null;
+  other: string[];
 };
 
-export type ConflictStrategy = "do_not_create" | "overwrite";
+export type RecordStatus = "enabled" | "disabled";
+
+export type ConflictStrategy = "do_not_create" | "overwrite" | "append" | "replace_all";
 
 export type RecordCreateRequest = {
   record_type: string;
@@ -89,7 +144,10 @@ export type RecordCreateRequest = {
   srv_port?: number | null;
   caa_flags?: number | null;
   caa_tag?: string | null;
-};
+  comment?: string | null;
+  tags?: string[] | null;
+  weight?: number | null;
+} & RecordExtra;
 
 export type RecordUpdateRequest = {
   id: string;
@@ -103,20 +161,152 @@ export type RecordUpdateRequest = {
   srv_port?: number | null;
   caa_flags?: number | null;
   caa_tag?: string | null;
+  comment?: string | null;
+  tags?: string[] | null;
+  weight?: number | null;
+  original_type?: string | null;
+  original_name?: string | null;
+} & RecordExtra;
+
+export type MailPresetParam = {
+  key: string;
+  label: string;
+  required: boolean;
+  default_value?: string | null;
+};
+
+export type MailPresetInfo = {
+  id: string;
+  label: string;
+  params: MailPresetParam[];
+};
+
+export type PresetAction = "create" | "skip" | "conflict";
+
+export type PresetPreviewItem = {
+  request: RecordCreateRequest;
+  action: PresetAction;
+  conflicts: DnsRecord[];
+};
+
+export type PresetApplyResult = {
+  request: RecordCreateRequest;
+  action: PresetAction;
+  record?: DnsRecord | null;
+  error?: string | null;
+};
+
+export type ZoneLintOptions = {
+  resolver?: string | null;
+  issuers?: string[];
+};
+
+export type LintSeverity = "error" | "warning" | "info";
+
+export type LintFinding = {
+  check: string;
+  severity: LintSeverity;
+  name: string;
+  record_ids: string[];
+  message: string;
+  suggestion: string;
+};
+
+export type RecordSearchQuery = {
+  provider?: Provider | null;
+  domain?: string | null;
+  name?: string | null;
+  content?: string | null;
+  record_type?: string | null;
+  regex?: boolean;
+  refresh?: boolean;
+};
+
+export type RecordSearchHit = {
+  domain_id: string;
+  domain_name: string;
+  domain_display_name?: string | null;
+  record: DnsRecord;
+};
+
+export type RecordSearchFailure = {
+  provider: Provider;
+  domain_name: string;
+  message: string;
+};
+
+export type RecordSearchResult = {
+  hits: RecordSearchHit[];
+  failures: RecordSearchFailure[];
+  domains_searched: number;
+};
+
+export type BulkReplaceRequest = {
+  provider?: Provider | null;
+  domain?: string | null;
+  record_type?: string | null;
+  old_content: string;
+  new_content?: string | null;
+  new_ttl?: number | null;
+  dry_run?: boolean;
+  plan?: BulkChange[];
+  rollback_on_failure?: boolean;
+};
+
+export type BulkChangeStatus = "planned" | "updated" | "failed" | "skipped" | "rolled_back" | "rollback_failed";
+
+export type BulkChange = {
+  domain_id: string;
+  domain_name: string;
+  before: DnsRecord;
+  after: DnsRecord;
+  status: BulkChangeStatus;
+  error?: string | null;
+};
+
+export type BulkReplaceResult = {
+  changes: BulkChange[];
+  failures: RecordSearchFailure[];
+};
+
+export type RecordWeight = {
+  id: string;
+  weight: number;
 };
 
 export type AppError = {
   code: string;
   message: string;
+  provider?: Provider | null;
+  operation?: string | null;
+  http_status?: number | null;
+  provider_code?: string | null;
+  request_id?: string | null;
+  retryable?: boolean;
 };
 
+function describeAppError(error: AppError): string {
+  const parts = [error.message];
+  if (error.http_status === 401 || error.http_status === 403 || error.code === "auth_failed") {
+    parts.push("请检查凭证是否正确、是否过期或缺少权限");
+  } else if (error.retryable) {
+    parts.push("服务商暂时不可用，请稍后重试");
+  }
+  if (error.provider_code) parts.push(`错误码: ${error.provider_code}`);
+  if (error.request_id) parts.push(`RequestId: ${error.request_id}`);
+  return parts.join("；");
+}
 
 export function resolveErrorMessage(error: unknown): string {
   if (typeof error === "string") return error;
   if (error instanceof Error) return error.message;
   if (error && typeof error === "object") {
     const maybeMessage = (error as { message?: unknown }).message;
-    if (typeof maybeMessage === "string") return maybeMessage;
+    if (typeof maybeMessage === "string") {
+      return typeof (error as { code?: unknown }).code === "string"
+        ? describeAppError(error as AppError)
+        : maybeMessage;
+    }
     try {
       return JSON.stringify(error);
     } catch {
@@ -142,62 +332,105 @@ export async function getIntegrations(masterPassword: string): Promise<Integrati
   return invoke("integrations_get", { masterPassword });
 }
 
-export async function testCloudflare(apiToken: string): Promise<IntegrationTestResult> {
-  return invoke("cloudflare_test", { apiToken });
+export async function getNetworkSettings(masterPassword: string): Promise<NetworkSettings> {
+  return invoke("network_settings_get", { masterPassword });
+}
+
+export async function saveNetworkSettings(masterPassword: string, settings: NetworkSettings): Promise<void> {
+  return invoke("network_settings_save", { masterPassword, settings });
+}
+
+export async function testNetwork(settings: NetworkSettings): Promise<NetworkProbeResult[]> {
+  return invoke("network_test", { settings });
+}
+
+export async function testCloudflare(apiToken: string, endpoint?: string): Promise<IntegrationTestResult> {
+  return invoke("cloudflare_test", { apiToken, endpoint });
 }
 
 export async function saveCloudflare(
   masterPassword: string,
   apiToken: string,
+  endpoint?: string,
 ): Promise<void> {
-  return invoke("cloudflare_save", { masterPassword, apiToken });
+  return invoke("cloudflare_save", { masterPassword, apiToken, endpoint });
 }
 
 export async function clearCloudflare(masterPassword: string): Promise<void> {
   return invoke("cloudflare_clear", { masterPassword });
 }
 
-export async function testDnspod(tokenId: string, token: string): Promise<IntegrationTestResult> {
-  return invoke("dnspod_test", { tokenId, token });
+export type DnspodApi3Keys = {
+  secretId?: string;
+  secretKey?: string;
+};
+
+export async function testDnspod(
+  tokenId: string,
+  token: string,
+  api3: DnspodApi3Keys = {},
+  endpoint?: string,
+): Promise<IntegrationTestResult> {
+  return invoke("dnspod_test", { tokenId, token, ...api3, endpoint });
 }
 
 export async function saveDnspod(
   masterPassword: string,
   tokenId: string,
   token: string,
+  api3: DnspodApi3Keys = {},
+  endpoint?: string,
 ): Promise<void> {
-  return invoke("dnspod_save", { masterPassword, tokenId, token });
+  return invoke("dnspod_save", { masterPassword, tokenId, token, ...api3, endpoint });
 }
 
 export async function clearDnspod(masterPassword: string): Promise<void> {
   return invoke("dnspod_clear", { masterPassword });
 }
 
+export type StsOptions = {
+  securityToken?: string;
+  roleArn?: string;
+};
+
 export async function testAliyun(
   accessKeyId: string,
   accessKeySecret: string,
+  sts: StsOptions = {},
+  endpoint?: string,
 ): Promise<IntegrationTestResult> {
-  return invoke("aliyun_test", { accessKeyId, accessKeySecret });
+  return invoke("aliyun_test", { accessKeyId, accessKeySecret, ...sts, endpoint });
 }
 
 export async function saveAliyun(
   masterPassword: string,
   accessKeyId: string,
   accessKeySecret: string,
+  sts: StsOptions = {},
+  endpoint?: string,
 ): Promise<void> {
-  return invoke("aliyun_save", { masterPassword, accessKeyId, accessKeySecret });
+  return invoke("aliyun_save", { masterPassword, accessKeyId, accessKeySecret, ...sts, endpoint });
 }
 
 export async function clearAliyun(masterPassword: string): Promise<void> {
   return invoke("aliyun_clear", { masterPassword });
 }
 
-export async function testHuawei(token: string): Promise<IntegrationTestResult> {
-  return invoke("huawei_test", { token });
+export type HuaweiCredsInput = {
+  token: string;
+  accessKeyId?: string;
+  secretAccessKey?: string;
+  region?: string;
+  projectId?: string;
+  endpoint?: string;
+};
+
+export async function testHuawei(creds: HuaweiCredsInput): Promise<IntegrationTestResult> {
+  return invoke("huawei_test", { ...creds });
 }
 
-export async function saveHuawei(masterPassword: string, token: string): Promise<void> {
-  return invoke("huawei_save", { masterPassword, token });
+export async function saveHuawei(masterPassword: string, creds: HuaweiCredsInput): Promise<void> {
+  return invoke("huawei_save", { masterPassword, ...creds });
 }
 
 export async function clearHuawei(masterPassword: string): Promise<void> {
@@ -207,44 +440,53 @@ export async function clearHuawei(masterPassword: string): Promise<void> {
 export async function testBaidu(
   accessKeyId: string,
   secretAccessKey: string,
+  securityToken?: string,
+  endpoint?: string,
 ): Promise<IntegrationTestResult> {
-  return invoke("baidu_test", { accessKeyId, secretAccessKey });
+  return invoke("baidu_test", { accessKeyId, secretAccessKey, securityToken, endpoint });
 }
 
 export async function saveBaidu(
   masterPassword: string,
   accessKeyId: string,
   secretAccessKey: string,
+  securityToken?: string,
+  endpoint?: string,
 ): Promise<void> {
-  return invoke("baidu_save", { masterPassword, accessKeyId, secretAccessKey });
+  return invoke("baidu_save", { masterPassword, accessKeyId, secretAccessKey, securityToken, endpoint });
 }
 
 export async function clearBaidu(masterPassword: string): Promise<void> {
   return invoke("baidu_clear", { masterPassword });
 }
 
-export async function testDnscom(apiKey: string, apiSecret: string): Promise<IntegrationTestResult> {
-  return invoke("dnscom_test", { apiKey, apiSecret });
+export async function testDnscom(
+  apiKey: string,
+  apiSecret: string,
+  endpoint?: string,
+): Promise<IntegrationTestResult> {
+  return invoke("dnscom_test", { apiKey, apiSecret, endpoint });
 }
 
 export async function saveDnscom(
   masterPassword: string,
   apiKey: string,
   apiSecret: string,
+  endpoint?: string,
 ): Promise<void> {
-  return invoke("dnscom_save", { masterPassword, apiKey, apiSecret });
+  return invoke("dnscom_save", { masterPassword, apiKey, apiSecret, endpoint });
 }
 
 export async function clearDnscom(masterPassword: string): Promise<void> {
   return invoke("dnscom_clear", { masterPassword });
 }
 
-export async function testRainyun(apiKey: string): Promise<IntegrationTestResult> {
-  return invoke("rainyun_test", { apiKey });
+export async function testRainyun(apiKey: string, endpoint?: string): Promise<IntegrationTestResult> {
+  return invoke("rainyun_test", { apiKey, endpoint });
 }
 
-export async function saveRainyun(masterPassword: string, apiKey: string): Promise<void> {
-  return invoke("rainyun_save", { masterPassword, apiKey });
+export async function saveRainyun(masterPassword: string, apiKey: string, endpoint?: string): Promise<void> {
+  return invoke("rainyun_save", { masterPassword, apiKey, endpoint });
 }
 
 export async function clearRainyun(masterPassword: string): Promise<void> {
@@ -254,16 +496,33 @@ export async function clearRainyun(masterPassword: string): Promise<void> {
 export async function testTencentCloud(
   secretId: string,
   secretKey: string,
+  sts: StsOptions = {},
+  endpoint?: string,
 ): Promise<IntegrationTestResult> {
-  return invoke("tencentcloud_test", { secretId, secretKey });
+  return invoke("tencentcloud_test", {
+    secretId,
+    secretKey,
+    token: sts.securityToken,
+    roleArn: sts.roleArn,
+    endpoint,
+  });
 }
 
 export async function saveTencentCloud(
   masterPassword: string,
   secretId: string,
   secretKey: string,
+  sts: StsOptions = {},
+  endpoint?: string,
 ): Promise<void> {
-  return invoke("tencentcloud_save", { masterPassword, secretId, secretKey });
+  return invoke("tencentcloud_save", {
+    masterPassword,
+    secretId,
+    secretKey,
+    token: sts.securityToken,
+    roleArn: sts.roleArn,
+    endpoint,
+  });
 }
 
 export async function clearTencentCloud(masterPassword: string): Promise<void> {
@@ -343,3 +602,107 @@ export async function deleteRecord(
     recordId,
   });
 }
+
+export async function setRecordStatus(
+  masterPassword: string,
+  provider: Provider,
+  domainId: string,
+  domainName: string,
+  recordId: string,
+  status: RecordStatus,
+): Promise<void> {
+  return invoke("record_set_status", {
+    masterPassword,
+    provider,
+    domainId,
+    domainName,
+    recordId,
+    status,
+  });
+}
+
+export async function setRecordWeights(
+  masterPassword: string,
+  provider: Provider,
+  domainId: string,
+  domainName: string,
+  weights: RecordWeight[],
+): Promise<void> {
+  return invoke("record_set_weights", {
+    masterPassword,
+    provider,
+    domainId,
+    domainName,
+    weights,
+  });
+}
+
+export async function listMailPresets(): Promise<MailPresetInfo[]> {
+  return invoke("mail_presets_list");
+}
+
+export async function previewMailPreset(
+  masterPassword: string,
+  provider: Provider,
+  domainId: string,
+  domainName: string,
+  preset: string,
+  params: Record<string, string>,
+): Promise<PresetPreviewItem[]> {
+  return invoke("mail_preset_preview", {
+    masterPassword,
+    provider,
+    domainId,
+    domainName,
+    preset,
+    params,
+  });
+}
+
+export async function applyMailPreset(
+  masterPassword: string,
+  provider: Provider,
+  domainId: string,
+  domainName: string,
+  preset: string,
+  params: Record<string, string>,
+  conflictStrategy: ConflictStrategy,
+): Promise<PresetApplyResult[]> {
+  return invoke("mail_preset_apply", {
+    masterPassword,
+    provider,
+    domainId,
+    domainName,
+    preset,
+    params,
+    conflictStrategy,
+  });
+}
+
+export async function lintZone(
+  masterPassword: string,
+  provider: Provider,
+  domainId: string,
+  domainName: string,
+  options: ZoneLintOptions,
+): Promise<LintFinding[]> {
+  return invoke("zone_lint", {
+    masterPassword,
+    provider,
+    domainId,
+    domainName,
+    options,
+  });
+}
+
+export async function searchRecords(masterPassword: string, query: RecordSearchQuery): Promise<RecordSearchResult> {
+  return invoke("records_search", { masterPassword, query });
+}
+
+export async function bulkReplaceRecords(masterPassword: string, req: BulkReplaceRequest): Promise<BulkReplaceResult> {
+  return invoke("records_bulk_replace", { masterPassword, req });
+}
+
+export async function rollbackBulkReplace(masterPassword: string, changes: BulkChange[]): Promise<BulkChange[]> {
+  return invoke("records_bulk_rollback", { masterPassword, changes });
+}
@@ -248,7 +248,7 @@ export function DomainsPage() {
               <div className="flex items-start justify-between">
                 <div className="space-y-1">
                   <h3 className="text-xl font-bold text-[var(--color-accent)] group-hover:text-[var(--color-primary)] transition-colors break-all">
-                    {d.name}
+                    {d.display_name ?? d.name}
                   </h3>
                   <div className="flex items-center gap-2 text-xs font-bold text-[var(--color-text-secondary)] uppercase tracking-wider">
                     <ProviderIcon provider={d.provider} />
@@ -57,14 +57,14 @@ const credentialGuides: Record<Provider, CredentialGuide> = {
     note: "Cloudflare 只会完整展示一次 Token，请创建后立即复制并妥善保存。",
   },
   dnspod: {
-    title: "如何获取 Token ID 和 Token Secret",
+    title: "如何获取 DNSPod API 密钥",
     steps: [
-      "登录 DNSPod 控制台，进入账号中心的 API Token 管理页面。",
-      "创建用于 DNS 解析管理的 Token。",
-      "复制 Token ID 到 ID 输入框。",
-      "复制 Token Secret 到 Token 输入框。",
+      "推荐：登录腾讯云控制台，在访问管理的 API 密钥页面创建 SecretId 和 SecretKey。",
+      "将 SecretId 和 SecretKey 填入下方 API 3.0 输入框。",
+      "旧版方式：在 DNSPod 控制台的 API Token 管理页面创建 Token。",
+      "复制 Token ID 和 Token Secret 到对应输入框。",
     ],
-    note: "请保留解析记录读取和修改权限，避免使用无关权限。",
+    note: "旧版 dnsapi.cn Token 接口即将下线，填写 API 3.0 密钥保存后会替换旧版 Token。",
   },
   aliyun: {
     title: "如何获取 AccessKey",
@@ -77,14 +77,14 @@ const credentialGuides: Record<Provider, CredentialGuide> = {
     note: "建议使用只授予云解析 DNS 管理权限的 RAM 用户。",
   },
   huawei: {
-    title: "如何获取 X-Auth-Token",
+    title: "如何获取 AK/SK 或 X-Auth-Token",
     steps: [
-      "登录华为云并通过 IAM/API Explorer 获取用户 Token。",
-      "选择 DNS 服务可访问的项目或区域。",
-      "复制响应中的 X-Subject-Token。",
-      "粘贴到下方 X-Auth-Token 输入框。",
+      "登录华为云控制台，进入“我的凭证”的访问密钥页面。",
+      "新增访问密钥，复制 AK 和 SK 到对应输入框。",
+      "按需填写区域（默认 cn-north-4），项目 ID 可留空自动获取。",
+      "也可以只填写通过 IAM 获取的 X-Auth-Token。",
     ],
-    note: "Token 通常有有效期，失效后需要重新获取并保存。",
+    note: "推荐使用 AK/SK，Token 有效期只有 24 小时，失效后需要重新获取并保存。",
   },
   baidu: {
     title: "如何获取百度云 AccessKey",
@@ -128,6 +128,18 @@ const credentialGuides: Record<Provider, CredentialGuide> = {
   },
 };
 
+// 自定义接口地址留空时使用的默认地址，仅作输入提示
+const DEFAULT_ENDPOINTS: Record<Provider, string> = {
+  cloudflare: "https://api.cloudflare.com/client/v4",
+  dnspod: "https://dnsapi.cn",
+  aliyun: "https://alidns.aliyuncs.com",
+  huawei: "https://dns.cn-north-4.myhuaweicloud.com",
+  baidu: "https://dns.baidubce.com",
+  dnscom: "https://openapi.dns.com/api",
+  rainyun: "https://api.v2.rainyun.com",
+  tencentcloud: "https://dnspod.tencentcloudapi.com",
+};
+
 function CredentialGuideCard({ guide }: { guide: CredentialGuide }) {
   return (
     <div className="border border-[var(--color-border)] bg-[var(--color-bg)] p-4 space-y-3">
@@ -178,17 +190,25 @@ export function IntegrationsPage() {
 
   const [dpTokenId, setDpTokenId] = useState("");
   const [dpToken, setDpToken] = useState("");
+  const [dpSecretId, setDpSecretId] = useState("");
+  const [dpSecretKey, setDpSecretKey] = useState("");
   const [dpTest, setDpTest] = useState<IntegrationTestResult | null>(null);
 
   const [aliyunAccessKeyId, setAliyunAccessKeyId] = useState("");
   const [aliyunAccessKeySecret, setAliyunAccessKeySecret] = useState("");
+  const [aliyunSecurityToken, setAliyunSecurityToken] = useState("");
+  const [aliyunRoleArn, setAliyunRoleArn] = useState("");
   const [aliyunTest, setAliyunTest] = useState<IntegrationTestResult | null>(null);
 
   const [huaweiToken, setHuaweiToken] = useState("");
+  const [huaweiAccessKeyId, setHuaweiAccessKeyId] = useState("");
+  const [huaweiSecretAccessKey, setHuaweiSecretAccessKey] = useState("");
+  const [huaweiRegion, setHuaweiRegion] = useState("");
   const [huaweiTest, setHuaweiTest] = useState<IntegrationTestResult | null>(null);
 
   const [baiduAccessKeyId, setBaiduAccessKeyId] = useState("");
   const [baiduSecretAccessKey, setBaiduSecretAccessKey] = useState("");
+  const [baiduSecurityToken, setBaiduSecurityToken] = useState("");
   const [baiduTest, setBaiduTest] = useState<IntegrationTestResult | null>(null);
 
   const [dnscomApiKey, setDnscomApiKey] = useState("");
@@ -199,7 +219,10 @@ export function IntegrationsPage() {
   const [rainyunTest, setRainyunTest] = useState<IntegrationTestResult | null>(null);
   const [tencentSecretKey, setTencentSecretKey] = useState("");
   const [tencentSecretId, setTencentSecretId] = useState("");
+  const [tencentToken, setTencentToken] = useState("");
+  const [tencentRoleArn, setTencentRoleArn] = useState("");
   const [tencentTest, setTencentTest] = useState<IntegrationTestResult | null>(null);
+  const [endpoint, setEndpoint] = useState("");
 
   const load = async () => {
     if (!masterPassword) return;
@@ -245,6 +268,7 @@ export function IntegrationsPage() {
     setDnscomTest(null);
     setRainyunTest(null);
     setTencentTest(null);
+    setEndpoint("");
     if (data && (data[provider as keyof IntegrationsInfo]?.configured ?? false)) {
       void loadProviderDomains(provider);
     }
@@ -259,10 +283,10 @@ export function IntegrationsPage() {
   const onCfTest = async () => {
     setCfTest(null);
     try {
-      const r = await testCloudflare(cfApiToken);
+      const r = await testCloudflare(cfApiToken, endpoint);
       setCfTest(r);
       if (r.ok && masterPassword) {
-        await saveCloudflare(masterPassword, cfApiToken);
+        await saveCloudflare(masterPassword, cfApiToken, endpoint);
         setCfApiToken("");
         await load();
         void loadProviderDomains("cloudflare");
@@ -275,11 +299,14 @@ export function IntegrationsPage() {
   const onDpTest = async () => {
     setDpTest(null);
     try {
-      const r = await testDnspod(dpTokenId, dpToken);
+      const api3 = { secretId: dpSecretId, secretKey: dpSecretKey };
+      const r = await testDnspod(dpTokenId, dpToken, api3, endpoint);
       setDpTest(r);
       if (r.ok && masterPassword) {
-        await saveDnspod(masterPassword, dpTokenId, dpToken);
+        await saveDnspod(masterPassword, dpTokenId, dpToken, api3, endpoint);
         setDpToken("");
+        setDpSecretId("");
+        setDpSecretKey("");
         await load();
         void loadProviderDomains("dnspod");
       }
@@ -291,11 +318,13 @@ export function IntegrationsPage() {
   const onAliyunTest = async () => {
     setAliyunTest(null);
     try {
-      const r = await testAliyun(aliyunAccessKeyId, aliyunAccessKeySecret);
+      const sts = { securityToken: aliyunSecurityToken, roleArn: aliyunRoleArn };
+      const r = await testAliyun(aliyunAccessKeyId, aliyunAccessKeySecret, sts, endpoint);
       setAliyunTest(r);
       if (r.ok && masterPassword) {
-        await saveAliyun(masterPassword, aliyunAccessKeyId, aliyunAccessKeySecret);
+        await saveAliyun(masterPassword, aliyunAccessKeyId, aliyunAccessKeySecret, sts, endpoint);
         setAliyunAccessKeySecret("");
+        setAliyunSecurityToken("");
         await load();
         void loadProviderDomains("aliyun");
       }
@@ -307,11 +336,20 @@ export function IntegrationsPage() {
   const onHuaweiTest = async () => {
     setHuaweiTest(null);
     try {
-      const r = await testHuawei(huaweiToken);
+      const creds = {
+        token: huaweiToken,
+        accessKeyId: huaweiAccessKeyId,
+        secretAccessKey: huaweiSecretAccessKey,
+        region: huaweiRegion,
+        endpoint,
+      };
+      const r = await testHuawei(creds);
       setHuaweiTest(r);
       if (r.ok && masterPassword) {
-        await saveHuawei(masterPassword, huaweiToken);
+        await saveHuawei(masterPassword, creds);
         setHuaweiToken("");
+        setHuaweiAccessKeyId("");
+        setHuaweiSecretAccessKey("");
         await load();
         void loadProviderDomains("huawei");
       }
@@ -323,11 +361,12 @@ export function IntegrationsPage() {
   const onBaiduTest = async () => {
     setBaiduTest(null);
     try {
-      const r = await testBaidu(baiduAccessKeyId, baiduSecretAccessKey);
+      const r = await testBaidu(baiduAccessKeyId, baiduSecretAccessKey, baiduSecurityToken, endpoint);
       setBaiduTest(r);
       if (r.ok && masterPassword) {
-        await saveBaidu(masterPassword, baiduAccessKeyId, baiduSecretAccessKey);
+        await saveBaidu(masterPassword, baiduAccessKeyId, baiduSecretAccessKey, baiduSecurityToken, endpoint);
         setBaiduSecretAccessKey("");
+        setBaiduSecurityToken("");
         await load();
         void loadProviderDomains("baidu");
       }
@@ -339,10 +378,10 @@ export function IntegrationsPage() {
   const onDnscomTest = async () => {
     setDnscomTest(null);
     try {
-      const r = await testDnscom(dnscomApiKey, dnscomApiSecret);
+      const r = await testDnscom(dnscomApiKey, dnscomApiSecret, endpoint);
       setDnscomTest(r);
       if (r.ok && masterPassword) {
-        await saveDnscom(masterPassword, dnscomApiKey, dnscomApiSecret);
+        await saveDnscom(masterPassword, dnscomApiKey, dnscomApiSecret, endpoint);
         setDnscomApiSecret("");
         await load();
         void loadProviderDomains("dnscom");
@@ -355,10 +394,10 @@ export function IntegrationsPage() {
   const onRainyunTest = async () => {
     setRainyunTest(null);
     try {
-      const r = await testRainyun(rainyunApiKey);
+      const r = await testRainyun(rainyunApiKey, endpoint);
       setRainyunTest(r);
       if (r.ok && masterPassword) {
-        await saveRainyun(masterPassword, rainyunApiKey);
+        await saveRainyun(masterPassword, rainyunApiKey, endpoint);
         setRainyunApiKey("");
         await load();
         void loadProviderDomains("rainyun");
@@ -371,12 +410,14 @@ export function IntegrationsPage() {
   const onTencentTest = async () => {
     setTencentTest(null);
     try {
-      const r = await testTencentCloud(tencentSecretId, tencentSecretKey);
+      const sts = { securityToken: tencentToken, roleArn: tencentRoleArn };
+      const r = await testTencentCloud(tencentSecretId, tencentSecretKey, sts, endpoint);
       setTencentTest(r);
       if (r.ok && masterPassword) {
-        await saveTencentCloud(masterPassword, tencentSecretId, tencentSecretKey);
+        await saveTencentCloud(masterPassword, tencentSecretId, tencentSecretKey, sts, endpoint);
         setTencentSecretId("");
         setTencentSecretKey("");
+        setTencentToken("");
         await load();
         void loadProviderDomains("tencentcloud");
       }
@@ -581,6 +622,14 @@ export function IntegrationsPage() {
       }
     })();
     const guide = credentialGuides[activeProvider];
+    const endpointInput = (
+      <Input
+        label="自定义接口地址（可选）"
+        value={endpoint}
+        onChange={(e) => setEndpoint(e.target.value)}
+        placeholder={DEFAULT_ENDPOINTS[activeProvider]}
+      />
+    );
     const testResult = (() => {
       switch (activeProvider) {
         case "cloudflare":
@@ -685,6 +734,7 @@ export function IntegrationsPage() {
                     onChange={(e) => setCfApiToken(e.target.value)}
                     placeholder="Cloudflare API Token"
                   />
+                  {endpointInput}
                   <Button onClick={onCfTest} className="w-full mt-4">
                     验证并保存
                   </Button>
@@ -692,18 +742,33 @@ export function IntegrationsPage() {
               ) : activeProvider === "dnspod" ? (
                 <div className="space-y-4">
                   <Input
-                    label="ID"
+                    label="SecretId（API 3.0）"
+                    type="password"
+                    value={dpSecretId}
+                    onChange={(e) => setDpSecretId(e.target.value)}
+                    placeholder="SecretId"
+                  />
+                  <Input
+                    label="SecretKey（API 3.0）"
+                    type="password"
+                    value={dpSecretKey}
+                    onChange={(e) => setDpSecretKey(e.target.value)}
+                    placeholder="SecretKey"
+                  />
+                  <Input
+                    label="ID（旧版）"
                     value={dpTokenId}
                     onChange={(e) => setDpTokenId(e.target.value)}
                     placeholder="Token ID"
                   />
                   <Input
-                    label="Token"
+                    label="Token（旧版）"
                     type="password"
                     value={dpToken}
                     onChange={(e) => setDpToken(e.target.value)}
                     placeholder="Token Secret"
                   />
+                  {endpointInput}
                   <Button onClick={onDpTest} className="w-full mt-4">
                     验证并保存
                   </Button>
@@ -723,6 +788,20 @@ export function IntegrationsPage() {
                     onChange={(e) => setAliyunAccessKeySecret(e.target.value)}
                     placeholder="AccessKeySecret"
                   />
+                  <Input
+                    label="SecurityToken（可选）"
+                    type="password"
+                    value={aliyunSecurityToken}
+                    onChange={(e) => setAliyunSecurityToken(e.target.value)}
+                    placeholder="使用 STS 临时凭证时填写"
+                  />
+                  <Input
+                    label="RoleArn（可选）"
+                    value={aliyunRoleArn}
+                    onChange={(e) => setAliyunRoleArn(e.target.value)}
+                    placeholder="acs:ram::123456789012****:role/dns-admin"
+                  />
+                  {endpointInput}
                   <Button onClick={onAliyunTest} className="w-full mt-4">
                     验证并保存
                   </Button>
@@ -730,12 +809,32 @@ export function IntegrationsPage() {
               ) : activeProvider === "huawei" ? (
                 <div className="space-y-4">
                   <Input
-                    label="X-Auth-Token"
+                    label="Access Key (AK)"
+                    value={huaweiAccessKeyId}
+                    onChange={(e) => setHuaweiAccessKeyId(e.target.value)}
+                    placeholder="Access Key ID"
+                  />
+                  <Input
+                    label="Secret Key (SK)"
+                    type="password"
+                    value={huaweiSecretAccessKey}
+                    onChange={(e) => setHuaweiSecretAccessKey(e.target.value)}
+                    placeholder="Secret Access Key"
+                  />
+                  <Input
+                    label="区域"
+                    value={huaweiRegion}
+                    onChange={(e) => setHuaweiRegion(e.target.value)}
+                    placeholder="cn-north-4"
+                  />
+                  <Input
+                    label="X-Auth-Token（可选）"
                     type="password"
                     value={huaweiToken}
                     onChange={(e) => setHuaweiToken(e.target.value)}
                     placeholder="X-Auth-Token"
                   />
+                  {endpointInput}
                   <Button onClick={onHuaweiTest} className="w-full mt-4">
                     验证并保存
                   </Button>
@@ -755,6 +854,14 @@ export function IntegrationsPage() {
                     onChange={(e) => setBaiduSecretAccessKey(e.target.value)}
                     placeholder="SecretAccessKey"
                   />
+                  <Input
+                    label="SessionToken（可选）"
+                    type="password"
+                    value={baiduSecurityToken}
+                    onChange={(e) => setBaiduSecurityToken(e.target.value)}
+                    placeholder="使用 STS 临时凭证时填写"
+                  />
+                  {endpointInput}
                   <Button onClick={onBaiduTest} className="w-full mt-4">
                     验证并保存
                   </Button>
@@ -774,6 +881,7 @@ export function IntegrationsPage() {
                     onChange={(e) => setDnscomApiSecret(e.target.value)}
                     placeholder="ApiSecret"
                   />
+                  {endpointInput}
                   <Button onClick={onDnscomTest} className="w-full mt-4">
                     验证并保存
                   </Button>
@@ -794,6 +902,20 @@ export function IntegrationsPage() {
                     onChange={(e) => setTencentSecretKey(e.target.value)}
                     placeholder="SecretKey"
                   />
+                  <Input
+                    label="Token（可选）"
+                    type="password"
+                    value={tencentToken}
+                    onChange={(e) => setTencentToken(e.target.value)}
+                    placeholder="使用临时密钥时填写"
+                  />
+                  <Input
+                    label="RoleArn（可选）"
+                    value={tencentRoleArn}
+                    onChange={(e) => setTencentRoleArn(e.target.value)}
+                    placeholder="qcs::cam::uin/100000000001:roleName/dns-admin"
+                  />
+                  {endpointInput}
                   <Button onClick={onTencentTest} className="w-full mt-4">
                     验证并保存
                   </Button>
@@ -807,6 +929,7 @@ export function IntegrationsPage() {
                     onChange={(e) => setRainyunApiKey(e.target.value)}
                     placeholder="ApiKey"
                   />
+                  {endpointInput}
                   <Button onClick={onRainyunTest} className="w-full mt-4">
                     验证并保存
                   </Button>
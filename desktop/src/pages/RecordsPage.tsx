@@ -1,6 +1,6 @@
 import { useEffect, useMemo, useState } from "react";
 import { useParams, useSearchParams, useNavigate } from "react-router-dom";
-import { ArrowLeft, Plus, RefreshCw, Trash2, Edit2, AlertCircle } from "lucide-react";
+import { ArrowLeft, Plus, RefreshCw, Trash2, Edit2, AlertCircle, Mail, Stethoscope } from "lucide-react";
 import { Modal } from "../components/Modal";
 import { Dropdown, type DropdownOption } from "../components/Dropdown";
 import { Button } from "../components/Button";
@@ -9,24 +9,60 @@ import { Card } from "../components/Card";
 import { Badge } from "../components/Badge";
 import { useApp } from "../app/AppContext";
 import {
+  applyMailPreset,
   createRecord,
   deleteRecord,
+  lintZone,
+  listMailPresets,
   listRecords,
+  previewMailPreset,
   resolveErrorMessage,
   updateRecord,
   type ConflictStrategy,
   type DomainItem,
   type DnsRecord,
+  type LintFinding,
+  type LintSeverity,
+  type MailPresetInfo,
+  type PresetAction,
+  type PresetApplyResult,
+  type PresetPreviewItem,
+  type ZoneLintOptions,
   type Provider,
   type RecordCreateRequest,
+  type RecordExtra,
   type RecordUpdateRequest,
+  type SvcParams,
+  type UrlRedirect,
 } from "../lib/api";
 
-const RECORD_TYPES = ["A", "AAAA", "CNAME", "TXT", "MX", "NS", "SRV", "CAA"] as const;
+const RECORD_TYPES = [
+  "A", "AAAA", "CNAME", "TXT", "MX", "NS", "SRV", "CAA", "PTR", "NAPTR", "SSHFP", "TLSA", "DS", "HTTPS", "SVCB", "URL", "ALIAS",
+] as const;
+
+const EXTRA_FIELDS: (keyof RecordExtra)[] = [
+  "sshfp_algorithm", "sshfp_fp_type",
+  "tlsa_usage", "tlsa_selector", "tlsa_matching_type",
+  "naptr_order", "naptr_preference", "naptr_flags", "naptr_service", "naptr_regexp",
+  "ds_key_tag", "ds_algorithm", "ds_digest_type",
+  "svc_priority", "svc_params", "url_redirect",
+];
+
+const URL_REDIRECT_OPTIONS: DropdownOption<UrlRedirect>[] = [
+  { value: "permanent", label: "显性转发 (301)" },
+  { value: "temporary", label: "显性转发 (302)" },
+  { value: "implicit", label: "隐性转发" },
+];
+
+const EMPTY_SVC_PARAMS: SvcParams = { alpn: [], port: null, ipv4hint: [], ipv6hint: [], ech: null, other: [] };
+
+function splitList(value: string) {
+  return value.split(",").map((v) => v.trim()).filter(Boolean);
+}
 const RECORDS_CACHE_PREFIX = "laochen_dns_records_cache_v1";
 const DOMAINS_CACHE_PREFIX = "laochen_dns_domains_cache_v1";
 
-function providerLabel(p: Provider) {
+export function providerLabel(p: Provider) {
   switch (p) {
     case "cloudflare":
       return "Cloudflare";
@@ -49,6 +85,14 @@ function providerLabel(p: Provider) {
   }
 }
 
+function pickExtra(source: RecordExtra): RecordExtra {
+  const extra: RecordExtra = {};
+  for (const key of EXTRA_FIELDS) {
+    if (source[key] != null) (extra as Record<string, unknown>)[key] = source[key];
+  }
+  return extra;
+}
+
 function buildRecordsCacheKey(provider: Provider, domainId: string) {
   return `${RECORDS_CACHE_PREFIX}:${provider}:${encodeURIComponent(domainId)}`;
 }
@@ -108,6 +152,8 @@ export function RecordsPage() {
   const [editing, setEditing] = useState<DnsRecord | null>(null);
   const [creating, setCreating] = useState(false);
   const [confirming, setConfirming] = useState<DnsRecord | null>(null);
+  const [applyingPreset, setApplyingPreset] = useState(false);
+  const [linting, setLinting] = useState(false);
 
   const resolvedDomainName = useMemo(() => {
     if (domainName) return domainName;
@@ -223,6 +269,14 @@ export function RecordsPage() {
             <RefreshCw className="w-4 h-4 mr-2" />
             刷新
           </Button>
+          <Button variant="outline" onClick={() => setLinting(true)} disabled={busy} className="border-[var(--color-border)] hover:bg-[var(--color-primary)] hover:text-white hover:border-[var(--color-primary)]">
+            <Stethoscope className="w-4 h-4 mr-2" />
+            体检
+          </Button>
+          <Button variant="outline" onClick={() => setApplyingPreset(true)} disabled={busy} className="border-[var(--color-border)] hover:bg-[var(--color-primary)] hover:text-white hover:border-[var(--color-primary)]">
+            <Mail className="w-4 h-4 mr-2" />
+            邮箱模板
+          </Button>
           <Button onClick={() => setCreating(true)} disabled={busy} className="bg-[var(--color-primary)] text-white hover:bg-black">
             <Plus className="w-4 h-4 mr-2" />
             新增记录
@@ -256,7 +310,7 @@ export function RecordsPage() {
                   <td className="px-6 py-4">
                     <Badge variant="outline" className="font-mono font-bold border-[var(--color-text)] text-[var(--color-text)]">{r.record_type}</Badge>
                   </td>
-                  <td className="px-6 py-4 font-bold text-[var(--color-accent)]">{r.name}</td>
+                  <td className="px-6 py-4 font-bold text-[var(--color-accent)]">{r.display_name ?? r.name}</td>
                   <td className="px-6 py-4 text-[var(--color-text)] font-mono break-all text-xs">
                     {r.content}
                   </td>
@@ -341,6 +395,9 @@ export function RecordsPage() {
                 srv_port: req.srv_port ?? null,
                 caa_flags: req.caa_flags ?? null,
                 caa_tag: req.caa_tag ?? null,
+                original_type: editing.record_type,
+                original_name: editing.name,
+                ...pickExtra(req),
               };
               await updateRecord(masterPassword, provider, domainId, resolvedDomainName, update);
               notifySuccess("记录已更新");
@@ -354,6 +411,27 @@ export function RecordsPage() {
         />
       )}
 
+      {applyingPreset && masterPassword && (
+        <MailPresetModal
+          onClose={() => setApplyingPreset(false)}
+          onPreview={(preset, params) =>
+            previewMailPreset(masterPassword, provider, domainId, resolvedDomainName, preset, params)
+          }
+          onApply={async (preset, params, strategy) => {
+            const results = await applyMailPreset(masterPassword, provider, domainId, resolvedDomainName, preset, params, strategy);
+            await refreshRecords();
+            return results;
+          }}
+        />
+      )}
+
+      {linting && masterPassword && (
+        <ZoneLintModal
+          onClose={() => setLinting(false)}
+          onRun={(options) => lintZone(masterPassword, provider, domainId, resolvedDomainName, options)}
+        />
+      )}
+
       {confirming && (
         <Modal title="删除记录" onClose={() => setConfirming(null)}>
           <div className="space-y-6">
@@ -362,7 +440,7 @@ export function RecordsPage() {
               <div>
                 <p className="font-bold uppercase tracking-wide">您确定吗？</p>
                 <p className="text-sm mt-2 font-mono bg-white/50 p-2 border border-red-200 inline-block">
-                  {confirming.record_type} {confirming.name}
+                  {confirming.record_type} {confirming.display_name ?? confirming.name}
                 </p>
                 <p className="text-xs mt-2 opacity-80">此操作无法撤销。</p>
               </div>
@@ -394,7 +472,7 @@ export function RecordModal({
   onSubmit: (req: RecordCreateRequest) => Promise<void>;
 }) {
   const [recordType, setRecordType] = useState(initial?.record_type ?? "A");
-  const [name, setName] = useState(initial?.name ?? "@");
+  const [name, setName] = useState(initial?.display_name ?? initial?.name ?? "@");
   const [content, setContent] = useState(initial?.content ?? "");
   const [ttl, setTtl] = useState<number>(initial?.ttl ?? 600);
 
@@ -404,6 +482,18 @@ export function RecordModal({
   const [srvPort, setSrvPort] = useState<number>(initial?.srv_port ?? 0);
   const [caaFlags, setCaaFlags] = useState<number>(initial?.caa_flags ?? 0);
   const [caaTag, setCaaTag] = useState<string>(initial?.caa_tag ?? "issue");
+  const [extra, setExtra] = useState<RecordExtra>(initial ? pickExtra(initial) : {});
+  const setExtraNumber = (key: keyof RecordExtra) => (e: { target: { value: string } }) =>
+    setExtra((prev) => ({ ...prev, [key]: e.target.value === "" ? null : Number(e.target.value) }));
+  const setExtraText = (key: keyof RecordExtra) => (e: { target: { value: string } }) =>
+    setExtra((prev) => ({ ...prev, [key]: e.target.value }));
+  const svcParams = extra.svc_params ?? EMPTY_SVC_PARAMS;
+  const setSvcParams = (patch: Partial<SvcParams>) =>
+    setExtra((prev) => ({ ...prev, svc_params: { ...(prev.svc_params ?? EMPTY_SVC_PARAMS), ...patch } }));
+  // 列表类参数按文本编辑，提交时再拆分
+  const [alpnText, setAlpnText] = useState(svcParams.alpn.join(","));
+  const [ipv4hintText, setIpv4hintText] = useState(svcParams.ipv4hint.join(","));
+  const [ipv6hintText, setIpv6hintText] = useState(svcParams.ipv6hint.join(","));
 
   const [conflictStrategy, setConflictStrategy] = useState<ConflictStrategy>("do_not_create");
   const [error, setError] = useState<string | null>(null);
@@ -417,8 +507,8 @@ export function RecordModal({
     const trimmedContent = content.trim();
     if (!trimmedContent) return "记录值不能为空";
     
-    // Add more validation if needed, similar to SmartResolvePage
-    if (ttl < 60 || ttl > 86400) return "TTL 必须在 60-86400 秒之间";
+    // 各服务商的 TTL 下限不同（Cloudflare 的 1 表示自动），由后端按服务商校验
+    if (ttl < 1 || ttl > 86400) return "TTL 必须在 1-86400 秒之间";
     
     if (recordType === "A") {
       const ipv4Regex = /^(?:(?:25[0-5]|2[0-4][0-9]|[01]?[0-9][0-9]?)\.){3}(?:25[0-5]|2[0-4][0-9]|[01]?[0-9][0-9]?)$/;
@@ -430,9 +520,17 @@ export function RecordModal({
       if (!ipv6Regex.test(trimmedContent) || !trimmedContent.includes(":")) return "AAAA 记录必须是有效的 IPv6 地址";
     }
 
-    if (recordType === "CNAME" || recordType === "NS" || recordType === "MX" || recordType === "SRV") {
+    if (recordType === "CNAME" || recordType === "NS" || recordType === "MX" || recordType === "SRV" || recordType === "PTR" || recordType === "ALIAS") {
        if (!trimmedContent.includes(".")) return "记录值必须是有效域名";
     }
+
+    if (recordType === "URL" && !/^https?:\/\/\S+$/.test(trimmedContent)) {
+      return "转发地址必须以 http:// 或 https:// 开头";
+    }
+
+    if ((recordType === "SSHFP" || recordType === "TLSA" || recordType === "DS") && !/^[0-9a-fA-F]+$/.test(trimmedContent)) {
+      return "记录值必须是十六进制字符串";
+    }
     
     return null;
   };
@@ -456,6 +554,18 @@ export function RecordModal({
       srv_port: srvPort,
       caa_flags: caaFlags,
       caa_tag: caaTag,
+      ...extra,
+      ...(recordType === "URL" ? { url_redirect: extra.url_redirect ?? "permanent" } : {}),
+      ...(recordType === "HTTPS" || recordType === "SVCB"
+        ? {
+            svc_params: {
+              ...svcParams,
+              alpn: splitList(alpnText),
+              ipv4hint: splitList(ipv4hintText),
+              ipv6hint: splitList(ipv6hintText),
+            },
+          }
+        : {}),
       conflict_strategy: initial ? "do_not_create" : conflictStrategy,
     });
   };
@@ -554,6 +664,87 @@ export function RecordModal({
           </div>
         )}
 
+        {recordType === "URL" && (
+          <div className="space-y-1.5">
+            <label className="text-xs font-bold text-[var(--color-text-secondary)] uppercase tracking-wider">跳转方式</label>
+            <Dropdown
+              value={extra.url_redirect ?? "permanent"}
+              options={URL_REDIRECT_OPTIONS}
+              onChange={(v) => setExtra((prev) => ({ ...prev, url_redirect: v }))}
+              className="w-full"
+            />
+            <p className="text-xs text-[var(--color-text-secondary)]">DNSPod、腾讯云和 DNS.COM 的显性转发为 301，阿里云为 302</p>
+          </div>
+        )}
+
+        {(recordType === "HTTPS" || recordType === "SVCB") && (
+          <div className="grid grid-cols-2 gap-4">
+            <Input label="优先级 (0 为别名模式)" type="number" value={extra.svc_priority ?? ""} onChange={setExtraNumber("svc_priority")} />
+            <Input
+              label="port"
+              type="number"
+              value={svcParams.port ?? ""}
+              onChange={(e) => setSvcParams({ port: e.target.value === "" ? null : Number(e.target.value) })}
+            />
+            <Input
+              label="alpn (逗号分隔)"
+              value={alpnText}
+              onChange={(e) => setAlpnText(e.target.value)}
+              placeholder="h3,h2"
+            />
+            <Input
+              label="ipv4hint"
+              value={ipv4hintText}
+              onChange={(e) => setIpv4hintText(e.target.value)}
+            />
+            <Input
+              label="ipv6hint"
+              value={ipv6hintText}
+              onChange={(e) => setIpv6hintText(e.target.value)}
+            />
+            <Input
+              label="ech"
+              value={svcParams.ech ?? ""}
+              onChange={(e) => setSvcParams({ ech: e.target.value || null })}
+            />
+          </div>
+        )}
+
+        {recordType === "SSHFP" && (
+          <div className="grid grid-cols-2 gap-4">
+            <Input label="算法" type="number" value={extra.sshfp_algorithm ?? ""} onChange={setExtraNumber("sshfp_algorithm")} />
+            <Input label="指纹类型" type="number" value={extra.sshfp_fp_type ?? ""} onChange={setExtraNumber("sshfp_fp_type")} />
+          </div>
+        )}
+
+        {recordType === "TLSA" && (
+          <div className="grid grid-cols-3 gap-4">
+            <Input label="用途" type="number" value={extra.tlsa_usage ?? ""} onChange={setExtraNumber("tlsa_usage")} />
+            <Input label="选择器" type="number" value={extra.tlsa_selector ?? ""} onChange={setExtraNumber("tlsa_selector")} />
+            <Input label="匹配类型" type="number" value={extra.tlsa_matching_type ?? ""} onChange={setExtraNumber("tlsa_matching_type")} />
+          </div>
+        )}
+
+        {recordType === "NAPTR" && (
+          <div className="grid grid-cols-2 gap-4">
+            <Input label="顺序" type="number" value={extra.naptr_order ?? ""} onChange={setExtraNumber("naptr_order")} />
+            <Input label="优先级" type="number" value={extra.naptr_preference ?? ""} onChange={setExtraNumber("naptr_preference")} />
+            <Input label="Flags" value={extra.naptr_flags ?? ""} onChange={setExtraText("naptr_flags")} />
+            <Input label="Service" value={extra.naptr_service ?? ""} onChange={setExtraText("naptr_service")} />
+            <div className="col-span-2">
+              <Input label="正则 (记录值填 .)" value={extra.naptr_regexp ?? ""} onChange={setExtraText("naptr_regexp")} />
+            </div>
+          </div>
+        )}
+
+        {recordType === "DS" && (
+          <div className="grid grid-cols-3 gap-4">
+            <Input label="Key Tag" type="number" value={extra.ds_key_tag ?? ""} onChange={setExtraNumber("ds_key_tag")} />
+            <Input label="算法" type="number" value={extra.ds_algorithm ?? ""} onChange={setExtraNumber("ds_algorithm")} />
+            <Input label="摘要类型" type="number" value={extra.ds_digest_type ?? ""} onChange={setExtraNumber("ds_digest_type")} />
+          </div>
+        )}
+
         {!initial && (
            <div className="space-y-1.5">
             <label className="text-xs font-bold text-[var(--color-text-secondary)] uppercase tracking-wider">冲突策略</label>
@@ -561,8 +752,9 @@ export function RecordModal({
               value={conflictStrategy}
               options={[
                 { value: "do_not_create", label: "不创建 (失败)" },
-                { value: "keep_existing", label: "保留现有 (忽略)" },
-                { value: "replace_existing", label: "替换现有" },
+                { value: "overwrite", label: "覆盖现有" },
+                { value: "append", label: "追加到现有记录集" },
+                { value: "replace_all", label: "替换整个记录集" },
               ]}
               onChange={(v) => setConflictStrategy(v as ConflictStrategy)}
               className="w-full"
@@ -582,3 +774,253 @@ export function RecordModal({
     </Modal>
   );
 }
+
+const PRESET_ACTION_LABELS: Record<PresetAction, string> = {
+  create: "新增",
+  skip: "已存在",
+  conflict: "冲突",
+};
+
+function MailPresetModal({
+  onClose,
+  onPreview,
+  onApply,
+}: {
+  onClose: () => void;
+  onPreview: (preset: string, params: Record<string, string>) => Promise<PresetPreviewItem[]>;
+  onApply: (preset: string, params: Record<string, string>, strategy: ConflictStrategy) => Promise<PresetApplyResult[]>;
+}) {
+  const [presets, setPresets] = useState<MailPresetInfo[]>([]);
+  const [presetId, setPresetId] = useState("");
+  const [values, setValues] = useState<Record<string, string>>({});
+  const [strategy, setStrategy] = useState<ConflictStrategy>("do_not_create");
+  const [preview, setPreview] = useState<PresetPreviewItem[] | null>(null);
+  const [results, setResults] = useState<PresetApplyResult[] | null>(null);
+  const [busy, setBusy] = useState(false);
+  const [error, setError] = useState<string | null>(null);
+
+  useEffect(() => {
+    listMailPresets()
+      .then((list) => {
+        setPresets(list);
+        if (list.length > 0) setPresetId(list[0].id);
+      })
+      .catch((e) => setError(resolveErrorMessage(e)));
+  }, []);
+
+  const preset = presets.find((p) => p.id === presetId);
+
+  // 切换模板或改参数后，之前的预览就作废了
+  const resetPreview = () => {
+    setPreview(null);
+    setResults(null);
+  };
+
+  const run = async (action: () => Promise<void>) => {
+    setBusy(true);
+    setError(null);
+    try {
+      await action();
+    } catch (e) {
+      setError(resolveErrorMessage(e));
+    } finally {
+      setBusy(false);
+    }
+  };
+
+  const rows = results ?? preview ?? [];
+
+  return (
+    <Modal title="邮箱模板" onClose={onClose}>
+      <div className="space-y-6">
+        {error && (
+          <div className="bg-red-50 text-red-600 p-4 border border-red-200 text-sm font-medium flex items-center gap-2">
+            <AlertCircle className="w-4 h-4" />
+            {error}
+          </div>
+        )}
+
+        <div className="space-y-1.5">
+          <label className="text-xs font-bold text-[var(--color-text-secondary)] uppercase tracking-wider">邮箱服务</label>
+          <Dropdown
+            value={presetId}
+            options={presets.map((p) => ({ value: p.id, label: p.label }))}
+            onChange={(v) => {
+              setPresetId(v);
+              setValues({});
+              resetPreview();
+            }}
+            className="w-full"
+          />
+        </div>
+
+        {preset?.params.map((param) => (
+          <Input
+            key={param.key}
+            label={param.required ? param.label : `${param.label}（可选）`}
+            value={values[param.key] ?? ""}
+            placeholder={param.default_value ?? ""}
+            onChange={(e) => {
+              setValues((prev) => ({ ...prev, [param.key]: e.target.value }));
+              resetPreview();
+            }}
+          />
+        ))}
+
+        {rows.length > 0 && (
+          <div className="border border-[var(--color-border)] divide-y divide-[var(--color-border)] text-xs">
+            {rows.map((row, i) => {
+              const conflicts = "conflicts" in row ? row.conflicts : [];
+              const failure = "error" in row ? row.error : null;
+              return (
+                <div key={i} className="p-3 space-y-1">
+                  <div className="flex items-center gap-2">
+                    <Badge variant="outline" className="font-mono">{row.request.record_type}</Badge>
+                    <span className="font-bold">{row.request.name}</span>
+                    <span className="ml-auto text-[var(--color-text-secondary)]">
+                      {results ? (failure ? "失败" : row.action === "skip" ? "已跳过" : "已写入") : PRESET_ACTION_LABELS[row.action]}
+                    </span>
+                  </div>
+                  <div className="font-mono break-all">{row.request.content}</div>
+                  {conflicts.map((c) => (
+                    <div key={c.id} className="font-mono break-all text-red-600">现有: {c.content}</div>
+                  ))}
+                  {failure && <div className="text-red-600">{failure}</div>}
+                </div>
+              );
+            })}
+          </div>
+        )}
+
+        {preview && !results && (
+          <div className="space-y-1.5">
+            <label className="text-xs font-bold text-[var(--color-text-secondary)] uppercase tracking-wider">冲突策略</label>
+            <Dropdown
+              value={strategy}
+              options={[
+                { value: "do_not_create", label: "跳过冲突的记录" },
+                { value: "append", label: "保留现有记录并追加" },
+                { value: "replace_all", label: "替换冲突的现有记录" },
+              ]}
+              onChange={(v) => setStrategy(v as ConflictStrategy)}
+              className="w-full"
+            />
+          </div>
+        )}
+
+        <div className="flex justify-end gap-3 pt-6 border-t border-[var(--color-border)]">
+          <Button variant="secondary" onClick={onClose} className="text-xs uppercase tracking-wide">
+            {results ? "关闭" : "取消"}
+          </Button>
+          {!preview && (
+            <Button
+              onClick={() => void run(async () => setPreview(await onPreview(presetId, values)))}
+              loading={busy}
+              disabled={!preset}
+              className="text-xs uppercase tracking-wide"
+            >
+              预览
+            </Button>
+          )}
+          {preview && !results && (
+            <Button
+              onClick={() => void run(async () => setResults(await onApply(presetId, values, strategy)))}
+              loading={busy}
+              className="text-xs uppercase tracking-wide"
+            >
+              应用
+            </Button>
+          )}
+        </div>
+      </div>
+    </Modal>
+  );
+}
+
+const SEVERITY_STYLES: Record<LintSeverity, { label: string; className: string }> = {
+  error: { label: "错误", className: "border-red-600 text-red-600" },
+  warning: { label: "警告", className: "border-amber-600 text-amber-600" },
+  info: { label: "提示", className: "border-[var(--color-text-secondary)] text-[var(--color-text-secondary)]" },
+};
+
+function ZoneLintModal({
+  onClose,
+  onRun,
+}: {
+  onClose: () => void;
+  onRun: (options: ZoneLintOptions) => Promise<LintFinding[]>;
+}) {
+  const [resolver, setResolver] = useState("");
+  const [issuers, setIssuers] = useState("");
+  const [findings, setFindings] = useState<LintFinding[] | null>(null);
+  const [busy, setBusy] = useState(false);
+  const [error, setError] = useState<string | null>(null);
+
+  const run = async () => {
+    setBusy(true);
+    setError(null);
+    try {
+      setFindings(await onRun({ resolver: resolver.trim() || null, issuers: splitList(issuers) }));
+    } catch (e) {
+      setError(resolveErrorMessage(e));
+    } finally {
+      setBusy(false);
+    }
+  };
+
+  return (
+    <Modal title="解析体检" onClose={onClose}>
+      <div className="space-y-6">
+        {error && (
+          <div className="bg-red-50 text-red-600 p-4 border border-red-200 text-sm font-medium flex items-center gap-2">
+            <AlertCircle className="w-4 h-4" />
+            {error}
+          </div>
+        )}
+
+        <Input
+          label="DoH 解析器（可选）"
+          value={resolver}
+          placeholder="https://dns.alidns.com/resolve"
+          onChange={(e) => setResolver(e.target.value)}
+        />
+        <Input
+          label="在用的证书签发机构（逗号分隔，可选）"
+          value={issuers}
+          placeholder="letsencrypt.org"
+          onChange={(e) => setIssuers(e.target.value)}
+        />
+
+        {findings && findings.length === 0 && (
+          <p className="text-sm text-[var(--color-text-secondary)]">没有发现问题</p>
+        )}
+
+        {findings && findings.length > 0 && (
+          <div className="border border-[var(--color-border)] divide-y divide-[var(--color-border)] text-xs max-h-96 overflow-y-auto">
+            {findings.map((f, i) => (
+              <div key={i} className="p-3 space-y-1">
+                <div className="flex items-center gap-2">
+                  <Badge variant="outline" className={SEVERITY_STYLES[f.severity].className}>
+                    {SEVERITY_STYLES[f.severity].label}
+                  </Badge>
+                  <span className="font-bold break-all">{f.name}</span>
+                </div>
+                <div>{f.message}</div>
+                <div className="text-[var(--color-text-secondary)]">建议: {f.suggestion}</div>
+              </div>
+            ))}
+          </div>
+        )}
+
+        <div className="flex justify-end gap-3 pt-6 border-t border-[var(--color-border)]">
+          <Button variant="secondary" onClick={onClose} className="text-xs uppercase tracking-wide">
+            关闭
+          </Button>
+          <Button onClick={() => void run()} loading={busy} className="text-xs uppercase tracking-wide">
+            {findings ? "重新体检" : "开始体检"}
+          </Button>
+        </div>
+      </div>
+    </Modal>
+  );
+}
@@ -0,0 +1,371 @@
+import { useState } from "react";
+import { useNavigate } from "react-router-dom";
+import { AlertCircle, RefreshCw, Replace, Search } from "lucide-react";
+import { useApp } from "../app/AppContext";
+import { Dropdown, type DropdownOption } from "../components/Dropdown";
+import { Button } from "../components/Button";
+import { Input } from "../components/Input";
+import { Badge } from "../components/Badge";
+import { Card } from "../components/Card";
+import { Modal } from "../components/Modal";
+import {
+  bulkReplaceRecords,
+  resolveErrorMessage,
+  rollbackBulkReplace,
+  searchRecords,
+  type BulkChange,
+  type BulkChangeStatus,
+  type BulkReplaceRequest,
+  type BulkReplaceResult,
+  type Provider,
+  type RecordSearchFailure,
+  type RecordSearchResult,
+} from "../lib/api";
+import { providerLabel } from "./RecordsPage";
+
+const PROVIDERS: Provider[] = ["cloudflare", "dnspod", "aliyun", "huawei", "baidu", "dnscom", "rainyun", "tencentcloud"];
+
+const PROVIDER_OPTIONS: DropdownOption<string>[] = [
+  { value: "", label: "全部服务商" },
+  ...PROVIDERS.map((p) => ({ value: p, label: providerLabel(p) })),
+];
+
+export function SearchPage() {
+  const { masterPassword } = useApp();
+  const navigate = useNavigate();
+
+  const [provider, setProvider] = useState("");
+  const [domain, setDomain] = useState("");
+  const [name, setName] = useState("");
+  const [content, setContent] = useState("");
+  const [recordType, setRecordType] = useState("");
+  const [regex, setRegex] = useState(false);
+  const [busy, setBusy] = useState(false);
+  const [error, setError] = useState<string | null>(null);
+  const [result, setResult] = useState<RecordSearchResult | null>(null);
+  const [replacing, setReplacing] = useState(false);
+
+  const run = async (refresh: boolean) => {
+    if (!masterPassword) return;
+    setBusy(true);
+    setError(null);
+    try {
+      const data = await searchRecords(masterPassword, {
+        provider: (provider || null) as Provider | null,
+        domain: domain || null,
+        name: name || null,
+        content: content || null,
+        record_type: recordType || null,
+        regex,
+        refresh,
+      });
+      setResult(data);
+    } catch (e) {
+      setError(resolveErrorMessage(e));
+    } finally {
+      setBusy(false);
+    }
+  };
+
+  return (
+    <div className="space-y-8 animate-in fade-in duration-500">
+      <div className="flex flex-col md:flex-row md:items-end justify-between gap-6 border-b border-[var(--color-border)] pb-6">
+        <div>
+          <h1 className="text-4xl font-bold tracking-tight uppercase text-[var(--color-accent)] leading-none mb-2">记录搜索</h1>
+          <p className="text-sm font-medium text-[var(--color-text-secondary)] uppercase tracking-wide">
+            在所有服务商和域名中查找解析记录
+          </p>
+        </div>
+        <div className="flex items-center gap-3">
+          <Button variant="outline" onClick={() => void run(true)} loading={busy} className="border-[var(--color-border)] hover:bg-[var(--color-primary)] hover:text-white hover:border-[var(--color-primary)]">
+            <RefreshCw className="w-4 h-4 mr-2" />
+            重新拉取
+          </Button>
+          <Button variant="outline" onClick={() => setReplacing(true)} disabled={busy} className="border-[var(--color-border)] hover:bg-[var(--color-primary)] hover:text-white hover:border-[var(--color-primary)]">
+            <Replace className="w-4 h-4 mr-2" />
+            批量替换
+          </Button>
+          <Button onClick={() => void run(false)} loading={busy} className="bg-[var(--color-primary)] text-white hover:bg-black">
+            <Search className="w-4 h-4 mr-2" />
+            搜索
+          </Button>
+        </div>
+      </div>
+
+      <div className="grid grid-cols-1 md:grid-cols-3 gap-6">
+        <div className="space-y-1.5">
+          <label className="text-xs font-bold text-[var(--color-text-secondary)] uppercase tracking-wider">服务商</label>
+          <Dropdown value={provider} options={PROVIDER_OPTIONS} onChange={setProvider} className="w-full" />
+        </div>
+        <Input label="域名" value={domain} placeholder="example.com" onChange={(e) => setDomain(e.target.value)} />
+        <Input label="类型" value={recordType} placeholder="A" onChange={(e) => setRecordType(e.target.value)} />
+        <Input label="主机记录" value={name} placeholder="www" onChange={(e) => setName(e.target.value)} />
+        <Input label="记录值" value={content} placeholder="1.2.3.4" onChange={(e) => setContent(e.target.value)} />
+        <label className="flex items-center gap-2 text-xs font-bold text-[var(--color-text-secondary)] uppercase tracking-wider self-end h-10">
+          <input type="checkbox" checked={regex} onChange={(e) => setRegex(e.target.checked)} />
+          主机记录和记录值按正则匹配
+        </label>
+      </div>
+
+      {error && (
+        <div className="bg-red-50 text-red-600 p-4 border border-red-200 text-sm font-medium flex items-center gap-2">
+          <AlertCircle className="w-4 h-4" />
+          {error}
+        </div>
+      )}
+
+      {result && result.failures.length > 0 && (
+        <div className="bg-red-50 text-red-600 p-4 border border-red-200 text-xs space-y-1">
+          {result.failures.map((f, i) => (
+            <div key={i}>
+              {providerLabel(f.provider)} {f.domain_name}: {f.message}
+            </div>
+          ))}
+        </div>
+      )}
+
+      {result && (
+        <Card className="border border-[var(--color-border)] shadow-none rounded-none">
+          <div className="px-6 py-3 text-xs text-[var(--color-text-secondary)] border-b border-[var(--color-border)]">
+            在 {result.domains_searched} 个域名中找到 {result.hits.length} 条记录
+          </div>
+          <div className="overflow-x-auto">
+            <table className="w-full text-left text-sm">
+              <thead className="bg-[var(--color-bg)] border-b border-[var(--color-border)]">
+                <tr>
+                  <th className="px-6 py-4 font-bold text-[var(--color-text-secondary)] uppercase tracking-wider text-xs">服务商</th>
+                  <th className="px-6 py-4 font-bold text-[var(--color-text-secondary)] uppercase tracking-wider text-xs">域名</th>
+                  <th className="px-6 py-4 font-bold text-[var(--color-text-secondary)] uppercase tracking-wider w-[100px] text-xs">类型</th>
+                  <th className="px-6 py-4 font-bold text-[var(--color-text-secondary)] uppercase tracking-wider text-xs">主机记录</th>
+                  <th className="px-6 py-4 font-bold text-[var(--color-text-secondary)] uppercase tracking-wider text-xs">记录值</th>
+                  <th className="px-6 py-4 font-bold text-[var(--color-text-secondary)] uppercase tracking-wider w-[100px] text-xs">TTL</th>
+                </tr>
+              </thead>
+              <tbody className="divide-y divide-[var(--color-border)] bg-[var(--color-surface)]">
+                {result.hits.map((hit) => (
+                  <tr
+                    key={`${hit.record.provider}:${hit.domain_id}:${hit.record.id}`}
+                    onClick={() =>
+                      navigate(`/records/${hit.record.provider}/${encodeURIComponent(hit.domain_id)}?name=${encodeURIComponent(hit.domain_name)}`)
+                    }
+                    className="hover:bg-[var(--color-bg)] transition-colors cursor-pointer"
+                  >
+                    <td className="px-6 py-4 text-xs">{providerLabel(hit.record.provider)}</td>
+                    <td className="px-6 py-4 font-bold text-[var(--color-accent)]">{hit.domain_display_name ?? hit.domain_name}</td>
+                    <td className="px-6 py-4">
+                      <Badge variant="outline" className="font-mono font-bold border-[var(--color-text)] text-[var(--color-text)]">{hit.record.record_type}</Badge>
+                    </td>
+                    <td className="px-6 py-4">{hit.record.display_name ?? hit.record.name}</td>
+                    <td className="px-6 py-4 text-[var(--color-text)] font-mono break-all text-xs">{hit.record.content}</td>
+                    <td className="px-6 py-4 text-[var(--color-text-secondary)] font-mono text-xs">{hit.record.ttl}</td>
+                  </tr>
+                ))}
+              </tbody>
+            </table>
+          </div>
+        </Card>
+      )}
+
+      {replacing && masterPassword && (
+        <BulkReplaceModal
+          initial={{
+            provider: (provider || null) as Provider | null,
+            domain: domain || null,
+            record_type: recordType || null,
+            old_content: regex ? "" : content,
+          }}
+          onClose={() => setReplacing(false)}
+          onRun={(req) => bulkReplaceRecords(masterPassword, req)}
+          onRollback={(changes) => rollbackBulkReplace(masterPassword, changes)}
+        />
+      )}
+    </div>
+  );
+}
+
+const CHANGE_STATUS_LABELS: Record<BulkChangeStatus, string> = {
+  planned: "待修改",
+  updated: "已修改",
+  failed: "失败",
+  skipped: "未处理",
+  rolled_back: "已回滚",
+  rollback_failed: "回滚失败",
+};
+
+function BulkReplaceModal({
+  initial,
+  onClose,
+  onRun,
+  onRollback,
+}: {
+  initial: BulkReplaceRequest;
+  onClose: () => void;
+  onRun: (req: BulkReplaceRequest) => Promise<BulkReplaceResult>;
+  onRollback: (changes: BulkChange[]) => Promise<BulkChange[]>;
+}) {
+  const [oldContent, setOldContent] = useState(initial.old_content);
+  const [newContent, setNewContent] = useState("");
+  const [newTtl, setNewTtl] = useState("");
+  const [rollbackOnFailure, setRollbackOnFailure] = useState(true);
+  const [changes, setChanges] = useState<BulkChange[] | null>(null);
+  const [failures, setFailures] = useState<RecordSearchFailure[]>([]);
+  const [applied, setApplied] = useState(false);
+  const [busy, setBusy] = useState(false);
+  const [error, setError] = useState<string | null>(null);
+
+  const request = (dryRun: boolean): BulkReplaceRequest => ({
+    ...initial,
+    old_content: oldContent,
+    new_content: newContent.trim() || null,
+    new_ttl: newTtl.trim() ? Number(newTtl) : null,
+    dry_run: dryRun,
+    rollback_on_failure: rollbackOnFailure,
+  });
+
+  const run = async (action: () => Promise<void>) => {
+    setBusy(true);
+    setError(null);
+    try {
+      await action();
+    } catch (e) {
+      setError(resolveErrorMessage(e));
+    } finally {
+      setBusy(false);
+    }
+  };
+
+  // 改了条件之后要重新预览，避免执行的和看到的不一致
+  const resetPlan = () => {
+    setChanges(null);
+    setApplied(false);
+  };
+
+  const canRollback = applied && (changes ?? []).some((c) => c.status === "updated");
+
+  return (
+    <Modal title="批量替换" onClose={onClose}>
+      <div className="space-y-6">
+        {error && (
+          <div className="bg-red-50 text-red-600 p-4 border border-red-200 text-sm font-medium flex items-center gap-2">
+            <AlertCircle className="w-4 h-4" />
+            {error}
+          </div>
+        )}
+
+        <Input
+          label="原记录值（整值匹配）"
+          value={oldContent}
+          disabled={applied}
+          onChange={(e) => {
+            setOldContent(e.target.value);
+            resetPlan();
+          }}
+        />
+        <div className="grid grid-cols-2 gap-6">
+          <Input
+            label="新记录值"
+            value={newContent}
+            disabled={applied}
+            onChange={(e) => {
+              setNewContent(e.target.value);
+              resetPlan();
+            }}
+          />
+          <Input
+            label="新 TTL（可选）"
+            type="number"
+            value={newTtl}
+            disabled={applied}
+            onChange={(e) => {
+              setNewTtl(e.target.value);
+              resetPlan();
+            }}
+          />
+        </div>
+        <label className="flex items-center gap-2 text-xs font-bold text-[var(--color-text-secondary)] uppercase tracking-wider">
+          <input type="checkbox" checked={rollbackOnFailure} disabled={applied} onChange={(e) => setRollbackOnFailure(e.target.checked)} />
+          有记录失败时停止并回滚已修改的记录
+        </label>
+
+        {failures.length > 0 && (
+          <div className="bg-red-50 text-red-600 p-4 border border-red-200 text-xs space-y-1">
+            {failures.map((f, i) => (
+              <div key={i}>
+                {providerLabel(f.provider)} {f.domain_name}: {f.message}
+              </div>
+            ))}
+          </div>
+        )}
+
+        {changes && changes.length === 0 && <p className="text-sm text-[var(--color-text-secondary)]">没有匹配的记录</p>}
+
+        {changes && changes.length > 0 && (
+          <div className="border border-[var(--color-border)] divide-y divide-[var(--color-border)] text-xs max-h-96 overflow-y-auto">
+            {changes.map((c) => (
+              <div key={`${c.before.provider}:${c.domain_id}:${c.before.id}`} className="p-3 space-y-1">
+                <div className="flex items-center gap-2">
+                  <Badge variant="outline" className="font-mono">{c.before.record_type}</Badge>
+                  <span className="font-bold break-all">
+                    {c.before.display_name ?? c.before.name} · {c.domain_name}
+                  </span>
+                  <span className="ml-auto text-[var(--color-text-secondary)]">{CHANGE_STATUS_LABELS[c.status]}</span>
+                </div>
+                <div className="font-mono break-all">
+                  {c.before.content} ({c.before.ttl}) → {c.after.content} ({c.after.ttl})
+                </div>
+                {c.error && <div className="text-red-600">{c.error}</div>}
+              </div>
+            ))}
+          </div>
+        )}
+
+        <div className="flex justify-end gap-3 pt-6 border-t border-[var(--color-border)]">
+          <Button variant="secondary" onClick={onClose} className="text-xs uppercase tracking-wide">
+            关闭
+          </Button>
+          {!changes && (
+            <Button
+              onClick={() =>
+                void run(async () => {
+                  const plan = await onRun(request(true));
+                  setChanges(plan.changes);
+                  setFailures(plan.failures);
+                })
+              }
+              loading={busy}
+              className="text-xs uppercase tracking-wide"
+            >
+              预览
+            </Button>
+          )}
+          {changes && !applied && changes.length > 0 && (
+            <Button
+              onClick={() =>
+                void run(async () => {
+                  const result = await onRun({ ...request(false), plan: changes ?? [] });
+                  setChanges(result.changes);
+                  setFailures(result.failures);
+                  setApplied(true);
+                })
+              }
+              loading={busy}
+              className="text-xs uppercase tracking-wide"
+            >
+              执行替换
+            </Button>
+          )}
+          {canRollback && (
+            <Button
+              variant="destructive"
+              onClick={() => void run(async () => setChanges(await onRollback(changes ?? [])))}
+              loading={busy}
+              className="text-xs uppercase tracking-wide"
+            >
+              回滚
+            </Button>
+          )}
+        </div>
+      </div>
+    </Modal>
+  );
+}
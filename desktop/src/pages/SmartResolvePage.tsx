@@ -43,7 +43,13 @@ type DomainMatch = {
 };
 
 function normalizeDomain(input: string) {
-  return input.trim().toLowerCase().replace(/\.$/, "");
+  const trimmed = input.trim().toLowerCase().replace(/\.$/, "");
+  try {
+    // URL 解析会按 UTS-46 把中文域名转成 xn-- 形式，和后端返回的域名、主机记录保持一致
+    return new URL(`http://${trimmed}`).hostname;
+  } catch {
+    return trimmed;
+  }
 }
 
 function findDomainMatch(fullDomain: string, domains: DomainItem[]): DomainMatch | null {